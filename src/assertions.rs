@@ -0,0 +1,180 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Support for the `assert_sci_eq!` and `assert_sci_close!`
+//! macros.  Not meant to be used directly.
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::cmp::Ordering;
+
+use super::{SciValue, decimal_digits, saturating_exp_to_i64};
+
+/**
+ * Asserts that two values are numerically equal, however
+ * they are split between base and exponent.  On failure
+ * both values are shown at a common exponent, with the
+ * differing digits marked.
+ */
+#[macro_export]
+macro_rules! assert_sci_eq {
+  ($left:expr, $right:expr) => ({
+    let left  = &$left;
+    let right = &$right;
+    if left.cmp_value(right) != ::std::cmp::Ordering::Equal {
+      panic!("assertion failed: values are not equal\n{}",
+             $crate::assertions::describe_difference(left, right));
+    }
+  })
+}
+
+/**
+ * Asserts that two values agree to the given number of
+ * significant figures, counted from the larger of the two.
+ * Failures are shown as for `assert_sci_eq!`.
+ */
+#[macro_export]
+macro_rules! assert_sci_close {
+  ($left:expr, $right:expr, $sig_figs:expr) => ({
+    let left  = &$left;
+    let right = &$right;
+    if !$crate::assertions::close_to_sig_figs(left, right, $sig_figs) {
+      panic!("assertion failed: values differ within {} significant figures\n{}",
+             $sig_figs, $crate::assertions::describe_difference(left, right));
+    }
+  })
+}
+
+/**
+ * Renders the two values one above the other at a common
+ * exponent, with a line marking the digits which differ.
+ * Values which can't be brought to a common exponent are
+ * shown as stored.
+ */
+pub fn describe_difference<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(left:&SciValue<B,E>, right:&SciValue<B,E>) -> String {
+  let common = if left.e_exp < right.e_exp { left.e_exp } else { right.e_exp };
+  let (left, right) = match (left.rebalance_to_exponent(common), right.rebalance_to_exponent(common)) {
+    (Ok(left), Ok(right)) => (left, right),
+    _                     => (*left, *right)
+  };
+
+  let left_base  = render_base(left.base);
+  let right_base = render_base(right.base);
+  let width = if left_base.len() > right_base.len() { left_base.len() } else { right_base.len() };
+  let left_text  = pad_to(left_base, width);
+  let right_text = pad_to(right_base, width);
+
+  let markers: String = left_text.chars().zip(right_text.chars())
+                                 .map(|(l, r)| if l == r { ' ' } else { '^' })
+                                 .collect();
+  format!(" left: {}e{}\nright: {}e{}\n       {}",
+          left_text, saturating_exp_to_i64(left.e_exp),
+          right_text, saturating_exp_to_i64(right.e_exp),
+          markers.trim_end())
+}
+
+/**
+ * Checks that the values are equal once rounded to the
+ * given number of significant figures of the larger one.
+ */
+pub fn close_to_sig_figs<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(left:&SciValue<B,E>, right:&SciValue<B,E>, sig_figs:usize) -> bool {
+  let magnitude = match (left.order_of_magnitude(), right.order_of_magnitude()) {
+    (Some(l), Some(r)) => if l > r { l } else { r },
+    (Some(l), None)    => l,
+    (None, Some(r))    => r,
+    (None, None)       => return true
+  };
+
+  let resolution = magnitude.saturating_sub(sig_figs as i64).saturating_add(1);
+  match <E as FromPrimitive>::from_i64(resolution) {
+    Some(exp) => left.eq_at_exponent(right, exp),
+    //Only a resolution below anything storable is possible here
+    None      => left.cmp_value(right) == Ordering::Equal
+  }
+}
+
+fn render_base<B:PrimInt + FromPrimitive>(base:B) -> String {
+  let digits = decimal_digits(base);
+  if digits.is_empty() {
+    return "0".to_string();
+  }
+
+  let mut text = String::new();
+  if base < B::zero() {
+    text.push('-');
+  }
+  for digit in digits.iter() {
+    text.push((b'0' + *digit) as char);
+  }
+  text
+}
+
+fn pad_to(text:String, width:usize) -> String {
+  let mut padded = String::new();
+  for _ in text.len()..width {
+    padded.push(' ');
+  }
+  padded.push_str(&text);
+  padded
+}
+
+#[cfg(test)]
+mod test{
+  use super::{describe_difference, close_to_sig_figs};
+  use super::super::SciValue;
+
+  #[test]
+  fn equal_values_pass(){
+    assert_sci_eq!(SciValue::wrap_with_exponent(20i32, 2isize), SciValue::wrap_with_exponent(2i32, 3isize));
+    assert_sci_close!(SciValue::wrap_with_exponent(12344i32, -2isize), SciValue::wrap_with_exponent(1231i32, -1isize), 3);
+  }
+
+  #[test]
+  #[should_panic]
+  fn unequal_values_fail(){
+    assert_sci_eq!(SciValue::wrap_with_exponent(21i32, 2isize), SciValue::wrap_with_exponent(2i32, 3isize));
+  }
+
+  #[test]
+  #[should_panic]
+  fn distant_values_fail(){
+    assert_sci_close!(SciValue::wrap_with_exponent(124i32, 0isize), SciValue::wrap_with_exponent(123i32, 0isize), 3);
+  }
+
+  #[test]
+  fn difference_marks_digits(){
+    let desc = describe_difference(&SciValue::wrap_with_exponent(12345i32, -2isize),
+                                   &SciValue::wrap_with_exponent(1236i32, -1isize));
+    assert_eq!(desc.as_str(), " left: 12345e-2\nright: 12360e-2\n          ^^");
+
+    let desc = describe_difference(&SciValue::wrap_with_exponent(-5i32, 0isize),
+                                   &SciValue::wrap_with_exponent(5i32, 1isize));
+    assert_eq!(desc.as_str(), " left: -5e0\nright: 50e0\n       ^^");
+  }
+
+  #[test]
+  fn closeness(){
+    let val = SciValue::wrap_with_exponent(98765i32, 0isize);
+    assert!(close_to_sig_figs(&val, &SciValue::wrap_with_exponent(988i32, 2isize), 3));
+    assert!(!close_to_sig_figs(&val, &SciValue::wrap_with_exponent(988i32, 2isize), 4));
+    assert!(close_to_sig_figs(&SciValue::wrap(0i32), &SciValue::wrap_with_exponent(0i32, 4isize), 2));
+  }
+}
@@ -0,0 +1,233 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Versioned binary encoding of `SciValue`s, for data
+//! which has to outlive the current representation.
+//!
+//! Every encoded value starts with a four byte header:
+//!
+//! | byte | meaning                                        |
+//! |------|------------------------------------------------|
+//! | 0    | format version, currently always 1             |
+//! | 1    | flags; bit 0 is set if the mantissa is signed  |
+//! | 2    | mantissa width in bytes                        |
+//! | 3    | exponent width in bytes                        |
+//!
+//! followed by the mantissa and then the exponent, each
+//! as a little-endian two's complement integer of the
+//! width given.  Exponents are always signed.
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Display, Formatter, Error};
+use std::error::Error as StdError;
+use std::mem;
+
+use super::SciValue;
+
+const VERSION_1: u8 = 1;
+const FLAG_SIGNED_MANTISSA: u8 = 0x01;
+const HEADER_LEN: usize = 4;
+
+/**
+ * Error decoding a binary encoded value.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+  /// The input ended before the encoded value did.
+  Truncated,
+  /// The header named a format version this crate doesn't know.
+  UnknownVersion(u8),
+  /// The header gave a width of zero or more than 16 bytes.
+  InvalidWidth(u8),
+  /// The stored value doesn't fit in the requested types.
+  OutOfRange
+}
+
+impl Display for DecodeError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match *self {
+      DecodeError::Truncated               => fmtr.write_str("encoded value is truncated"),
+      DecodeError::UnknownVersion(version) => write!(fmtr, "unknown encoding version {}", version),
+      DecodeError::InvalidWidth(width)     => write!(fmtr, "invalid field width {}", width),
+      DecodeError::OutOfRange              => fmtr.write_str("encoded value doesn't fit in the target types")
+    }
+  }
+}
+
+impl StdError for DecodeError {}
+
+/**
+ * Encodes a value in version 1 of the format, at the
+ * widths of its own base and exponent types.
+ */
+pub fn encode_v1<B:PrimInt, E:PrimInt + Signed>(val:&SciValue<B,E>) -> Vec<u8> {
+  let base_width = mem::size_of::<B>();
+  let exp_width  = mem::size_of::<E>();
+  let signed     = B::min_value() < B::zero();
+
+  let mut bytes = Vec::with_capacity(HEADER_LEN + base_width + exp_width);
+  bytes.push(VERSION_1);
+  bytes.push(if signed { FLAG_SIGNED_MANTISSA } else { 0 });
+  bytes.push(base_width as u8);
+  bytes.push(exp_width as u8);
+  push_le(&mut bytes, int_bits(val.base), base_width);
+  push_le(&mut bytes, int_bits(val.e_exp), exp_width);
+  bytes
+}
+
+/**
+ * Decodes a value in any known version of the format,
+ * returning it along with the number of bytes it used.
+ *
+ * The stored widths don't need to match the requested
+ * types - values are converted, failing only if they
+ * don't fit.
+ */
+pub fn decode_any<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(bytes:&[u8]) -> Result<(SciValue<B,E>, usize), DecodeError> {
+  if bytes.len() < HEADER_LEN {
+    return Err(DecodeError::Truncated);
+  }
+  match bytes[0] {
+    VERSION_1 => decode_v1(bytes),
+    version   => Err(DecodeError::UnknownVersion(version))
+  }
+}
+
+fn decode_v1<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(bytes:&[u8]) -> Result<(SciValue<B,E>, usize), DecodeError> {
+  let signed     = bytes[1] & FLAG_SIGNED_MANTISSA != 0;
+  let base_width = check_width(bytes[2])?;
+  let exp_width  = check_width(bytes[3])?;
+
+  let total = HEADER_LEN + base_width + exp_width;
+  if bytes.len() < total {
+    return Err(DecodeError::Truncated);
+  }
+
+  let base_bits = read_le(&bytes[HEADER_LEN..HEADER_LEN + base_width]);
+  let exp_bits  = read_le(&bytes[HEADER_LEN + base_width..total]);
+
+  let base = if signed {
+    <B as FromPrimitive>::from_i128(sign_extend(base_bits, base_width))
+  } else {
+    <B as FromPrimitive>::from_u128(base_bits)
+  };
+  let exp = <E as FromPrimitive>::from_i128(sign_extend(exp_bits, exp_width));
+
+  match (base, exp) {
+    (Some(base), Some(exp)) => Ok((SciValue::wrap_with_exponent(base, exp), total)),
+    _                       => Err(DecodeError::OutOfRange)
+  }
+}
+
+fn check_width(width:u8) -> Result<usize, DecodeError> {
+  if width == 0 || width > 16 {
+    Err(DecodeError::InvalidWidth(width))
+  } else {
+    Ok(width as usize)
+  }
+}
+
+/**
+ * The two's complement bit pattern of an integer
+ * of at most 128 bits.
+ */
+fn int_bits<T:PrimInt>(val:T) -> u128 {
+  if val < T::zero() {
+    val.to_i128().expect("Couldn't convert value to i128") as u128
+  } else {
+    val.to_u128().expect("Couldn't convert value to u128")
+  }
+}
+
+fn sign_extend(bits:u128, width:usize) -> i128 {
+  let unused = 128 - 8 * width;
+  ((bits << unused) as i128) >> unused
+}
+
+fn push_le(bytes:&mut Vec<u8>, bits:u128, width:usize) {
+  for idx in 0..width {
+    bytes.push((bits >> (8 * idx)) as u8);
+  }
+}
+
+fn read_le(bytes:&[u8]) -> u128 {
+  bytes.iter().rev().fold(0u128, |bits, byte| (bits << 8) | *byte as u128)
+}
+
+#[cfg(test)]
+mod test{
+  use super::{encode_v1, decode_any, DecodeError};
+  use super::super::SciValue;
+
+  #[test]
+  fn encoding_layout(){
+    assert_eq!(encode_v1(&SciValue::wrap_with_exponent(-2i16, 3i8)), vec![1, 1, 2, 1, 0xFE, 0xFF, 3]);
+    assert_eq!(encode_v1(&SciValue::wrap_with_exponent(300u16, -1i8)), vec![1, 0, 2, 1, 0x2C, 0x01, 0xFF]);
+  }
+
+  #[test]
+  fn round_trip(){
+    let val = SciValue::wrap_with_exponent(-123456789i64, -20i32);
+    let encoded = encode_v1(&val);
+    assert_eq!(decode_any(encoded.as_slice()), Ok((val, 16)));
+
+    let unsigned = SciValue::wrap_with_exponent(18446744073709551615u64, 4i16);
+    assert_eq!(decode_any(encode_v1(&unsigned).as_slice()), Ok((unsigned, 14)));
+  }
+
+  #[test]
+  fn wide_round_trip(){
+    let val = SciValue::wrap_with_exponent(-170141183460469231731687303715884105728i128, 3i64);
+    let encoded = encode_v1(&val);
+    assert_eq!(&encoded[..4], &[1, 1, 16, 8]);
+    assert_eq!(decode_any(encoded.as_slice()), Ok((val, 28)));
+
+    let unsigned = SciValue::wrap_with_exponent(u128::MAX, -2i128);
+    assert_eq!(decode_any(encode_v1(&unsigned).as_slice()), Ok((unsigned, 36)));
+
+    let small = encode_v1(&SciValue::<i128,i32>::wrap(1));
+    let res: Result<(SciValue<i64,i32>, usize), DecodeError> = decode_any(small.as_slice());
+    assert_eq!(res, Ok((SciValue::wrap(1i64), 24)));
+  }
+
+  #[test]
+  fn widths_convert(){
+    let narrow = encode_v1(&SciValue::wrap_with_exponent(-7i8, 2i8));
+    assert_eq!(decode_any(narrow.as_slice()), Ok((SciValue::wrap_with_exponent(-7i64, 2i64), 6)));
+
+    let wide = encode_v1(&SciValue::wrap_with_exponent(1000i32, 0i32));
+    let res: Result<(SciValue<i8,i8>, usize), DecodeError> = decode_any(wide.as_slice());
+    assert_eq!(res, Err(DecodeError::OutOfRange));
+  }
+
+  #[test]
+  fn bad_input(){
+    let res: Result<(SciValue<i32,i32>, usize), DecodeError> = decode_any(&[2, 0, 4, 4]);
+    assert_eq!(res, Err(DecodeError::UnknownVersion(2)));
+    let res: Result<(SciValue<i32,i32>, usize), DecodeError> = decode_any(&[1, 1, 4, 4, 0]);
+    assert_eq!(res, Err(DecodeError::Truncated));
+    let res: Result<(SciValue<i32,i32>, usize), DecodeError> = decode_any(&[1, 1, 0, 4]);
+    assert_eq!(res, Err(DecodeError::InvalidWidth(0)));
+    let res: Result<(SciValue<i32,i32>, usize), DecodeError> = decode_any(&[1, 1, 17, 4]);
+    assert_eq!(res, Err(DecodeError::InvalidWidth(17)));
+  }
+}
@@ -0,0 +1,200 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Display, Formatter, Error};
+use std::error::Error as StdError;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+
+use super::{SciValue, ParseSciValueError, ParseLimits};
+
+/**
+ * How `load_column` reads its input.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnOptions {
+  /// Limits applied to every value parsed.
+  pub limits: ParseLimits,
+  /// Skip lines which are empty once trimmed, instead of failing on them.
+  pub skip_blank_lines: bool,
+  /// Skip lines starting with this character, after trimming.
+  pub comment_prefix: Option<char>
+}
+
+impl ColumnOptions {
+  /// Unlimited parsing, skipping blank lines, with no comments.
+  pub fn new() -> ColumnOptions {
+    ColumnOptions{limits: ParseLimits::unlimited(), skip_blank_lines: true, comment_prefix: None}
+  }
+}
+
+impl Default for ColumnOptions {
+  fn default() -> ColumnOptions {
+    ColumnOptions::new()
+  }
+}
+
+/**
+ * What went wrong reading one line of a column.
+ */
+#[derive(Debug)]
+pub enum ColumnErrorKind {
+  /// Reading from the input failed.  No further lines are read.
+  Io(io::Error),
+  /// The line wasn't a valid value; the offset is into the trimmed line.
+  Parse(ParseSciValueError)
+}
+
+/**
+ * Error reading a column, with the 1-based number of the
+ * line it happened on.
+ */
+#[derive(Debug)]
+pub struct ColumnError {
+  pub line: u64,
+  pub kind: ColumnErrorKind
+}
+
+impl Display for ColumnError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match self.kind {
+      ColumnErrorKind::Io(ref err)    => write!(fmtr, "line {}: {}", self.line, err),
+      ColumnErrorKind::Parse(ref err) => write!(fmtr, "line {}: {}", self.line, err)
+    }
+  }
+}
+
+impl StdError for ColumnError {}
+
+/**
+ * Iterator over the values of a column, one per line.
+ * See `load_column`.
+ */
+pub struct ColumnReader<R, B, E:PrimInt + Signed> {
+  reader: R,
+  options: ColumnOptions,
+  buf: String,
+  line: u64,
+  failed: bool,
+  values: PhantomData<SciValue<B,E>>
+}
+
+/**
+ * Reads a column of values from `reader`, one per line,
+ * with surrounding whitespace ignored.
+ *
+ * Lines are read into a single reused buffer, so apart
+ * from the values themselves nothing is allocated per
+ * line.  A line that fails to parse yields an error and
+ * reading carries on; an I/O error ends the iteration.
+ */
+pub fn load_column<R:BufRead, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(reader:R, options:ColumnOptions) -> ColumnReader<R,B,E> {
+  ColumnReader{reader, options, buf: String::new(), line: 0, failed: false, values: PhantomData}
+}
+
+impl<R:BufRead, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Iterator for ColumnReader<R,B,E> {
+  type Item = Result<SciValue<B,E>, ColumnError>;
+
+  fn next(&mut self) -> Option<Result<SciValue<B,E>, ColumnError>> {
+    while !self.failed {
+      self.buf.clear();
+      self.line += 1;
+      match self.reader.read_line(&mut self.buf) {
+        Ok(0)    => return None,
+        Ok(_)    => {},
+        Err(err) => {
+          self.failed = true;
+          return Some(Err(ColumnError{line: self.line, kind: ColumnErrorKind::Io(err)}));
+        }
+      }
+
+      let text = self.buf.trim();
+      if (text.is_empty() && self.options.skip_blank_lines) ||
+         self.options.comment_prefix.is_some_and(|prefix| text.starts_with(prefix)) {
+        continue;
+      }
+      let line = self.line;
+      return Some(SciValue::from_str_with_limits(text, &self.options.limits).map_err(|err| {
+        ColumnError{line, kind: ColumnErrorKind::Parse(err)}
+      }));
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::{load_column, ColumnOptions, ColumnErrorKind};
+  use super::super::{SciValue, ParseErrorKind};
+  use std::io::{self, Read, BufReader};
+
+  #[test]
+  fn reads_values(){
+    let input = "5e2\n  -21e1 \n\n# calibration\n3e-4\r\n";
+    let options = ColumnOptions{comment_prefix: Some('#'), ..ColumnOptions::new()};
+    let values: Vec<SciValue<i64,i32>> = load_column(input.as_bytes(), options).map(|val| val.unwrap()).collect();
+    assert_eq!(values, vec![SciValue::wrap_with_exponent(5, 2),
+                            SciValue::wrap_with_exponent(-21, 1),
+                            SciValue::wrap_with_exponent(3, -4)]);
+  }
+
+  #[test]
+  fn reports_lines(){
+    let input = "1e0\n2x\n\n3e0\n";
+    let options = ColumnOptions{skip_blank_lines: false, ..ColumnOptions::new()};
+    let results: Vec<_> = load_column::<_, i32, i32>(input.as_bytes(), options).collect();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap(), &SciValue::wrap(1));
+    match results[1] {
+      Err(ref err) => {
+        assert_eq!(err.line, 2);
+        match err.kind {
+          ColumnErrorKind::Parse(ref parse) => assert_eq!((parse.kind, parse.offset), (ParseErrorKind::InvalidDigit, 1)),
+          ColumnErrorKind::Io(_)            => panic!("expected a parse error")
+        }
+        assert_eq!(format!("{}", err), "line 2: invalid digit at offset 1");
+      },
+      Ok(_) => panic!("expected an error")
+    }
+    assert_eq!(results[2].as_ref().err().map(|err| err.line), Some(3));
+    assert_eq!(results[3].as_ref().unwrap(), &SciValue::wrap(3));
+  }
+
+  struct Failing;
+
+  impl Read for Failing {
+    fn read(&mut self, _:&mut [u8]) -> io::Result<usize> {
+      Err(io::Error::new(io::ErrorKind::Other, "disk on fire"))
+    }
+  }
+
+  #[test]
+  fn stops_on_io_error(){
+    let mut column = load_column::<_, i32, i32>(BufReader::new(Failing), ColumnOptions::new());
+    match column.next() {
+      Some(Err(ref err)) => assert_eq!(err.line, 1),
+      _                  => panic!("expected an I/O error")
+    }
+    assert!(column.next().is_none());
+  }
+}
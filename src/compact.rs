@@ -0,0 +1,191 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error};
+
+use super::{SciValue, RoundingMode, base_constant, exp_constant};
+
+const MANTISSA_BITS: u32 = 18;
+const EXPONENT_BITS: u32 = 6;
+const MANTISSA_MAX: i64 = (1 << (MANTISSA_BITS - 1)) - 1;
+const MANTISSA_MIN: i64 = -(1 << (MANTISSA_BITS - 1));
+const EXPONENT_MAX: i64 = (1 << (EXPONENT_BITS - 1)) - 1;
+const EXPONENT_MIN: i64 = -(1 << (EXPONENT_BITS - 1));
+
+/**
+ * Compact storage for values which only need around five
+ * significant digits, packed into the low 24 bits of a u32:
+ * an 18 bit two's complement mantissa in bits 0-17 and a
+ * 6 bit two's complement exponent in bits 18-23.
+ *
+ * Mantissas range over +/-131071 and exponents over
+ * -32 to 31.  Encoding never fails - values are rounded
+ * and saturated to fit.
+ */
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SciValue24(u32);
+
+impl SciValue24 {
+  pub fn from_bits(bits:u32) -> SciValue24 {
+    SciValue24(bits & 0x00FFFFFF)
+  }
+
+  pub fn to_bits(&self) -> u32 {
+    self.0
+  }
+
+  pub fn mantissa(&self) -> i32 {
+    ((self.0 << (32 - MANTISSA_BITS)) as i32) >> (32 - MANTISSA_BITS)
+  }
+
+  pub fn exponent(&self) -> i32 {
+    ((self.0 << (32 - MANTISSA_BITS - EXPONENT_BITS)) as i32) >> (32 - EXPONENT_BITS)
+  }
+
+  /**
+   * Packs a value.  Digits which don't fit in the mantissa
+   * are rounded away (half to even), values too small for
+   * the exponent range round to zero, and values too large
+   * saturate to the largest magnitude of the same sign.
+   */
+  pub fn encode<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(val:&SciValue<B,E>) -> SciValue24 {
+    let type_b_10 = base_constant::<B>(10, "encode_sci24");
+    let min_exp = exp_constant::<E>(EXPONENT_MIN, "encode_sci24");
+    let max_exp = exp_constant::<E>(EXPONENT_MAX, "encode_sci24");
+
+    let reduced = val.reduce();
+    if reduced.base == B::zero() {
+      return SciValue24(0);
+    }
+    let negative = reduced.base < B::zero();
+
+    //Round from the reduced value each time, to avoid
+    //rounding twice
+    let mut target  = if reduced.e_exp < min_exp { min_exp } else { reduced.e_exp };
+    let mut rounded = reduced.round_to_exponent(target, RoundingMode::HalfEven);
+    while mantissa_of(rounded.base).is_none() {
+      target = match target.checked_add(&E::one()) {
+        Some(next) => next,
+        None       => return SciValue24::saturated(negative)
+      };
+      rounded = reduced.round_to_exponent(target, RoundingMode::HalfEven);
+    }
+
+    //Shift any excess exponent into the mantissa
+    while rounded.e_exp > max_exp {
+      let scaled = rounded.base.checked_mul(&type_b_10);
+      match scaled {
+        Some(base) if mantissa_of(base).is_some() => {
+          rounded = SciValue::wrap_with_exponent(base, rounded.e_exp - E::one());
+        }
+        _ => return SciValue24::saturated(negative)
+      }
+    }
+
+    let mantissa = mantissa_of(rounded.base).unwrap();
+    let exponent = rounded.e_exp.to_i64().unwrap();
+    if mantissa == 0 {
+      return SciValue24(0);
+    }
+    SciValue24::pack(mantissa, exponent)
+  }
+
+  /**
+   * Unpacks the value, returning None if the mantissa
+   * doesn't fit in the requested base type.
+   */
+  pub fn decode<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(&self) -> Option<SciValue<B,E>> {
+    match (<B as FromPrimitive>::from_i32(self.mantissa()), <E as FromPrimitive>::from_i32(self.exponent())) {
+      (Some(base), Some(exp)) => Some(SciValue::wrap_with_exponent(base, exp)),
+      _                       => None
+    }
+  }
+
+  fn saturated(negative:bool) -> SciValue24 {
+    SciValue24::pack(if negative { -MANTISSA_MAX } else { MANTISSA_MAX }, EXPONENT_MAX)
+  }
+
+  fn pack(mantissa:i64, exponent:i64) -> SciValue24 {
+    let mantissa_bits = (mantissa as u32) & ((1 << MANTISSA_BITS) - 1);
+    let exponent_bits = (exponent as u32) & ((1 << EXPONENT_BITS) - 1);
+    SciValue24(exponent_bits << MANTISSA_BITS | mantissa_bits)
+  }
+}
+
+impl Debug for SciValue24 {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    fmtr.write_str(format!("SciValue24{}mantissa : {}, exponent : {}{}", "{", self.mantissa(), self.exponent(), "}").as_str())
+  }
+}
+
+fn mantissa_of<B:PrimInt>(base:B) -> Option<i64> {
+  base.to_i64().and_then(|val| if (MANTISSA_MIN..=MANTISSA_MAX).contains(&val) { Some(val) } else { None })
+}
+
+#[cfg(test)]
+mod test{
+  use super::SciValue24;
+  use super::super::SciValue;
+
+  #[test]
+  fn exact_values(){
+    let packed = SciValue24::encode(&SciValue::wrap_with_exponent(-12345i64, -3isize));
+    assert_eq!(packed.mantissa(), -12345);
+    assert_eq!(packed.exponent(), -3);
+    assert_eq!(packed.decode(), Some(SciValue::wrap_with_exponent(-12345i64, -3isize)));
+    assert_eq!(SciValue24::from_bits(packed.to_bits()), packed);
+
+    let reduced = SciValue24::encode(&SciValue::wrap_with_exponent(500i32, 0isize));
+    assert_eq!(reduced.decode(), Some(SciValue::wrap_with_exponent(5i32, 2isize)));
+  }
+
+  #[test]
+  fn rounds_long_mantissas(){
+    let packed = SciValue24::encode(&SciValue::wrap_with_exponent(123456789i64, 0isize));
+    assert_eq!(packed.decode(), Some(SciValue::wrap_with_exponent(123457i64, 3isize)));
+  }
+
+  #[test]
+  fn saturates(){
+    let huge = SciValue24::encode(&SciValue::wrap_with_exponent(4i32, 40isize));
+    assert_eq!(huge.mantissa(), 131071);
+    assert_eq!(huge.exponent(), 31);
+
+    let shifted = SciValue24::encode(&SciValue::wrap_with_exponent(4i32, 35isize));
+    assert_eq!(shifted.decode(), Some(SciValue::wrap_with_exponent(40000i64, 31isize)));
+
+    let tiny = SciValue24::encode(&SciValue::wrap_with_exponent(4i32, -40isize));
+    assert_eq!(tiny.to_bits(), 0);
+
+    let negative = SciValue24::encode(&SciValue::wrap_with_exponent(-7i32, 90isize));
+    assert_eq!(negative.mantissa(), -131071);
+  }
+
+  #[test]
+  fn narrow_decode(){
+    let packed = SciValue24::encode(&SciValue::wrap_with_exponent(1000i32, 1isize));
+    assert_eq!(packed.decode::<i8,isize>(), Some(SciValue::wrap_with_exponent(1i8, 4isize)));
+    let wide = SciValue24::encode(&SciValue::wrap_with_exponent(1001i32, 1isize));
+    assert_eq!(wide.decode::<i8,isize>(), None);
+  }
+}
@@ -0,0 +1,213 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Golden vectors and round trip checks, enabled with the
+//! `conformance` feature.
+//!
+//! Downstream crates can run these against their own
+//! serialization setup - a JSON library and its options,
+//! say - to verify values come back out exactly as they
+//! went in:
+//!
+//! ```ignore
+//! scinotation::conformance::check_codec_round_trips(
+//!   |val| serde_json::to_string(val).map_err(|err| err.to_string()),
+//!   |json| serde_json::from_str(json).map_err(|err| err.to_string())).unwrap();
+//! ```
+
+use std::fmt::{Display, Formatter, Error};
+use std::error::Error as StdError;
+
+use super::SciValue;
+
+/**
+ * A text notation the crate can parse.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+  /// The `FromStr` notation, such as `-21e1`.
+  Standard,
+  /// Rust source literals, for `SciValue::from_rust_literal`.
+  RustLiteral
+}
+
+/**
+ * A piece of text along with the exact value it must
+ * parse to.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoldenVector {
+  pub notation: Notation,
+  pub text: &'static str,
+  pub base: i64,
+  pub exp: i32
+}
+
+const fn vector(notation:Notation, text:&'static str, base:i64, exp:i32) -> GoldenVector {
+  GoldenVector{notation, text, base, exp}
+}
+
+/**
+ * The golden vectors, covering signs, exponent markers,
+ * the extremes of `SciValue<i64,i32>`, and stored forms
+ * that reduction would otherwise change.
+ */
+pub const GOLDEN_VECTORS: &[GoldenVector] = &[
+  vector(Notation::Standard,    "0",                                  0,        0),
+  vector(Notation::Standard,    "0e5",                                0,        5),
+  vector(Notation::Standard,    "-0",                                 0,        0),
+  vector(Notation::Standard,    "42",                                 42,       0),
+  vector(Notation::Standard,    "5e2",                                5,        2),
+  vector(Notation::Standard,    "500e0",                              500,      0),
+  vector(Notation::Standard,    "-21e1",                              -21,      1),
+  vector(Notation::Standard,    "3E-4",                               3,        -4),
+  vector(Notation::Standard,    "+7e+3",                              7,        3),
+  vector(Notation::Standard,    "007e1",                              7,        1),
+  vector(Notation::Standard,    "9223372036854775807e-2147483648",    i64::MAX, i32::MIN),
+  vector(Notation::Standard,    "-9223372036854775808e2147483647",    i64::MIN, i32::MAX),
+  vector(Notation::RustLiteral, "1_000_000u64",                       1000000,  0),
+  vector(Notation::RustLiteral, "2.5e3_f64",                          25,       2),
+  vector(Notation::RustLiteral, "0.000_125",                          125,      -6),
+  vector(Notation::RustLiteral, "7.",                                 7,        0),
+  vector(Notation::RustLiteral, "1E-3f32",                            1,        -3)
+];
+
+/**
+ * A golden vector which didn't survive a check.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceFailure {
+  pub vector: GoldenVector,
+  /// The step of the round trip that failed.
+  pub stage: &'static str,
+  pub detail: String
+}
+
+impl Display for ConformanceFailure {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    write!(fmtr, "{} failed for {:?}: {}", self.stage, self.vector.text, self.detail)
+  }
+}
+
+impl StdError for ConformanceFailure {}
+
+impl GoldenVector {
+  /**
+   * The exact value the text stands for.
+   */
+  pub fn value(&self) -> SciValue<i64,i32> {
+    SciValue::wrap_with_exponent(self.base, self.exp)
+  }
+
+  fn fail(&self, stage:&'static str, detail:String) -> ConformanceFailure {
+    ConformanceFailure{vector: *self, stage, detail}
+  }
+
+  fn parse(&self) -> Result<SciValue<i64,i32>, ConformanceFailure> {
+    let parsed = match self.notation {
+      Notation::Standard    => self.text.parse::<SciValue<i64,i32>>(),
+      Notation::RustLiteral => SciValue::from_rust_literal(self.text)
+    };
+    match parsed {
+      Ok(val) if val == self.value() => Ok(val),
+      Ok(val)                        => Err(self.fail("parse", format!("got {}, expected {}", val, self.value()))),
+      Err(err)                       => Err(self.fail("parse", err.to_string()))
+    }
+  }
+}
+
+/**
+ * Checks every golden vector parses to its exact value,
+ * and that the value's `Display` output parses back to
+ * the same stored form.
+ */
+pub fn check_text_round_trips() -> Result<(), ConformanceFailure> {
+  for golden in GOLDEN_VECTORS.iter() {
+    let val = golden.parse()?;
+    let printed = val.to_string();
+    match printed.parse::<SciValue<i64,i32>>() {
+      Ok(reparsed) if reparsed == val => (),
+      Ok(reparsed)                    => return Err(golden.fail("reparse", format!("{} came back as {}", printed, reparsed))),
+      Err(err)                        => return Err(golden.fail("reparse", format!("{}: {}", printed, err)))
+    }
+  }
+  Ok(())
+}
+
+/**
+ * Checks every golden vector survives a round trip
+ * through a caller supplied encoding: parsed, encoded,
+ * decoded and then compared by stored form, so even
+ * values like `500e0` and `5e2` must stay distinct.
+ */
+pub fn check_codec_round_trips<T, Enc, Dec>(encode:Enc, decode:Dec) -> Result<(), ConformanceFailure>
+    where Enc:Fn(&SciValue<i64,i32>) -> Result<T, String>,
+          Dec:Fn(&T) -> Result<SciValue<i64,i32>, String> {
+  for golden in GOLDEN_VECTORS.iter() {
+    let val = golden.parse()?;
+    let encoded = encode(&val).map_err(|err| golden.fail("encode", err))?;
+    match decode(&encoded) {
+      Ok(decoded) if decoded == val => (),
+      Ok(decoded)                   => return Err(golden.fail("decode", format!("came back as {}", decoded))),
+      Err(err)                      => return Err(golden.fail("decode", err))
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod test{
+  use super::{check_text_round_trips, check_codec_round_trips, GOLDEN_VECTORS};
+  use super::super::binary::{encode_v1, decode_any};
+
+  #[test]
+  fn text_round_trips(){
+    assert_eq!(check_text_round_trips(), Ok(()));
+  }
+
+  #[test]
+  fn codec_round_trips(){
+    assert_eq!(check_codec_round_trips(|val| Ok(encode_v1(val)),
+                                       |bytes| decode_any(bytes.as_slice()).map(|(val, _)| val).map_err(|err| err.to_string())),
+               Ok(()));
+
+    //A codec which reduces values is caught
+    let failure = check_codec_round_trips(|val| Ok(val.reduce()), |val| Ok(*val)).unwrap_err();
+    assert_eq!((failure.vector, failure.stage), (GOLDEN_VECTORS[1], "decode"));
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde_round_trips(){
+    use serde_test::{Token, Configure, assert_tokens};
+    for golden in GOLDEN_VECTORS.iter() {
+      assert_tokens(&golden.value().readable(), &[
+        Token::Struct{name: "SciValue", len: 2},
+        Token::Str("base"),
+        Token::I64(golden.base),
+        Token::Str("exp"),
+        Token::I32(golden.exp),
+        Token::StructEnd
+      ]);
+    }
+  }
+}
@@ -0,0 +1,227 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Serde adapters which write values as text fields, so
+//! every cell in a column of a CSV export (or any other
+//! text format) uses one notation, whatever the values'
+//! base and exponent split:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Row {
+//!   #[serde(with = "scinotation::csv::plain")]
+//!   price: SciValue<i64,i32>,
+//!   #[serde(with = "scinotation::csv::notation")]
+//!   concentration: SciValue<i64,i32>
+//! }
+//! ```
+//!
+//! `plain` writes decimals such as `-0.015` or `1500`, which
+//! spreadsheets read as numbers without reinterpretation.
+//! `notation` writes the reduced e-notation, such as
+//! `-15e-3`.  Both write the reduced value, so `1500e-2`
+//! goes out as `15` or `15e0`.
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use serde::{Deserialize, Deserializer, Serializer};
+use serde::de::Error as DeError;
+use serde::ser::Error as SerError;
+
+use super::{SciValue, ParseSciValueError, FormatOptions, decimal_digits, saturating_exp_to_i64};
+
+/// The most zeros `plain` pads a value with, on either
+/// side of the point.  This covers the whole `f64` range,
+/// while refusing to write out a huge exponent in full.
+pub const MAX_PLAIN_ZEROS: usize = 400;
+
+/**
+ * Plain decimal fields, such as `-0.015`.
+ */
+pub mod plain {
+  use num_traits::{PrimInt, Signed, FromPrimitive};
+  use serde::{Deserializer, Serializer};
+
+  use super::super::SciValue;
+
+  /**
+   * Writes the value as a plain decimal.  Fails if that
+   * would take more than `MAX_PLAIN_ZEROS` zeros.
+   */
+  pub fn serialize<S, B, E>(val:&SciValue<B,E>, serializer:S) -> Result<S::Ok, S::Error>
+      where S:Serializer, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive {
+    super::serialize_plain(val, serializer)
+  }
+
+  /**
+   * Reads a plain decimal, with an optional sign and
+   * point.  Exponents aren't accepted.
+   */
+  pub fn deserialize<'de, D, B, E>(deserializer:D) -> Result<SciValue<B,E>, D::Error>
+      where D:Deserializer<'de>, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive {
+    super::deserialize_plain(deserializer)
+  }
+}
+
+/**
+ * E-notation fields, such as `-15e-3`.
+ */
+pub mod notation {
+  use num_traits::{PrimInt, Signed, FromPrimitive};
+  use serde::{Deserializer, Serializer};
+
+  use super::super::SciValue;
+
+  /**
+   * Writes the reduced value in e-notation.
+   */
+  pub fn serialize<S, B, E>(val:&SciValue<B,E>, serializer:S) -> Result<S::Ok, S::Error>
+      where S:Serializer, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive {
+    super::serialize_notation(val, serializer)
+  }
+
+  /**
+   * Reads e-notation as `from_str` does.
+   */
+  pub fn deserialize<'de, D, B, E>(deserializer:D) -> Result<SciValue<B,E>, D::Error>
+      where D:Deserializer<'de>, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive {
+    super::deserialize_notation(deserializer)
+  }
+}
+
+fn serialize_plain<S, B, E>(val:&SciValue<B,E>, serializer:S) -> Result<S::Ok, S::Error>
+    where S:Serializer, B:PrimInt + FromPrimitive, E:PrimInt + Signed {
+  let reduced = val.reduce();
+  let digits: String = decimal_digits(reduced.base).iter().map(|digit| (b'0' + digit) as char).collect();
+  if digits.is_empty() {
+    return serializer.serialize_str("0");
+  }
+  let sign = if reduced.base < B::zero() { "-" } else { "" };
+
+  let exp = saturating_exp_to_i64(reduced.e_exp);
+  let zeros = usize::try_from(exp.unsigned_abs()).unwrap_or(usize::MAX).saturating_sub(if exp < 0 { digits.len() } else { 0 });
+  if zeros > MAX_PLAIN_ZEROS {
+    return Err(S::Error::custom("value needs too many zeros to write as a plain decimal"));
+  }
+  let text = if exp >= 0 {
+    format!("{}{}{}", sign, digits, "0".repeat(zeros))
+  } else if zeros > 0 {
+    format!("{}0.{}{}", sign, "0".repeat(zeros), digits)
+  } else {
+    let point = digits.len() - exp.unsigned_abs() as usize;
+    let (int_part, frac_part) = digits.split_at(point);
+    format!("{}{}.{}", sign, if int_part.is_empty() { "0" } else { int_part }, frac_part)
+  };
+  serializer.serialize_str(&text)
+}
+
+fn deserialize_plain<'de, D, B, E>(deserializer:D) -> Result<SciValue<B,E>, D::Error>
+    where D:Deserializer<'de>, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive {
+  let text = String::deserialize(deserializer)?;
+  if text.contains(['e', 'E']) {
+    return Err(D::Error::custom(format!("expected a plain decimal, found `{}`", text)));
+  }
+  let parsed: Result<SciValue<B,E>, ParseSciValueError> = match text.find('.') {
+    Some(pos) => {
+      let frac = &text[pos + 1..];
+      format!("{}{}e-{}", &text[..pos], frac, frac.len()).parse()
+    },
+    None      => text.parse()
+  };
+  //Offsets in the error are into the rewritten text, so
+  //they aren't passed on
+  parsed.map_err(|_| D::Error::custom(format!("invalid plain decimal `{}`", text)))
+}
+
+fn serialize_notation<S, B, E>(val:&SciValue<B,E>, serializer:S) -> Result<S::Ok, S::Error>
+    where S:Serializer, B:PrimInt + FromPrimitive, E:PrimInt + Signed {
+  serializer.serialize_str(&val.format_with(&FormatOptions::new()))
+}
+
+fn deserialize_notation<'de, D, B, E>(deserializer:D) -> Result<SciValue<B,E>, D::Error>
+    where D:Deserializer<'de>, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive {
+  let text = String::deserialize(deserializer)?;
+  text.parse().map_err(|err:ParseSciValueError| D::Error::custom(format!("invalid e-notation `{}`: {}", text, err)))
+}
+
+#[cfg(test)]
+mod test{
+  use serde::{Serialize, Deserialize};
+  use serde::de::value::{StrDeserializer, Error};
+  use serde::de::IntoDeserializer;
+  use serde_test::{assert_tokens, assert_ser_tokens, assert_ser_tokens_error, Token};
+  use super::super::SciValue;
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct Row {
+    #[serde(with = "super::plain")]
+    price: SciValue<i64,i32>,
+    #[serde(with = "super::notation")]
+    concentration: SciValue<i64,i32>
+  }
+
+  #[derive(Serialize)]
+  struct Plain(#[serde(with = "super::plain")] SciValue<i64,i32>);
+
+  fn plain(text:&str) -> Result<SciValue<i64,i32>, Error> {
+    let deserializer: StrDeserializer<Error> = text.into_deserializer();
+    super::plain::deserialize(deserializer)
+  }
+
+  #[test]
+  fn column_notations(){
+    //Written in reduced form, whatever the split
+    let row = Row{price: SciValue::wrap_with_exponent(-1500, -5), concentration: SciValue::wrap_with_exponent(1500, -5)};
+    assert_ser_tokens(&row, &[
+      Token::Struct{name: "Row", len: 2},
+      Token::Str("price"),
+      Token::Str("-0.015"),
+      Token::Str("concentration"),
+      Token::Str("15e-3"),
+      Token::StructEnd
+    ]);
+    assert_tokens(&Row{price: SciValue::wrap_with_exponent(-15, -3), concentration: SciValue::wrap_with_exponent(15, -3)}, &[
+      Token::Struct{name: "Row", len: 2},
+      Token::Str("price"),
+      Token::Str("-0.015"),
+      Token::Str("concentration"),
+      Token::Str("15e-3"),
+      Token::StructEnd
+    ]);
+  }
+
+  #[test]
+  fn plain_decimals(){
+    for &(base, exp, text) in [(1234, -2, "12.34"), (12, 3, "12000"), (-5, -1, "-0.5"), (0, 9, "0"), (7, 0, "7")].iter() {
+      let val = SciValue::wrap_with_exponent(base, exp);
+      assert_ser_tokens(&Plain(val), &[Token::NewtypeStruct{name: "Plain"}, Token::Str(text)]);
+      assert_eq!(plain(text).map(|read| read.reduce()), Ok(val.reduce()));
+    }
+    assert_ser_tokens_error(&Plain(SciValue::wrap_with_exponent(1, 401)), &[Token::NewtypeStruct{name: "Plain"}],
+                            "value needs too many zeros to write as a plain decimal");
+
+    assert_eq!(plain("-.25"), Ok(SciValue::wrap_with_exponent(-25, -2)));
+    assert_eq!(plain("007.50"), Ok(SciValue::wrap_with_exponent(750, -2)));
+    assert_eq!(plain("1e5").unwrap_err().to_string(), "expected a plain decimal, found `1e5`");
+    assert_eq!(plain("1.2.3").unwrap_err().to_string(), "invalid plain decimal `1.2.3`");
+    assert!(plain("").is_err());
+  }
+}
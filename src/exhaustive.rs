@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Exhaustive checks of the arithmetic on `SciValue<i8,i8>`,
+//! enabled with the `exhaustive` feature.
+//!
+//! Every pair of values with an exponent in `EXPONENTS` is
+//! run through the checked operators and compared against
+//! an `i128` oracle, which is exact for all of them.  This
+//! is several million pairs, so is best run in release:
+//!
+//! ```text
+//! cargo test --release --features exhaustive exhaustive
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use super::{SciValue, PrecisionLoss};
+
+const EXPONENTS: RangeInclusive<i8> = -8..=8;
+
+fn values() -> Vec<SciValue<i8,i8>> {
+  EXPONENTS.flat_map(|exp| (i8::MIN..=i8::MAX).map(move |base| SciValue::wrap_with_exponent(base, exp))).collect()
+}
+
+/// The value as a multiple of 10^at, for `at` no more than its exponent.
+fn scaled(base:i128, exp:i8, at:i8) -> i128 {
+  base * 10i128.pow((exp - at) as u32)
+}
+
+/// Whether `val` is exactly `mantissa * 10^exp`.
+fn equals(val:&SciValue<i8,i8>, mantissa:i128, exp:i8) -> bool {
+  let at = val.e_exp.min(exp);
+  scaled(val.base as i128, val.e_exp, at) == scaled(mantissa, exp, at)
+}
+
+fn fits(val:i128) -> bool {
+  i8::try_from(val).is_ok()
+}
+
+/// Whether `checked_add` and `checked_sub` can bring `val` down to exponent `at`.
+fn matches_to(val:&SciValue<i8,i8>, at:i8) -> bool {
+  let shift = (val.e_exp - at) as u32;
+  fits(10i128.pow(shift)) && fits(scaled(val.base as i128, val.e_exp, at))
+}
+
+/// Whether the exact value has a reduced form with an `i8` base.
+fn representable(mut mantissa:i128) -> bool {
+  while mantissa != 0 && mantissa % 10 == 0 {
+    mantissa /= 10;
+  }
+  fits(mantissa)
+}
+
+fn check_sum(lhs:&SciValue<i8,i8>, rhs:&SciValue<i8,i8>, negate:bool) {
+  let at = lhs.e_exp.min(rhs.e_exp);
+  let rhs_exact = scaled(rhs.base as i128, rhs.e_exp, at);
+  let exact = scaled(lhs.base as i128, lhs.e_exp, at) + if negate { -rhs_exact } else { rhs_exact };
+  let op = if negate { "-" } else { "+" };
+
+  let checked = if negate { lhs.checked_sub(rhs) } else { lhs.checked_add(rhs) };
+  let fits_matched = matches_to(lhs, at) && matches_to(rhs, at) && fits(exact);
+  match checked {
+    Some(res) => assert!(fits_matched && res.e_exp == at && equals(&res, exact, at),
+                         "checked {:?} {} {:?} gave {:?}", lhs, op, rhs, res),
+    None      => assert!(!fits_matched, "checked {:?} {} {:?} failed", lhs, op, rhs)
+  }
+  if let Some(res) = checked {
+    let plain = if negate { *lhs - *rhs } else { *lhs + *rhs };
+    assert!(plain == res, "{:?} {} {:?} gave {:?}", lhs, op, rhs, plain);
+  }
+
+  if !negate {
+    match lhs.add_exact(rhs) {
+      Ok(res)  => assert!(equals(&res, exact, at), "{:?}.add_exact({:?}) gave {:?}", lhs, rhs, res),
+      Err(err) => assert!(err == PrecisionLoss::Truncation && !representable(exact),
+                          "{:?}.add_exact({:?}) failed with {:?}", lhs, rhs, err)
+    }
+  }
+}
+
+fn check_product(lhs:&SciValue<i8,i8>, rhs:&SciValue<i8,i8>) {
+  let exact = lhs.base as i128 * rhs.base as i128;
+  let exp = lhs.e_exp + rhs.e_exp;
+  match lhs.checked_mul(rhs) {
+    Some(res) => assert!(fits(exact) && res.e_exp == exp && equals(&res, exact, exp) && *lhs * *rhs == res,
+                         "checked {:?} * {:?} gave {:?}", lhs, rhs, res),
+    None      => assert!(!fits(exact), "checked {:?} * {:?} failed", lhs, rhs)
+  }
+}
+
+fn check_comparison(lhs:&SciValue<i8,i8>, rhs:&SciValue<i8,i8>) {
+  let at = lhs.e_exp.min(rhs.e_exp);
+  let exact = scaled(lhs.base as i128, lhs.e_exp, at).cmp(&scaled(rhs.base as i128, rhs.e_exp, at));
+  assert!(lhs.cmp_value(rhs) == exact, "{:?}.cmp_value({:?})", lhs, rhs);
+  assert!(lhs.cmp_value(rhs) == Ordering::Equal || lhs.in_range(..*rhs) == (exact == Ordering::Less),
+          "{:?}.in_range(..{:?})", lhs, rhs);
+}
+
+#[test]
+fn exhaustive_i8_arithmetic(){
+  let all = values();
+  for lhs in all.iter() {
+    for rhs in all.iter() {
+      check_sum(lhs, rhs, false);
+      check_sum(lhs, rhs, true);
+      check_product(lhs, rhs);
+      check_comparison(lhs, rhs);
+    }
+  }
+}
@@ -0,0 +1,258 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error};
+use std::ops::{Add, Sub, Mul};
+use std::cmp::Ordering;
+
+use super::{SciValue, FloatConversion};
+
+/**
+ * A value computed in `f64` arithmetic for as long as that
+ * stays exact, and as a `SciValue` from the first result
+ * which wouldn't be.
+ *
+ * A float is only kept if it converts exactly to a
+ * `SciValue<B,E>`, and every float result is checked with
+ * an error-free transformation (the rounding error of a
+ * sum, or a fused multiply-add for a product), so no
+ * precision is ever lost to the fast path.  Once a result
+ * needs exact arithmetic, everything computed from it
+ * stays exact.
+ */
+pub struct FastSci<B, E:PrimInt + Signed> {
+  repr: Repr<B,E>
+}
+
+enum Repr<B, E:PrimInt + Signed> {
+  Float(f64),
+  Exact(SciValue<B,E>)
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> FastSci<B,E> {
+  /**
+   * Starts on the fast path if the value is exactly an
+   * `f64`.
+   */
+  pub fn new(val:SciValue<B,E>) -> FastSci<B,E> {
+    match val.to_f64_with_status() {
+      (float, FloatConversion::Exact) => FastSci{repr: Repr::Float(float)},
+      _                               => FastSci{repr: Repr::Exact(val)}
+    }
+  }
+
+  /**
+   * Starts on the fast path from a float, which must
+   * convert exactly as `SciValue::from_f64_exact` requires.
+   */
+  pub fn from_f64(val:f64) -> Option<FastSci<B,E>> {
+    SciValue::<B,E>::from_f64_exact(val).ok().map(|_| FastSci{repr: Repr::Float(val)})
+  }
+
+  /// Whether the value is still computed in `f64`.
+  pub fn is_fast(&self) -> bool {
+    match self.repr {
+      Repr::Float(_) => true,
+      Repr::Exact(_) => false
+    }
+  }
+
+  /// The value, exactly.
+  pub fn to_sci(&self) -> SciValue<B,E> {
+    match self.repr {
+      Repr::Float(float) => SciValue::from_f64_exact(float).expect("Fast path floats always convert exactly"),
+      Repr::Exact(val)   => val
+    }
+  }
+
+  /**
+   * Adds the values, returning None only if the exact sum
+   * overflows.
+   */
+  pub fn checked_add(&self, rhs:&FastSci<B,E>) -> Option<FastSci<B,E>> {
+    if let (Repr::Float(lhs), Repr::Float(rhs)) = (&self.repr, &rhs.repr) {
+      if let Some(sum) = exact_sum(*lhs, *rhs) {
+        return Some(sum);
+      }
+    }
+    self.to_sci().checked_add(&rhs.to_sci()).map(|sum| FastSci{repr: Repr::Exact(sum)})
+  }
+
+  /**
+   * Subtracts `rhs`, returning None only if the exact
+   * difference overflows.
+   */
+  pub fn checked_sub(&self, rhs:&FastSci<B,E>) -> Option<FastSci<B,E>> {
+    if let (Repr::Float(lhs), Repr::Float(rhs)) = (&self.repr, &rhs.repr) {
+      if let Some(diff) = exact_sum(*lhs, -*rhs) {
+        return Some(diff);
+      }
+    }
+    self.to_sci().checked_sub(&rhs.to_sci()).map(|diff| FastSci{repr: Repr::Exact(diff)})
+  }
+
+  /**
+   * Multiplies the values, returning None only if the
+   * exact product overflows.
+   */
+  pub fn checked_mul(&self, rhs:&FastSci<B,E>) -> Option<FastSci<B,E>> {
+    if let (Repr::Float(lhs), Repr::Float(rhs)) = (&self.repr, &rhs.repr) {
+      let product = lhs * rhs;
+      //The fused multiply-add gives the rounding error of
+      //the product exactly, including on underflow
+      if product.is_finite() && lhs.mul_add(*rhs, -product) == 0.0 {
+        if let Some(fast) = FastSci::from_f64(product) {
+          return Some(fast);
+        }
+      }
+    }
+    self.to_sci().checked_mul(&rhs.to_sci()).map(|product| FastSci{repr: Repr::Exact(product)})
+  }
+
+  /// Compares the values numerically.
+  pub fn cmp_value(&self, rhs:&FastSci<B,E>) -> Ordering {
+    match (&self.repr, &rhs.repr) {
+      (Repr::Float(lhs), Repr::Float(rhs)) => lhs.partial_cmp(rhs).expect("Fast path floats are never NaN"),
+      _                                    => self.to_sci().cmp_value(&rhs.to_sci())
+    }
+  }
+}
+
+/**
+ * The sum of two floats if it's exact and stays on the
+ * fast path.  The rounding error of the sum is found by
+ * Knuth's TwoSum.
+ */
+fn exact_sum<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(lhs:f64, rhs:f64) -> Option<FastSci<B,E>> {
+  let sum = lhs + rhs;
+  let rhs_part = sum - lhs;
+  let error = (lhs - (sum - rhs_part)) + (rhs - rhs_part);
+  if sum.is_finite() && error == 0.0 {
+    FastSci::from_f64(sum)
+  } else {
+    None
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for FastSci<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match self.repr {
+      Repr::Float(float) => write!(fmtr, "FastSci::Float({:?})", float),
+      Repr::Exact(val)   => write!(fmtr, "FastSci::Exact({:?})", val)
+    }
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for Repr<B,E> {
+  fn clone(&self) -> Repr<B,E> {
+    *self
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Copy for Repr<B,E> {}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for FastSci<B,E> {
+  fn clone(&self) -> FastSci<B,E> {
+    *self
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Copy for FastSci<B,E> {}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> PartialEq for FastSci<B,E> {
+  fn eq(&self, rhs:&FastSci<B,E>) -> bool {
+    self.cmp_value(rhs) == Ordering::Equal
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Add for FastSci<B,E> {
+  type Output = FastSci<B,E>;
+
+  fn add(self, rhs:FastSci<B,E>) -> FastSci<B,E> {
+    self.checked_add(&rhs).expect("FastSci addition overflowed")
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Sub for FastSci<B,E> {
+  type Output = FastSci<B,E>;
+
+  fn sub(self, rhs:FastSci<B,E>) -> FastSci<B,E> {
+    self.checked_sub(&rhs).expect("FastSci subtraction overflowed")
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Mul for FastSci<B,E> {
+  type Output = FastSci<B,E>;
+
+  fn mul(self, rhs:FastSci<B,E>) -> FastSci<B,E> {
+    self.checked_mul(&rhs).expect("FastSci multiplication overflowed")
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::FastSci;
+  use super::super::SciValue;
+
+  #[test]
+  fn stays_fast_while_exact(){
+    let half = FastSci::new(SciValue::wrap_with_exponent(5i64, -1i32));
+    let three = FastSci::new(SciValue::wrap(3i64));
+    assert!(half.is_fast() && three.is_fast());
+
+    let sum = half + three;
+    assert!(sum.is_fast());
+    assert_eq!(sum.to_sci(), SciValue::wrap_with_exponent(35, -1));
+    let product = sum * three - half;
+    assert!(product.is_fast());
+    assert_eq!(product.to_sci(), SciValue::wrap_with_exponent(1, 1));
+  }
+
+  #[test]
+  fn falls_back_on_the_tail(){
+    //0.1 isn't a binary fraction, so starts exact
+    let tenth = FastSci::new(SciValue::wrap_with_exponent(1i64, -1i32));
+    assert!(!tenth.is_fast());
+    let sum = tenth + FastSci::new(SciValue::wrap(2i64));
+    assert!(!sum.is_fast());
+    assert_eq!(sum.to_sci(), SciValue::wrap_with_exponent(21, -1));
+
+    //2^53 + 1 rounds in f64, but not as a SciValue
+    let big = FastSci::new(SciValue::wrap_with_exponent(1i64 << 53, 0i32));
+    let one = FastSci::new(SciValue::wrap(1i64));
+    let sum = big + one;
+    assert!(!sum.is_fast());
+    assert_eq!(sum.to_sci(), SciValue::wrap((1i64 << 53) + 1));
+
+    let product = FastSci::new(SciValue::wrap_with_exponent((1i64 << 27) + 1, 0i32)) * FastSci::new(SciValue::wrap((1i64 << 27) + 1));
+    assert!(!product.is_fast());
+    assert_eq!(product.to_sci(), SciValue::wrap(((1i64 << 27) + 1) * ((1i64 << 27) + 1)));
+
+    //Floats which don't fit the types never take the fast path
+    assert!(FastSci::<i8,i8>::from_f64(1000.5).is_none());
+    assert_eq!(FastSci::<i8,i8>::new(SciValue::wrap(100)).checked_add(&FastSci::new(SciValue::wrap(100))).map(|sum| sum.to_sci()),
+               Some(SciValue::wrap_with_exponent(2, 2)));
+    assert_eq!(FastSci::<i8,i8>::new(SciValue::wrap(99)).checked_add(&FastSci::new(SciValue::wrap(99))), None);
+    assert!(FastSci::<i64,i32>::new(SciValue::wrap(2)) == FastSci::new(SciValue::wrap_with_exponent(20, -1)));
+  }
+}
@@ -0,0 +1,302 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Display, Formatter, Error};
+use std::error::Error as StdError;
+
+use super::{SciValue, base_constant, base_from_magnitude, float_text};
+
+/**
+ * Error converting a float to a `SciValue` exactly.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromFloatError {
+  /// The float was infinite or NaN.
+  NotFinite,
+  /// The float is negative, but the base type is unsigned.
+  BaseOverflow,
+  /// The exact value doesn't fit the types, but a rounded
+  /// one would.
+  RoundingRequired,
+  /// The float's magnitude is beyond the exponent type.
+  ExponentOverflow
+}
+
+impl Display for FromFloatError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match *self {
+      FromFloatError::NotFinite        => fmtr.write_str("float is infinite or NaN"),
+      FromFloatError::BaseOverflow     => fmtr.write_str("negative float for an unsigned base type"),
+      FromFloatError::RoundingRequired => fmtr.write_str("float can't be represented without rounding"),
+      FromFloatError::ExponentOverflow => fmtr.write_str("float's exponent doesn't fit in the exponent type")
+    }
+  }
+}
+
+impl StdError for FromFloatError {}
+
+/**
+ * How a conversion to a float went.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatConversion {
+  /// The float holds the value exactly.
+  Exact,
+  /// The float is the nearest one to the value.
+  Rounded,
+  /// The value is too large, and became an infinity.
+  Overflow,
+  /// The value is too small, and became zero.
+  Underflow
+}
+
+/**
+ * What converting a value to an `f64` would give.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatClass {
+  /// The float would hold the value exactly.
+  Exact,
+  /// The float would be the nearest normal one to the value.
+  Rounded,
+  /// The float would be nonzero but subnormal, with less
+  /// than the full 53 bits of precision.
+  Subnormal,
+  /// The value is too large, and would become an infinity.
+  Overflow,
+  /// The value is too small, and would become zero.
+  Underflow
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Classifies what converting the value to an `f64` would
+   * give, so callers can decide whether a float is safe to
+   * use for it.  Subnormal results are reported as such
+   * whether or not they happen to be exact.
+   */
+  pub fn classify_f64(&self) -> FloatClass {
+    match self.to_f64_with_status() {
+      (_, FloatConversion::Overflow)             => FloatClass::Overflow,
+      (_, FloatConversion::Underflow)            => FloatClass::Underflow,
+      (converted, _) if converted.is_subnormal() => FloatClass::Subnormal,
+      (_, FloatConversion::Exact)                => FloatClass::Exact,
+      (_, FloatConversion::Rounded)              => FloatClass::Rounded
+    }
+  }
+
+  /**
+   * Converts the value to the nearest `f64`, along with
+   * whether that lost anything.
+   */
+  pub fn to_f64_with_status(&self) -> (f64, FloatConversion) {
+    let converted: f64 = float_text(self).parse().expect("Couldn't parse float text");
+    (converted, self.float_status(converted))
+  }
+
+  /**
+   * Converts the value to the nearest `f32`, along with
+   * whether that lost anything.  This rounds once, rather
+   * than going through an `f64`.
+   */
+  pub fn to_f32_with_status(&self) -> (f32, FloatConversion) {
+    let converted: f32 = float_text(self).parse().expect("Couldn't parse float text");
+    (converted, self.float_status(converted as f64))
+  }
+
+  fn float_status(&self, converted:f64) -> FloatConversion {
+    if converted.is_infinite() {
+      FloatConversion::Overflow
+    } else if converted == 0.0 && self.base != B::zero() {
+      FloatConversion::Underflow
+    } else if SciValue::from_f64_exact(converted) == Ok(self.reduce()) {
+      //A float equal to the value has exact digits no
+      //longer than its reduced base, so they always fit
+      FloatConversion::Exact
+    } else {
+      FloatConversion::Rounded
+    }
+  }
+
+  /**
+   * Converts a float to the exact decimal value it holds,
+   * in reduced form.  Every finite float is a binary
+   * fraction, so it has a finite decimal expansion -
+   * `0.1f64` is exactly
+   * `1000000000000000055511151231257827021181583404541015625e-55`,
+   * which is more digits than any primitive base holds.
+   * Fails if the exact value doesn't fit the types, with
+   * an error saying whether rounding would have helped.
+   */
+  pub fn from_f64_exact(val:f64) -> Result<SciValue<B,E>, FromFloatError> {
+    if !val.is_finite() {
+      return Err(FromFloatError::NotFinite);
+    }
+    let bits = val.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7FF) as i64;
+    let fraction = bits & ((1u64 << 52) - 1);
+    let (mut mantissa, mut exp2) = if biased_exp == 0 {
+      (fraction, -1074i64)
+    } else {
+      (fraction | (1u64 << 52), biased_exp - 1075)
+    };
+    if mantissa == 0 {
+      return Ok(SciValue::wrap_with_exponent(B::zero(), E::zero()));
+    }
+    while mantissa & 1 == 0 {
+      mantissa >>= 1;
+      exp2 += 1;
+    }
+
+    if val < 0.0 && B::min_value() == B::zero() {
+      return Err(FromFloatError::BaseOverflow);
+    }
+
+    if exp2 >= 0 {
+      //m * 2^k has one trailing zero for each factor of 5
+      //in m, up to k of them, so the reduced value is
+      //(m / 5^z) * 2^(k - z) * 10^z and never needs more
+      //than 128 bits to reach a base the types can hold
+      let mut zeros = 0i64;
+      while zeros < exp2 && mantissa % 5 == 0 {
+        mantissa /= 5;
+        zeros += 1;
+      }
+      let shift = exp2 - zeros;
+      let mag = mantissa as u128;
+      if shift >= 128 || mag.leading_zeros() < shift as u32 {
+        return Err(inexact_error::<E>(val));
+      }
+      let base = base_from_magnitude::<B>(val < 0.0, mag << shift).ok_or_else(|| inexact_error::<E>(val))?;
+      let exp = <E as FromPrimitive>::from_i64(zeros).ok_or_else(|| inexact_error::<E>(val))?;
+      return Ok(SciValue::wrap_with_exponent(base, exp));
+    }
+
+    //Working from the signed mantissa lets negative bases
+    //reach B::MIN
+    let signed = if val < 0.0 { -(mantissa as i64) } else { mantissa as i64 };
+    let mut base = <B as FromPrimitive>::from_i64(signed).ok_or_else(|| inexact_error::<E>(val))?;
+
+    //m * 2^-k is m * 5^k * 10^-k, and as m is odd there
+    //are no trailing zeros to reduce
+    let type_b_5 = base_constant::<B>(5, "from_f64_exact");
+    for _ in 0..-exp2 {
+      base = base.checked_mul(&type_b_5).ok_or_else(|| inexact_error::<E>(val))?;
+    }
+    let exp = <E as FromPrimitive>::from_i64(exp2).ok_or_else(|| inexact_error::<E>(val))?;
+    Ok(SciValue::wrap_with_exponent(base, exp))
+  }
+}
+
+/**
+ * Only exact conversions are possible, so accepts just
+ * those floats `from_f64_exact` does.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> TryFrom<f64> for SciValue<B,E> {
+  type Error = FromFloatError;
+
+  fn try_from(val:f64) -> Result<SciValue<B,E>, FromFloatError> {
+    SciValue::from_f64_exact(val)
+  }
+}
+
+/**
+ * Why a finite float's exact value didn't fit: either a
+ * single rounded digit at its leading place would, or
+ * even that place is beyond the exponent type.
+ */
+fn inexact_error<E:PrimInt + Signed + FromPrimitive>(val:f64) -> FromFloatError {
+  let text = format!("{:e}", val);
+  let leading = text[text.find('e').expect("Couldn't find float exponent") + 1..].parse::<i64>()
+                                   .expect("Couldn't parse float exponent");
+  match <E as FromPrimitive>::from_i64(leading) {
+    Some(_) => FromFloatError::RoundingRequired,
+    None    => FromFloatError::ExponentOverflow
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::{FromFloatError, FloatConversion, FloatClass};
+  use super::super::SciValue;
+
+  #[test]
+  fn exact_floats(){
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(3.5), Ok(SciValue::wrap_with_exponent(35, -1)));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(-0.375), Ok(SciValue::wrap_with_exponent(-375, -3)));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(1500.0), Ok(SciValue::wrap_with_exponent(15, 2)));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(-0.0), Ok(SciValue::wrap(0)));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(-9223372036854775808.0), Ok(SciValue::wrap(i64::MIN)));
+    assert_eq!(SciValue::<i128,i32>::from_f64_exact(2f64.powi(-30)),
+               Ok(SciValue::wrap_with_exponent(931322574615478515625i128, -30)));
+    //Large powers of ten only fit once their zeros are stripped
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(1e20), Ok(SciValue::wrap_with_exponent(1, 20)));
+    assert_eq!(SciValue::<i8,i8>::from_f64_exact(1000.0), Ok(SciValue::wrap_with_exponent(1, 3)));
+    assert_eq!(SciValue::<i8,i8>::from_f64_exact(-1280.0), Ok(SciValue::wrap_with_exponent(-128, 1)));
+    assert_eq!(SciValue::<i128,i32>::from_f64_exact(2f64.powi(100)), Ok(SciValue::wrap(1i128 << 100)));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(2f64.powi(100)), Err(FromFloatError::RoundingRequired));
+  }
+
+  #[test]
+  fn float_status(){
+    assert_eq!(SciValue::wrap_with_exponent(-35i64, -1i32).to_f64_with_status(), (-3.5, FloatConversion::Exact));
+    assert_eq!(SciValue::wrap_with_exponent(1i64, -1i32).to_f64_with_status(), (0.1, FloatConversion::Rounded));
+    assert_eq!(SciValue::wrap_with_exponent(0i64, 900i32).to_f64_with_status(), (0.0, FloatConversion::Exact));
+    assert_eq!(SciValue::wrap_with_exponent(5i64, 400i32).to_f64_with_status(), (f64::INFINITY, FloatConversion::Overflow));
+    assert_eq!(SciValue::wrap_with_exponent(-5i64, -400i32).to_f64_with_status().1, FloatConversion::Underflow);
+    assert_eq!(SciValue::<i64,i32>::wrap(16777217).to_f32_with_status(), (16777216.0, FloatConversion::Rounded));
+    assert_eq!(SciValue::<i64,i32>::wrap(16777216).to_f32_with_status(), (16777216.0, FloatConversion::Exact));
+    assert_eq!(SciValue::wrap_with_exponent(5i64, 39i32).to_f32_with_status(), (f32::INFINITY, FloatConversion::Overflow));
+  }
+
+  #[test]
+  fn float_classes(){
+    assert_eq!(SciValue::wrap_with_exponent(25i64, -2i32).classify_f64(), FloatClass::Exact);
+    assert_eq!(SciValue::wrap_with_exponent(3i64, -1i32).classify_f64(), FloatClass::Rounded);
+    assert_eq!(SciValue::wrap_with_exponent(1i64, -310i32).classify_f64(), FloatClass::Subnormal);
+    assert_eq!(SciValue::wrap_with_exponent(1i64, 309i32).classify_f64(), FloatClass::Overflow);
+    assert_eq!(SciValue::wrap_with_exponent(-1i64, -330i32).classify_f64(), FloatClass::Underflow);
+    assert_eq!(SciValue::wrap_with_exponent(0i64, -330i32).classify_f64(), FloatClass::Exact);
+  }
+
+  #[test]
+  fn inexact_floats(){
+    assert_eq!(SciValue::<i128,i32>::from_f64_exact(0.1), Err(FromFloatError::RoundingRequired));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(2f64.powi(-30)), Err(FromFloatError::RoundingRequired));
+    assert_eq!(SciValue::<u32,i32>::from_f64_exact(-1.0), Err(FromFloatError::BaseOverflow));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(f64::INFINITY), Err(FromFloatError::NotFinite));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(f64::NAN), Err(FromFloatError::NotFinite));
+    assert_eq!(SciValue::<i64,i32>::from_f64_exact(1e300), Err(FromFloatError::RoundingRequired));
+    assert_eq!(SciValue::<i64,i8>::from_f64_exact(1e300), Err(FromFloatError::ExponentOverflow));
+    assert_eq!(SciValue::<i64,i8>::from_f64_exact(2f64.powi(-1000)), Err(FromFloatError::ExponentOverflow));
+    assert_eq!(SciValue::<i128,i8>::from_f64_exact(2f64.powi(-100)), Err(FromFloatError::RoundingRequired));
+  }
+
+  #[test]
+  fn try_from_float(){
+    assert_eq!(SciValue::<i64,i32>::try_from(-2.25), Ok(SciValue::wrap_with_exponent(-225, -2)));
+    assert_eq!(SciValue::<i64,i32>::try_from(1e20), Ok(SciValue::wrap_with_exponent(1, 20)));
+    assert_eq!(SciValue::<i64,i32>::try_from(0.3), Err(FromFloatError::RoundingRequired));
+    assert_eq!(SciValue::<i64,i32>::try_from(f64::NEG_INFINITY), Err(FromFloatError::NotFinite));
+  }
+}
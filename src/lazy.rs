@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error};
+use std::ops::{Add, Sub, Mul};
+use std::cmp::Ordering;
+use std::cell::RefCell;
+
+use super::SciValue;
+
+/**
+ * A value which is only reduced when it is read.
+ *
+ * Arithmetic works on the raw, unreduced operands,
+ * and the reduced form is computed and cached the first
+ * time it's needed - by a comparison, by formatting, or
+ * by `get`.  This suits accumulations where many writes
+ * happen between reads.
+ */
+pub struct LazySci<B, E:PrimInt + Signed> {
+  raw: SciValue<B,E>,
+  reduced: RefCell<Option<SciValue<B,E>>>
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> LazySci<B,E> {
+  pub fn new(val:SciValue<B,E>) -> LazySci<B,E> {
+    LazySci{raw: val, reduced: RefCell::new(None)}
+  }
+
+  /// The value as it was produced, without reduction.
+  pub fn raw(&self) -> &SciValue<B,E> {
+    &self.raw
+  }
+
+  /// The reduced value, computed on first use.
+  pub fn get(&self) -> SciValue<B,E> {
+    let mut cache = self.reduced.borrow_mut();
+    if cache.is_none() {
+      *cache = Some(self.raw.reduce());
+    }
+    *cache.as_ref().unwrap()
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Add for LazySci<B,E> {
+  type Output = LazySci<B,E>;
+
+  fn add(self, rhs:LazySci<B,E>) -> LazySci<B,E> {
+    LazySci::new(self.raw + rhs.raw)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Sub for LazySci<B,E> {
+  type Output = LazySci<B,E>;
+
+  fn sub(self, rhs:LazySci<B,E>) -> LazySci<B,E> {
+    LazySci::new(self.raw - rhs.raw)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Mul for LazySci<B,E> {
+  type Output = LazySci<B,E>;
+
+  fn mul(self, rhs:LazySci<B,E>) -> LazySci<B,E> {
+    LazySci::new(self.raw * rhs.raw)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialEq for LazySci<B,E> {
+  fn eq(&self, rhs:&LazySci<B,E>) -> bool {
+    self.get() == rhs.get()
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Eq for LazySci<B,E> {}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialOrd for LazySci<B,E> {
+  fn partial_cmp(&self, other:&LazySci<B,E>) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Ord for LazySci<B,E> {
+  fn cmp(&self, other:&LazySci<B,E>) -> Ordering {
+    self.get().cmp_value(&other.get())
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Clone for LazySci<B,E> {
+  fn clone(&self) -> LazySci<B,E> {
+    LazySci{raw: self.raw, reduced: RefCell::new(*self.reduced.borrow())}
+  }
+}
+
+impl<B:PrimInt + FromPrimitive + Debug, E:PrimInt + Signed + Debug> Debug for LazySci<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    fmtr.write_str(format!("LazySci({:?})", self.get()).as_str())
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::LazySci;
+  use super::super::SciValue;
+
+  #[test]
+  fn reduces_on_read(){
+    let total = LazySci::new(SciValue::wrap_with_exponent(150i32, 0isize)) +
+                LazySci::new(SciValue::wrap_with_exponent(5i32, 1isize));
+    assert_eq!(*total.raw(), SciValue::wrap_with_exponent(200, 0isize));
+    assert_eq!(total.get(), SciValue::wrap_with_exponent(2, 2isize));
+    assert_eq!(*total.raw(), SciValue::wrap_with_exponent(200, 0isize));
+  }
+
+  #[test]
+  fn compares_by_value(){
+    let lhs = LazySci::new(SciValue::wrap_with_exponent(20i32, 2isize));
+    let rhs = LazySci::new(SciValue::wrap_with_exponent(2i32, 3isize));
+    assert!(lhs == rhs);
+    assert!(LazySci::new(SciValue::wrap_with_exponent(3i32, 2isize)) < rhs);
+  }
+}
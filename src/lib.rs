@@ -20,12 +20,82 @@
  * THE SOFTWARE.
  *
  */
-#![feature(core)]
+use std::fmt::{Debug, Display, Formatter, Error};
+use std::error::Error as StdError;
+use num_traits::{PrimInt, Signed, Zero, One, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, Pow, NumCast, FromPrimitive, ToPrimitive, WrappingAdd, WrappingSub, WrappingMul};
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, AddAssign, SubAssign, MulAssign, DivAssign, RangeBounds, Bound};
+use std::cmp::{Ordering, Reverse};
+use std::iter::{Sum, Product};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::time::Duration;
 
-use std::fmt::{Debug, Formatter, Error};
-use std::num::{SignedInt, Int, FromPrimitive};
-use std::ops::{Add, Sub, Mul, Div};
-use std::cmp::Ordering;
+#[cfg(not(feature = "observer"))]
+use observer::OverflowKind;
+
+pub use sketch::SciSketch;
+pub use lazy::LazySci;
+pub use pg::PgNumericError;
+pub use proto::SignMagnitude;
+#[cfg(feature = "protobuf")]
+pub use proto::ProtoDecodeError;
+pub use compact::SciValue24;
+pub use parse::{ParseSciValueError, ParseErrorKind, ParseLimits, ParseWarning};
+pub use topk::TopK;
+pub use table::{TableWriter, TableStyle, ColumnFormat};
+pub use logscale::MAX_LOG_DIGITS;
+pub use column::{load_column, ColumnReader, ColumnOptions, ColumnError, ColumnErrorKind};
+pub use report::{TargetType, ConversionReport};
+pub use render::{NotationRenderer, FormatOptions, ExponentMarker};
+pub use notation::{convert_notation, NotationStyle, NotationError};
+pub use tagged::{Tagged, MergeTags};
+pub use float::{FromFloatError, FloatConversion, FloatClass};
+pub use sample::sample_stratified;
+pub use twosci::TwoSci;
+pub use fast::FastSci;
+pub use stats::RunningStats;
+pub use vec::{SciVec, ElementFlags};
+pub use resample::{resample, Aggregator};
+#[cfg(feature = "observer")]
+pub use observer::{set_overflow_observer, OverflowObserver, OverflowEvent, OverflowKind};
+
+#[macro_use]
+#[doc(hidden)]
+pub mod assertions;
+
+mod sketch;
+mod lazy;
+mod pg;
+mod compact;
+mod parse;
+mod topk;
+mod table;
+mod logscale;
+mod column;
+mod report;
+mod render;
+mod notation;
+mod tagged;
+mod float;
+mod sample;
+mod monotonic;
+mod twosci;
+mod fast;
+mod stats;
+mod vec;
+mod resample;
+pub mod proto;
+pub mod binary;
+pub mod telemetry;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+pub mod csv;
+mod observer;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(all(test, feature = "exhaustive"))]
+mod exhaustive;
 
 /**
  * Structure storing a number in a format similar
@@ -33,15 +103,135 @@ use std::cmp::Ordering;
  *
  * Probably inefficient, but also probably quite
  * accurate.
+ *
+ * The base must be a primitive integer.  Values are
+ * `Copy` and every operation is bounded on `PrimInt`, so
+ * heap-allocated bases such as `num_bigint::BigInt` are
+ * not supported.  For more digits than a `u64` holds, use
+ * an `i128` or `u128` base, or `TwoSci`.
  */
-pub struct SciValue<BASEVAL,EXPSTORE:SignedInt>{
+pub struct SciValue<BASEVAL,EXPSTORE:PrimInt + Signed>{
   base: BASEVAL,
   e_exp: EXPSTORE
 }
 
-impl<B:Int,E:SignedInt> SciValue<B,E> {
+/**
+ * Error produced when a value can't be re-expressed
+ * exactly in the requested form.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecisionLoss {
+  /// The base would overflow its type.
+  Overflow,
+  /// Nonzero digits would be dropped from the base.
+  Truncation
+}
+
+/**
+ * Integer types with a wider counterpart, used
+ * for computations whose results can outgrow
+ * the original type.
+ */
+pub trait Widen : PrimInt {
+  type Wide : PrimInt;
+
+  fn widen(self) -> <Self as Widen>::Wide;
+}
+
+macro_rules! widen_impl {
+  ($narrow:ty, $wide:ty) => {
+    impl Widen for $narrow {
+      type Wide = $wide;
+
+      fn widen(self) -> $wide {
+        self as $wide
+      }
+    }
+  }
+}
+
+widen_impl!(i8, i16);
+widen_impl!(i16, i32);
+widen_impl!(i32, i64);
+widen_impl!(i64, i128);
+widen_impl!(u8, u16);
+widen_impl!(u16, u32);
+widen_impl!(u32, u64);
+widen_impl!(u64, u128);
+
+/**
+ * How to round away digits which can't be kept.  With the
+ * `serde` feature, modes are written by name in snake case,
+ * such as `"half_even"`, for configuration files.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RoundingMode {
+  /// Drop the digits, rounding toward zero.
+  Truncate,
+  /// Round toward negative infinity.
+  Floor,
+  /// Round toward positive infinity.
+  Ceiling,
+  /// Round to the nearest value, with ties going to
+  /// the even neighbour (banker's rounding).
+  HalfEven,
+  /// Round to the nearest value, with ties going away
+  /// from zero (commercial rounding).
+  HalfAwayFromZero
+}
+
+/**
+ * The type a failed conversion was targeting.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertTarget {
+  /// The base (mantissa) type of the value.
+  Base,
+  /// The exponent type of the value.
+  Exponent
+}
+
+/**
+ * Error produced when a value an operation needs
+ * can't be converted into the type it's needed in,
+ * recording the context of the failure.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvertError {
+  /// The operation in progress when conversion failed.
+  pub operation: &'static str,
+  /// The type which couldn't hold the value.
+  pub target: ConvertTarget,
+  /// The mantissa involved, if it fits in an i64.
+  pub mantissa: Option<i64>,
+  /// The exponent (or power of ten) involved, if it fits in an i64.
+  pub exponent: Option<i64>
+}
+
+impl Display for ConvertError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    let target = match self.target {
+      ConvertTarget::Base     => "base",
+      ConvertTarget::Exponent => "exponent"
+    };
+    write!(fmtr, "{}: value doesn't fit in the {} type", self.operation, target)?;
+    if let Some(mantissa) = self.mantissa {
+      write!(fmtr, " (mantissa {})", mantissa)?;
+    }
+    if let Some(exponent) = self.exponent {
+      write!(fmtr, " (exponent {})", exponent)?;
+    }
+    Ok(())
+  }
+}
+
+impl StdError for ConvertError {}
+
+impl<B:PrimInt,E:PrimInt + Signed> SciValue<B,E> {
   pub fn wrap(val:B) -> SciValue<B,E> {
-    SciValue{base: val, e_exp : <E as Int>::zero()}
+    SciValue{base: val, e_exp : E::zero()}
   }
 
   pub fn wrap_with_exponent(val:B, exp:E) -> SciValue<B,E> {
@@ -49,23 +239,364 @@ impl<B:Int,E:SignedInt> SciValue<B,E> {
   }
 }
 
-impl<B:Int, E:SignedInt> SciValue<B,E> {
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Builds a value from an already split integer part
+   * and fractional digits, scaled by 10^exp - so the parts
+   * of `-12.5e3` are `(-12, &[5], 3)`.
+   *
+   * The sign of the value is taken from the integer part.
+   * Returns None if a digit is greater than 9 or if the
+   * result doesn't fit in the base and exponent types.
+   */
+  pub fn from_parts(int_part:B, frac_digits:&[u8], exp:E) -> Option<SciValue<B,E>> {
+    let type_b_10 = base_constant::<B>(10, "from_parts");
+    let negative  = int_part < B::zero();
+
+    let mut base = int_part;
+    for digit in frac_digits.iter() {
+      if *digit > 9 {
+        return None;
+      }
+      let digit_val = base_constant::<B>(*digit as i64, "from_parts");
+      let scaled    = base.checked_mul(&type_b_10);
+      base = scaled.and_then(|val| if negative { val.checked_sub(&digit_val) } else { val.checked_add(&digit_val) })?;
+    }
+
+    <E as FromPrimitive>::from_usize(frac_digits.len())
+      .and_then(|shift| exp.checked_sub(&shift))
+      .map(|new_exp| SciValue::wrap_with_exponent(base, new_exp))
+  }
+
+  /**
+   * Computes the decimal value of `num / den` to
+   * `extra_digits` digits after the decimal point, rounding
+   * any further digits using `mode`.  The result always has
+   * an exponent of `-extra_digits`.
+   *
+   * Returns None if `den` is zero or the result doesn't
+   * fit in the base and exponent types.
+   */
+  pub fn from_ratio(num:B, den:B, extra_digits:usize, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    let type_b_0 = B::zero();
+    let type_b_10 = base_constant::<B>(10, "from_ratio");
+    if den == type_b_0 {
+      return None;
+    }
+    let exp = match <E as FromPrimitive>::from_usize(extra_digits) {
+      Some(digits) => E::zero() - digits,
+      None         => return None
+    };
+
+    //Long division, one digit at a time.  Digits and
+    //remainders carry the sign of the result, so these
+    //steps work for any combination of signs.
+    let mut quot = num / den;
+    let mut rem  = num % den;
+    for _ in 0..extra_digits {
+      let shifted = rem.checked_mul(&type_b_10)?;
+      quot = quot.checked_mul(&type_b_10).and_then(|val| val.checked_add(&(shifted / den)))?;
+      rem = shifted % den;
+    }
+
+    let shifted = rem.checked_mul(&type_b_10)?;
+    let digit = shifted / den;
+    let digit_magnitude = if digit < type_b_0 { type_b_0 - digit } else { digit };
+    let negative = (num < type_b_0) != (den < type_b_0) && num != type_b_0;
+
+    let rounded = if !mode_rounds_away(mode, quot, digit_magnitude, shifted % den != type_b_0, negative) {
+      Some(quot)
+    } else if negative {
+      quot.checked_sub(&B::one())
+    } else {
+      quot.checked_add(&B::one())
+    };
+    rounded.map(|base| SciValue::wrap_with_exponent(base, exp))
+  }
+
+  /**
+   * Builds a value from an integer count of minor units,
+   * such as cents, with `decimals` digits per major unit.
+   */
+  pub fn from_minor_units(raw:B, decimals:u8) -> SciValue<B,E> {
+    let shift = exp_constant::<E>(decimals as i64, "from_minor_units");
+    SciValue::wrap_with_exponent(raw, E::zero() - shift)
+  }
+
+  /**
+   * Converts the value to an integer count of minor units,
+   * such as cents, with `decimals` digits per major unit.
+   * Digits below the minor unit are rounded using `mode`.
+   * Fails if the count overflows the base type.
+   */
+  pub fn to_minor_units(&self, decimals:u8, mode:RoundingMode) -> Result<B, PrecisionLoss> {
+    let target = E::zero() - exp_constant::<E>(decimals as i64, "to_minor_units");
+    self.round_to_exponent(target, mode).rebalance_to_exponent(target).map(|val| val.base)
+  }
+
+  /**
+   * Converts a number of seconds to a `Duration`.  Returns
+   * None if the value is negative, has digits below a
+   * nanosecond, or is too long for a `Duration`.
+   */
+  pub fn try_to_duration(&self) -> Option<Duration> {
+    let nanos = SciValue::wrap_with_exponent(self.base, self.e_exp.checked_add(&exp_constant::<E>(9, "try_to_duration"))?)
+                  .to_integer_exact::<u128>()?;
+    let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+    Some(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+  }
+
+  /**
+   * Computes `self / whole`, rounded using `mode` to the
+   * decimal place of 10^exp.  The result always has the
+   * exponent `exp`.
+   *
+   * Returns None if `whole` is zero or the result doesn't
+   * fit in the base and exponent types.
+   */
+  pub fn ratio_of(&self, whole:&SciValue<B,E>, exp:E, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    //The quotient's digits need shifting by this much to
+    //land on the place of 10^exp
+    let shift = self.e_exp.checked_sub(&whole.e_exp).and_then(|diff| diff.checked_sub(&exp))?;
+    let ratio = if shift >= E::zero() {
+      SciValue::<B,E>::from_ratio(self.base, whole.base, shift.to_usize()?, mode)?
+    } else {
+      let den = checked_pow10::<B,E>(E::zero() - shift).and_then(|scale| whole.base.checked_mul(&scale))?;
+      SciValue::<B,E>::from_ratio(self.base, den, 0, mode)?
+    };
+    Some(SciValue::wrap_with_exponent(ratio.base, exp))
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> SciValue<B,E> {
   pub fn pow(self, exp: E) -> SciValue<B,E>{
-    let mut newbase = self.base.clone();
+    let mut newbase = self.base;
 
-    //Using a range, multiple *exp* times -1
+    //Multiply *exp* times -1
     //(Not *exp* times exactly as we already start
     // with base^1)
-    for _ in range::<E>(<E as Int>::one(),exp) {
-      newbase = newbase * self.base.clone();
+    let mut count = E::one();
+    while count < exp {
+      newbase = newbase * self.base;
+      count = count + E::one();
     }
     SciValue{base: newbase, e_exp: self.e_exp * exp}
   }
 
+  pub fn square(&self) -> SciValue<B,E> {
+    *self * *self
+  }
+
+  pub fn cube(&self) -> SciValue<B,E> {
+    *self * *self * *self
+  }
+
+  /**
+   * Squares the number, returning None if either
+   * the base or the exponent overflows.
+   */
+  pub fn checked_square(&self) -> Option<SciValue<B,E>> {
+    self.checked_mul(self)
+  }
+
+  /**
+   * Cubes the number, returning None if either
+   * the base or the exponent overflows.
+   */
+  pub fn checked_cube(&self) -> Option<SciValue<B,E>> {
+    self.checked_mul(self).and_then(|sq| sq.checked_mul(self))
+  }
+
+  /**
+   * Difference between this value's exponent and another's,
+   * ignoring both bases.  For values stored at their unit
+   * prefixes, such as a reading in mV against one in V, this
+   * is the prefix shift between them (-3).
+   */
+  pub fn prefix_difference(&self, other:&SciValue<B,E>) -> E {
+    self.e_exp - other.e_exp
+  }
+
+  /**
+   * Shifts the exponent by `shift`, leaving the base alone.
+   * Re-expresses a reading in a unit prefix as the base
+   * unit: 5 mV shifted by -3 is 5e-3 V.
+   */
+  pub fn with_prefix_shift(&self, shift:E) -> SciValue<B,E> {
+    SciValue::wrap_with_exponent(self.base, self.e_exp + shift)
+  }
+
+  /**
+   * Multiplies the numbers, returning None if either
+   * the base or the exponent overflows.
+   */
+  pub fn checked_mul(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    match (self.base.checked_mul(&rhs.base), self.e_exp.checked_add(&rhs.e_exp)) {
+      (Some(base), Some(exp)) => Some(SciValue::wrap_with_exponent(base, exp)),
+      _                       => None
+    }
+  }
+
+  /**
+   * Raises the number to the power `exp`, returning None
+   * if the base or the exponent overflows.  Unlike `pow`,
+   * a power of zero gives one, and negative powers, which
+   * generally have no integer base, give None.
+   */
+  pub fn checked_pow(&self, exp:E) -> Option<SciValue<B,E>> {
+    if exp < E::zero() {
+      return None;
+    }
+    let new_exp = self.e_exp.checked_mul(&exp)?;
+    checked_base_pow(self.base, exp).map(|base| SciValue::wrap_with_exponent(base, new_exp))
+  }
+
+  /**
+   * Multiplies the numbers, clamping instead of overflowing.
+   * The exponents are added saturating at `E::MIN`/`E::MAX`,
+   * and a base which overflows saturates at `B::MIN` or
+   * `B::MAX`, by the sign of the true product.
+   */
+  pub fn saturating_mul(&self, rhs:&SciValue<B,E>) -> SciValue<B,E> {
+    let exp = self.e_exp.checked_add(&rhs.e_exp);
+    let product = match (self.base.checked_mul(&rhs.base), exp) {
+      (Some(base), Some(exp)) => return SciValue::wrap_with_exponent(base, exp),
+      (Some(base), None)      => SciValue::wrap_with_exponent(base, self.e_exp.saturating_add(rhs.e_exp)),
+      (None, _)               => saturated((self.base < B::zero()) != (rhs.base < B::zero()), self.e_exp.saturating_add(rhs.e_exp))
+    };
+    observer::notify(OverflowKind::Saturated, "saturating_mul");
+    product
+  }
+
+  /**
+   * Raises the number to the power `exp`, clamping the base
+   * to `B::MAX` or `B::MIN` (by the sign of the true power)
+   * and the exponent to `E::MAX` or `E::MIN` as
+   * `saturating_mul` does.  A power of zero gives one, and
+   * negative powers give zero.
+   */
+  pub fn saturating_pow(&self, exp:E) -> SciValue<B,E> {
+    let type_e_2 = E::one() + E::one();
+    if exp < E::zero() {
+      return SciValue::wrap(B::zero());
+    }
+    let e_exp = self.e_exp.checked_mul(&exp);
+    let saturated_exp = e_exp.unwrap_or(
+      if self.e_exp > E::zero() { E::max_value() } else { E::min_value() }
+    );
+    let power = match (checked_base_pow(self.base, exp), e_exp) {
+      (Some(base), Some(e_exp)) => return SciValue::wrap_with_exponent(base, e_exp),
+      (Some(base), None)        => SciValue::wrap_with_exponent(base, saturated_exp),
+      (None, _)                 => saturated(self.base < B::zero() && exp % type_e_2 != E::zero(), saturated_exp)
+    };
+    observer::notify(OverflowKind::Saturated, "saturating_pow");
+    power
+  }
+}
+
+impl<B:PrimInt + Signed, E:PrimInt + Signed> SciValue<B,E> {
+  /**
+   * Negates the number, returning None if the base is
+   * the minimum value of its type (whose negation can't
+   * be represented).
+   */
+  pub fn checked_neg(&self) -> Option<SciValue<B,E>> {
+    B::zero().checked_sub(&self.base).map(|base| SciValue::wrap_with_exponent(base, self.e_exp))
+  }
+
+  /**
+   * Takes the absolute value of the number, returning
+   * None if the base is the minimum value of its type.
+   */
+  pub fn checked_abs(&self) -> Option<SciValue<B,E>> {
+    if self.base < B::zero() {
+      self.checked_neg()
+    } else {
+      Some(*self)
+    }
+  }
+}
+
+impl<B, E> SciValue<B,E>
+    where B:PrimInt + FromPrimitive + WrappingAdd + WrappingSub + WrappingMul, E:PrimInt + Signed + WrappingAdd + WrappingSub {
+  /**
+   * Adds the numbers as the std `overflowing_add` does,
+   * returning the wrapped result and whether the base or
+   * the exponent overflowed.  Overflow while matching
+   * exponents counts, and wraps the rescaled base.
+   */
+  pub fn overflowing_add(&self, rhs:&SciValue<B,E>) -> (SciValue<B,E>, bool) {
+    let (lhs, rhs, matched_over) = match_exponents_wrapping(self, rhs);
+    let over = matched_over || lhs.base.checked_add(&rhs.base).is_none();
+    (SciValue::wrap_with_exponent(lhs.base.wrapping_add(&rhs.base), lhs.e_exp), over)
+  }
+
+  /**
+   * Subtracts `rhs`, wrapping on overflow as
+   * `overflowing_add` does.
+   */
+  pub fn overflowing_sub(&self, rhs:&SciValue<B,E>) -> (SciValue<B,E>, bool) {
+    let (lhs, rhs, matched_over) = match_exponents_wrapping(self, rhs);
+    let over = matched_over || lhs.base.checked_sub(&rhs.base).is_none();
+    (SciValue::wrap_with_exponent(lhs.base.wrapping_sub(&rhs.base), lhs.e_exp), over)
+  }
+
+  /**
+   * Multiplies the numbers, wrapping the base and the
+   * exponent independently on overflow.
+   */
+  pub fn overflowing_mul(&self, rhs:&SciValue<B,E>) -> (SciValue<B,E>, bool) {
+    let over = self.base.checked_mul(&rhs.base).is_none() || self.e_exp.checked_add(&rhs.e_exp).is_none();
+    (SciValue::wrap_with_exponent(self.base.wrapping_mul(&rhs.base), self.e_exp.wrapping_add(&rhs.e_exp)), over)
+  }
+
+  /**
+   * Divides by `rhs` as `checked_div` does, wrapping instead
+   * of failing: `MIN / -1` gives `MIN`, and the exponent
+   * wraps.  Panics on division by zero, like the std
+   * `overflowing_div`.
+   */
+  pub fn overflowing_div(&self, rhs:&SciValue<B,E>) -> (SciValue<B,E>, bool) {
+    if rhs.base == B::zero() {
+      panic!("attempt to divide by zero");
+    }
+    let (base, exp) = self.scale_for_div(rhs);
+    //Only MIN / -1 fails, and its wrapped quotient is MIN itself
+    let quot = base.checked_div(&rhs.base);
+    let over = quot.is_none() || exp.checked_sub(&rhs.e_exp).is_none();
+    (SciValue::wrap_with_exponent(quot.unwrap_or(base), exp.wrapping_sub(&rhs.e_exp)), over)
+  }
+}
+
+impl<B:Widen, E:PrimInt + Signed> SciValue<B,E> {
+  /**
+   * Converts the base to its wider counterpart,
+   * keeping the exponent as is.
+   */
+  pub fn widen(&self) -> SciValue<<B as Widen>::Wide,E> {
+    SciValue::wrap_with_exponent(self.base.widen(), self.e_exp)
+  }
+
+  /**
+   * Squares the number using the wider base type.
+   * The base can't overflow, so this only returns None
+   * if the exponent does.
+   */
+  pub fn widening_square(&self) -> Option<SciValue<<B as Widen>::Wide,E>> {
+    self.widen().checked_square()
+  }
 
+  /**
+   * Cubes the number using the wider base type.
+   * Returns None if even the wider type overflows,
+   * or if the exponent does.
+   */
+  pub fn widening_cube(&self) -> Option<SciValue<<B as Widen>::Wide,E>> {
+    self.widen().checked_cube()
+  }
 }
 
-impl<B:Int + FromPrimitive, E:SignedInt> SciValue<B,E> {
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> SciValue<B,E> {
   /**
    * Converts the number to its full representation,
    * out of scientific notation.  Returns None if
@@ -77,160 +608,1831 @@ impl<B:Int + FromPrimitive, E:SignedInt> SciValue<B,E> {
    * value of the base type.
    */
   pub fn to_full_value(self) -> Option<B> {
-    if self.e_exp < <E as Int>::zero(){
+    if self.e_exp < E::zero(){
       return None;
     }
 
 
-    let type_b_10 = <B as FromPrimitive>::from_int(10is).expect("Couldn't get a 10 value");
+    let type_b_10 = base_constant::<B>(10, "to_full_value");
 
     let mut full_val = self.base;
     let mut remaining_exp  = self.e_exp;
-    while remaining_exp > (<E as Int>::zero()) {
-      if full_val > <B as Int>::max_value() / type_b_10 {
+    while remaining_exp > (E::zero()) {
+      if full_val > B::max_value() / type_b_10 {
         return None;
       }
 
       full_val = full_val * type_b_10;
-      remaining_exp = remaining_exp - <E as Int>::one();
+      remaining_exp = remaining_exp - E::one();
     }
     Some(full_val)
   }
 
   pub fn reduce(&self) -> SciValue<B,E> {
-    let mut new_base = self.base.clone();
+    let mut new_base = self.base;
     let mut new_exp  = self.e_exp;
-    let type_b_0 = <B as Int>::zero();
-    let type_b_10 = <B as FromPrimitive>::from_int(10is).expect("Couldn't get a 10 value");
-    while new_base.clone() % type_b_10.clone() == type_b_0.clone() {
-      new_base = new_base / type_b_10.clone();
-      new_exp  = new_exp + <E as Int>::one();
+    let type_b_0 = B::zero();
+    let type_b_10 = base_constant::<B>(10, "reduce");
+    if new_base == type_b_0 {
+      //Zero has no digits to strip, and is kept at
+      //a canonical exponent of 0
+      return SciValue::wrap_with_exponent(type_b_0, E::zero());
+    }
+    while new_base % type_b_10 == type_b_0 {
+      new_base = new_base / type_b_10;
+      new_exp  = new_exp + E::one();
     }
     SciValue::wrap_with_exponent(new_base, new_exp)
   }
+
+  /**
+   * Converts the number to a `usize`, for use as an
+   * index or element count.  Returns None unless the
+   * value is an exact, non-negative integer which fits
+   * in a `usize`.
+   */
+  pub fn to_usize_checked(&self) -> Option<usize> {
+    if self.base < B::zero() {
+      return None;
+    }
+    if self.base == B::zero() {
+      return Some(0usize);
+    }
+    self.reduce().to_full_value().and_then(|full| full.to_usize())
+  }
+
+  /**
+   * Re-expresses the number with the given exponent,
+   * scaling the base to match.  Fails if the base would
+   * overflow, or if nonzero digits would have to be
+   * dropped to reach the target exponent.
+   */
+  pub fn rebalance_to_exponent(&self, target: E) -> Result<SciValue<B,E>, PrecisionLoss> {
+    let type_b_0 = B::zero();
+    let type_b_10 = base_constant::<B>(10, "rebalance_to_exponent");
+
+    if self.base == type_b_0 {
+      return Ok(SciValue::wrap_with_exponent(type_b_0, target));
+    }
+
+    let mut new_base = self.base;
+    let mut new_exp  = self.e_exp;
+    while new_exp > target {
+      new_base = new_base.checked_mul(&type_b_10).ok_or(PrecisionLoss::Overflow)?;
+      new_exp  = new_exp - E::one();
+    }
+    while new_exp < target {
+      if new_base % type_b_10 != type_b_0 {
+        return Err(PrecisionLoss::Truncation);
+      }
+      new_base = new_base / type_b_10;
+      new_exp  = new_exp + E::one();
+    }
+    Ok(SciValue::wrap_with_exponent(new_base, new_exp))
+  }
+
+  /**
+   * Computes the integer square root (the floor of the
+   * square root) of the number, as an exact value.
+   *
+   * Returns None for negative numbers and for numbers
+   * which aren't integers, as well as when the root
+   * can't be computed without overflowing the base type.
+   */
+  pub fn isqrt(&self) -> Option<SciValue<B,E>> {
+    let type_b_0 = B::zero();
+    let type_b_10 = base_constant::<B>(10, "isqrt");
+    let type_e_2 = E::one() + E::one();
+
+    if self.base < type_b_0 {
+      return None;
+    }
+    if self.base == type_b_0 {
+      return Some(SciValue::wrap(type_b_0));
+    }
+
+    let reduced = self.reduce();
+    if reduced.e_exp < E::zero() {
+      return None;
+    }
+
+    //The value is split as mantissa * 100^pairs, so that
+    //the exponent of the mantissa is even
+    let odd_exp = reduced.e_exp % type_e_2 != E::zero();
+    let mantissa = if odd_exp {
+      reduced.base.checked_mul(&type_b_10)?
+    } else {
+      reduced.base
+    };
+    let mut pairs = reduced.e_exp / type_e_2;
+
+    let mut root = isqrt_base(mantissa);
+    let mut rem  = mantissa - root * root;
+    if rem == type_b_0 {
+      return Some(SciValue::wrap_with_exponent(root, pairs));
+    }
+
+    //Not a perfect square, so every digit of the root
+    //is needed.  Continue the root digit by digit, as
+    //in long-hand square root.
+    while pairs > E::zero() {
+      match next_root_digit(root, rem) {
+        Some((next_root, next_rem)) => {
+          root = next_root;
+          rem  = next_rem;
+        }
+        None => return None
+      }
+      pairs = pairs - E::one();
+    }
+    Some(SciValue::wrap(root))
+  }
+
+  /**
+   * Compares the numeric values represented, regardless
+   * of how they are split between base and exponent.
+   * `20e2` and `2e3` compare as equal.
+   *
+   * Never overflows, as the base with the smaller exponent
+   * is divided down rather than the other being scaled up.
+   */
+  pub fn cmp_value(&self, other:&SciValue<B,E>) -> Ordering {
+    if self.e_exp < other.e_exp {
+      return other.cmp_value(self).reverse();
+    }
+
+    //self.base * 10^diff is compared against other.base by
+    //splitting other.base into quotient and remainder of 10^diff
+    let type_b_0 = B::zero();
+    let scale = self.e_exp.checked_sub(&other.e_exp).and_then(|diff| checked_pow10::<B,E>(diff));
+    let (quot, rem) = match scale {
+      Some(scale) => (other.base / scale, other.base % scale),
+      None        => (type_b_0, other.base)
+    };
+    match self.base.cmp(&quot) {
+      Ordering::Equal => type_b_0.cmp(&rem),
+      retval          => retval
+    }
+  }
+
+  /**
+   * Whether the value lies within the range, comparing
+   * by numeric value as `cmp_value` does, so `20e2` is in
+   * `2e3..` even though `SciValue`'s own ordering puts it
+   * below `2e3`.
+   */
+  pub fn in_range<R:RangeBounds<SciValue<B,E>>>(&self, range:R) -> bool {
+    let above_start = match range.start_bound() {
+      Bound::Included(start) => self.cmp_value(start) != Ordering::Less,
+      Bound::Excluded(start) => self.cmp_value(start) == Ordering::Greater,
+      Bound::Unbounded       => true
+    };
+    above_start && match range.end_bound() {
+      Bound::Included(end) => self.cmp_value(end) != Ordering::Greater,
+      Bound::Excluded(end) => self.cmp_value(end) == Ordering::Less,
+      Bound::Unbounded     => true
+    }
+  }
+
+  /// Alias of `in_range`.
+  pub fn contains<R:RangeBounds<SciValue<B,E>>>(&self, range:R) -> bool {
+    self.in_range(range)
+  }
+
+  /**
+   * Adds all of the given values into this one.
+   *
+   * Addends are grouped by exponent and each group is
+   * summed directly, with the group totals then added from
+   * the largest exponent down.  This matches exponents once
+   * per distinct exponent, rather than once per value.
+   */
+  pub fn accumulate(&mut self, values:&[SciValue<B,E>]) {
+    let mut sorted: Vec<SciValue<B,E>> = values.to_vec();
+    sorted.sort_by_key(|val| Reverse(val.e_exp));
+
+    let mut total = *self;
+    let mut pos = 0usize;
+    while pos < sorted.len() {
+      let group_exp = sorted[pos].e_exp;
+      let mut group_base = B::zero();
+      while pos < sorted.len() && sorted[pos].e_exp == group_exp {
+        group_base = group_base + sorted[pos].base;
+        pos += 1;
+      }
+      total += SciValue::wrap_with_exponent(group_base, group_exp);
+    }
+    *self = total;
+  }
+
+  /**
+   * Adds the numbers, returning None if matching their
+   * exponents or adding the bases overflows.
+   */
+  pub fn checked_add(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    let (lhs, rhs) = match_exponents_checked(self, rhs)?;
+    lhs.base.checked_add(&rhs.base).map(|base| SciValue::wrap_with_exponent(base, lhs.e_exp))
+  }
+
+  /**
+   * The decimal digit of the value's magnitude at the
+   * place of 10^position, which is 0 for places beyond
+   * either end of the base.
+   */
+  pub fn digit_at(&self, position:E) -> u8 {
+    let scale = match position.checked_sub(&self.e_exp).and_then(checked_pow10::<B,E>) {
+      Some(scale) => scale,
+      None        => return 0
+    };
+    let digit = (self.base / scale) % base_constant::<B>(10, "digit_at");
+    let magnitude = if digit < B::zero() { B::zero() - digit } else { digit };
+    magnitude.to_u8().expect("Couldn't convert a digit to u8")
+  }
+
+  /**
+   * Replaces the digit of the value's magnitude at the
+   * place of 10^position, keeping its sign.  If the place
+   * is below the value's exponent, the value is first
+   * rebalanced down to it.  Leaves the value unchanged and
+   * fails if the base would overflow.
+   *
+   * Panics if `digit` is more than 9.
+   */
+  pub fn set_digit_at(&mut self, position:E, digit:u8) -> Result<(), PrecisionLoss> {
+    assert!(digit < 10, "set_digit_at: {} is not a decimal digit", digit);
+    let old = self.digit_at(position);
+    if old == digit {
+      return Ok(());
+    }
+    let target = if position < self.e_exp { self.rebalance_to_exponent(position)? } else { *self };
+    let scale = position.checked_sub(&target.e_exp)
+                        .and_then(checked_pow10::<B,E>)
+                        .ok_or(PrecisionLoss::Overflow)?;
+
+    let grows = digit > old;
+    let change = base_constant::<B>(if grows { digit - old } else { old - digit } as i64, "set_digit_at")
+                   .checked_mul(&scale)
+                   .ok_or(PrecisionLoss::Overflow)?;
+    //A growing digit moves the base away from zero
+    let base = if grows == (target.base < B::zero()) {
+      target.base.checked_sub(&change)
+    } else {
+      target.base.checked_add(&change)
+    };
+    self.base  = base.ok_or(PrecisionLoss::Overflow)?;
+    self.e_exp = target.e_exp;
+    Ok(())
+  }
+
+  /**
+   * Converts the value to a primitive integer exactly,
+   * multiplying out the exponent.  Returns None unless
+   * the value is an integer which fits in `T`.
+   */
+  pub fn to_integer_exact<T:PrimInt + FromPrimitive>(&self) -> Option<T> {
+    self.exact_integer().ok()
+  }
+
+  fn exact_integer<T:PrimInt + FromPrimitive>(&self) -> Result<T, PrecisionLoss> {
+    let reduced = self.reduce();
+    if reduced.e_exp < E::zero() {
+      return Err(PrecisionLoss::Truncation);
+    }
+    let base = <T as NumCast>::from(reduced.base).ok_or(PrecisionLoss::Overflow)?;
+    if base == T::zero() {
+      return Ok(base);
+    }
+    checked_pow10::<T,E>(reduced.e_exp)
+      .and_then(|scale| base.checked_mul(&scale))
+      .ok_or(PrecisionLoss::Overflow)
+  }
+
+  /**
+   * Adds the numbers only if the exact sum can be
+   * represented.  When `checked_add` fails, the sum is
+   * formed again digit by digit and reduced, so a sum which
+   * fits at some exponent is always found, even if matching
+   * the operands' exponents would overflow.
+   *
+   * Fails with `Truncation` if the sum could only be kept
+   * by dropping digits, and with `Overflow` if it doesn't
+   * fit at any exponent.
+   */
+  pub fn add_exact(&self, rhs:&SciValue<B,E>) -> Result<SciValue<B,E>, PrecisionLoss> {
+    if let Some(sum) = self.checked_add(rhs) {
+      return Ok(sum);
+    }
+    match self.wide_sum(rhs, false) {
+      WideSum::Exact(sum)     => Ok(sum),
+      WideSum::Truncated(_)   => Err(PrecisionLoss::Truncation),
+      WideSum::Overflow(_)    => Err(PrecisionLoss::Overflow)
+    }
+  }
+
+  /**
+   * Adds `rhs` (or subtracts it, if `negate` is set) over
+   * the operands' decimal digits, so the exponents never
+   * have to be matched in `B`.  A result with too many
+   * digits for `B` is truncated toward zero.
+   */
+  fn wide_sum(&self, rhs:&SciValue<B,E>, negate:bool) -> WideSum<B,E> {
+    //Digits below this many places under the larger
+    //operand's exponent can't survive truncation to any
+    //primitive base (which holds at most 39 digits), so a
+    //lower operand further away is replaced by a single
+    //sticky digit with the same sign
+    const STICKY_PLACES:usize = 80;
+
+    let (lhs, rhs) = (self.reduce(), rhs.reduce());
+    let lhs = (lhs.base < B::zero(), wide_digits(lhs.base), lhs.e_exp);
+    let rhs = ((rhs.base < B::zero()) != negate, wide_digits(rhs.base), rhs.e_exp);
+    let ((low_neg, mut low_digits, low_exp), (high_neg, high_digits, high_exp)) =
+      if lhs.2 <= rhs.2 { (lhs, rhs) } else { (rhs, lhs) };
+
+    let shift = high_exp.checked_sub(&low_exp).and_then(|shift| shift.to_usize());
+    let (shift, exp) = match shift {
+      Some(shift) if shift <= STICKY_PLACES || low_digits.is_empty() => (shift, low_exp),
+      _                                                              => {
+        low_digits = vec![1];
+        let sticky = <E as NumCast>::from(STICKY_PLACES).expect("Couldn't convert sticky places to exponent type");
+        (STICKY_PLACES, high_exp - sticky)
+      }
+    };
+    let mut shifted = if high_digits.is_empty() { Vec::new() } else { vec![0; shift] };
+    shifted.extend(high_digits);
+
+    let (negative, mut digits) = if low_neg == high_neg {
+      (low_neg, add_digits(&low_digits, &shifted))
+    } else if cmp_digits(&low_digits, &shifted) == Ordering::Greater {
+      (low_neg, sub_digits(&low_digits, &shifted))
+    } else {
+      (high_neg, sub_digits(&shifted, &low_digits))
+    };
+    while digits.last() == Some(&0) {
+      digits.pop();
+    }
+    if digits.is_empty() {
+      return WideSum::Exact(SciValue::wrap_with_exponent(B::zero(), E::zero()));
+    }
+    if negative && B::min_value() == B::zero() {
+      return WideSum::Overflow(true);
+    }
+
+    let stripped = digits.iter().take_while(|&&digit| digit == 0).count();
+    let mut mag   = 0u128;
+    let mut base  = B::zero();
+    let mut taken = 0usize;
+    for &digit in digits[stripped..].iter().rev() {
+      let next = mag.checked_mul(10)
+                    .and_then(|mag| mag.checked_add(digit as u128))
+                    .and_then(|next| base_from_magnitude::<B>(negative, next).map(|base| (next, base)));
+      match next {
+        Some((next, next_base)) => {
+          mag  = next;
+          base = next_base;
+          taken += 1;
+        },
+        None                    => break
+      }
+    }
+    let mut dropped = digits.len() - stripped - taken;
+    //Dropping digits can leave zeros at the end of the
+    //ones kept, which are stripped again
+    if dropped > 0 {
+      while mag % 10 == 0 {
+        mag /= 10;
+        dropped += 1;
+      }
+      base = base_from_magnitude::<B>(negative, mag).expect("A shorter magnitude should still fit");
+    }
+    let exp = <E as NumCast>::from(stripped + dropped).and_then(|places| exp.checked_add(&places));
+    match exp {
+      Some(exp) if dropped > 0 => WideSum::Truncated(SciValue::wrap_with_exponent(base, exp)),
+      Some(exp)                => WideSum::Exact(SciValue::wrap_with_exponent(base, exp)),
+      None                     => WideSum::Overflow(negative)
+    }
+  }
+
+  /**
+   * Subtracts `rhs`, returning None if matching the
+   * exponents or subtracting the bases overflows.
+   */
+  pub fn checked_sub(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    let (lhs, rhs) = match_exponents_checked(self, rhs)?;
+    lhs.base.checked_sub(&rhs.base).map(|base| SciValue::wrap_with_exponent(base, lhs.e_exp))
+  }
+
+  /**
+   * Adds the numbers, clamping instead of overflowing.  A
+   * sum with more digits than `B` holds is truncated toward
+   * zero, and only a sum too large for any exponent gives
+   * `B::MAX` or `B::MIN` at `E::MAX`, by the sign of the
+   * true sum.  A negative sum of unsigned values gives zero.
+   */
+  pub fn saturating_add(&self, rhs:&SciValue<B,E>) -> SciValue<B,E> {
+    match self.checked_add(rhs) {
+      Some(sum) => sum,
+      None      => self.wide_sum(rhs, false).saturate("saturating_add")
+    }
+  }
+
+  /**
+   * Subtracts `rhs`, clamping instead of overflowing as
+   * `saturating_add` does.
+   */
+  pub fn saturating_sub(&self, rhs:&SciValue<B,E>) -> SciValue<B,E> {
+    match self.checked_sub(rhs) {
+      Some(diff) => diff,
+      None       => self.wide_sum(rhs, true).saturate("saturating_sub")
+    }
+  }
+
+  /**
+   * Divides by `rhs` as `/` does, scaling the dividend up
+   * until it divides evenly or can grow no further.  Returns
+   * None on division by zero or if the base or exponent
+   * overflows.
+   */
+  pub fn checked_div(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    if rhs.base == B::zero() {
+      return None;
+    }
+    let (base, exp) = self.scale_for_div(rhs);
+    match (base.checked_div(&rhs.base), exp.checked_sub(&rhs.e_exp)) {
+      (Some(quot), Some(diff)) => Some(SciValue::wrap_with_exponent(quot, diff)),
+      _                        => None
+    }
+  }
+
+  /**
+   * Divides by `rhs` giving the integer quotient, truncated
+   * toward zero, and the remainder, as integer `/` and `%`
+   * do.  The values are first brought to the lower of their
+   * exponents, which the remainder keeps; it has the sign
+   * of `self`.  So 7.5 divided by 2 gives 3 remainder 1.5,
+   * and `q * rhs + r` is always `self`.
+   *
+   * Panics if `rhs` is zero or if matching the exponents
+   * overflows, as `+` does.
+   */
+  pub fn div_rem(self, rhs:SciValue<B,E>) -> (SciValue<B,E>, SciValue<B,E>) {
+    let (lhs, rhs) = match_exponents(self, rhs).unwrap_or_else(|err| panic!("{}", err));
+    let quot = lhs.base / rhs.base;
+    (SciValue::wrap(quot), SciValue::wrap_with_exponent(lhs.base % rhs.base, lhs.e_exp))
+  }
+
+  /**
+   * Scales the dividend's base up by 10 until it divides
+   * evenly by the nonzero `rhs` base, or can't grow without
+   * overflowing the base or the exponent.
+   */
+  fn scale_for_div(&self, rhs:&SciValue<B,E>) -> (B, E) {
+    let type_b_10 = base_constant::<B>(10, "div");
+    let mut base = self.base;
+    let mut exp  = self.e_exp;
+    loop {
+      //Only MIN / -1 fails, which would divide evenly
+      let even = match base.checked_div(&rhs.base) {
+        Some(quot) => quot * rhs.base == base,
+        None       => true
+      };
+      if even || base >= B::max_value() / type_b_10 {
+        return (base, exp);
+      }
+      match (base.checked_mul(&type_b_10), exp.checked_sub(&E::one())) {
+        (Some(scaled), Some(lowered)) => { base = scaled; exp = lowered; }
+        _                             => return (base, exp)
+      }
+    }
+  }
+
+  /**
+   * Checks whether the two numbers are equal once
+   * both have been rounded (half away from zero) to
+   * the decimal place of 10^exp, ignoring any digits
+   * below that resolution.
+   */
+  pub fn eq_at_exponent(&self, other:&SciValue<B,E>, exp:E) -> bool {
+    let lhs = self.round_to_exponent(exp, RoundingMode::HalfAwayFromZero);
+    let rhs = other.round_to_exponent(exp, RoundingMode::HalfAwayFromZero);
+    lhs.cmp_value(&rhs) == Ordering::Equal
+  }
+
+  /**
+   * Rounds the number using the given mode, so that its
+   * exponent is at least `target`.  Numbers which already
+   * have an exponent of at least `target` are unchanged.
+   */
+  pub fn round_to_exponent(&self, target:E, mode:RoundingMode) -> SciValue<B,E> {
+    self.round_to_exponent_by(target, |quot, digit, sticky, negative| {
+      mode_rounds_away(mode, quot, digit, sticky, negative)
+    })
+  }
+
+  /**
+   * Rounds the number to an integer using the given mode.
+   * Numbers with a non-negative exponent are unchanged.
+   */
+  pub fn round(&self, mode:RoundingMode) -> SciValue<B,E> {
+    self.round_to_exponent(E::zero(), mode)
+  }
+
+  /**
+   * Rounds the number to an integer using the given mode
+   * and returns it in the base type, or Overflow if it
+   * doesn't fit.
+   */
+  pub fn to_integer(&self, mode:RoundingMode) -> Result<B, PrecisionLoss> {
+    self.round(mode).rebalance_to_exponent(E::zero()).map(|val| val.base)
+  }
+
+  /**
+   * Drops digits until the exponent reaches `target`, then
+   * moves the result one unit away from zero if `round_away`
+   * says to.  `round_away` is given the truncated base, the
+   * magnitude of the first dropped digit, whether any later
+   * dropped digit was nonzero, and whether the number is
+   * negative.
+   */
+  fn round_to_exponent_by<F>(&self, target:E, round_away:F) -> SciValue<B,E>
+      where F:Fn(B, B, bool, bool) -> bool {
+    if target <= self.e_exp {
+      return *self;
+    }
+
+    let type_b_0 = B::zero();
+    let negative = self.base < type_b_0;
+    let (quot, digit, sticky) = match target.checked_sub(&self.e_exp) {
+      Some(count) => drop_digits(self.base, count),
+      None        => (type_b_0, type_b_0, self.base != type_b_0)
+    };
+    let digit_magnitude = if digit < type_b_0 { type_b_0 - digit } else { digit };
+
+    let rounded = if !round_away(quot, digit_magnitude, sticky, negative) {
+      quot
+    } else if negative {
+      quot - B::one()
+    } else {
+      quot + B::one()
+    };
+    SciValue::wrap_with_exponent(rounded, target)
+  }
+
+  /**
+   * Compares the absolute values of the numbers,
+   * again regardless of how they are split between
+   * base and exponent.
+   */
+  pub fn cmp_magnitude(&self, other:&SciValue<B,E>) -> Ordering {
+    let type_b_0 = B::zero();
+    match (self.base < type_b_0, other.base < type_b_0) {
+      (false, false) => self.cmp_value(other),
+      (true, true)   => other.cmp_value(self),
+      //Negating the non-negative side can't overflow
+      (true, false)  => self.cmp_value(&SciValue::wrap_with_exponent(type_b_0 - other.base, other.e_exp)).reverse(),
+      (false, true)  => SciValue::wrap_with_exponent(type_b_0 - self.base, self.e_exp).cmp_value(other).reverse()
+    }
+  }
+
+  /**
+   * The exponent the number would have if written
+   * with a single digit before the decimal point,
+   * or None for zero.
+   */
+  fn order_of_magnitude(&self) -> Option<i64> {
+    let type_b_0 = B::zero();
+    let type_b_10 = base_constant::<B>(10, "order_of_magnitude");
+    if self.base == type_b_0 {
+      return None;
+    }
+
+    let mut extra_digits = 0i64;
+    let mut remaining = self.base / type_b_10;
+    while remaining != type_b_0 {
+      extra_digits += 1;
+      remaining = remaining / type_b_10;
+    }
+    let exp = saturating_exp_to_i64(self.e_exp);
+    Some(exp.checked_add(extra_digits).unwrap_or(i64::MAX))
+  }
+
+  /**
+   * Checks that the number is at least 10^exp in absolute
+   * value, using only its order of magnitude.  Much cheaper
+   * than an exact comparison, so useful for pre-filtering.
+   * Zero is never at least any magnitude.
+   */
+  pub fn magnitude_at_least(&self, exp:E) -> bool {
+    match self.order_of_magnitude() {
+      Some(magnitude) => magnitude >= saturating_exp_to_i64(exp),
+      None            => false
+    }
+  }
+
+  /**
+   * Checks that the number is less than 10^exp in absolute
+   * value, using only its order of magnitude.  Zero is less
+   * than every magnitude.
+   */
+  pub fn magnitude_less_than(&self, exp:E) -> bool {
+    !self.magnitude_at_least(exp)
+  }
+}
+
+impl<B: PrimInt + Debug, E: PrimInt + Signed + Debug> Debug for SciValue<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    fmtr.write_str(format!("SciValue{}base : {:?}, e_exp : {:?}{}", "{", self.base, self.e_exp, "}").as_str())
+  }
+}
+
+/**
+ * Formats the value in e-notation as stored, so
+ * `wrap_with_exponent(5, 2)` prints as `5e2`.
+ * No reduction is done; `20e1` stays `20e1`.
+ */
+impl<B: PrimInt + Display, E: PrimInt + Signed + Display> Display for SciValue<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    write!(fmtr, "{}e{}", self.base, self.e_exp)
+  }
+}
+
+impl<B,E> Clone for SciValue<B,E> where B: PrimInt, E:PrimInt + Signed {
+  fn clone(&self) -> SciValue<B,E> {
+    *self
+  }
+}
+
+impl<B,E> Copy for SciValue<B,E> where B: PrimInt, E:PrimInt + Signed {}
+
+/**
+ * Plain integers convert at an exponent of zero, as
+ * with `SciValue::wrap`.  These are implemented per base
+ * type, as a blanket `From<B>` would overlap the
+ * `TryFrom<f64>` impl.
+ */
+macro_rules! from_int_impl {
+  ($($int:ty),*) => {
+    $(
+      impl<E:PrimInt + Signed> From<$int> for SciValue<$int,E> {
+        fn from(val:$int) -> SciValue<$int,E> {
+          SciValue::wrap(val)
+        }
+      }
+    )*
+  }
+}
+
+from_int_impl!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/**
+ * Exact conversions to primitive integers, failing with
+ * `Truncation` for values with fractional digits and
+ * `Overflow` for values which don't fit.
+ */
+macro_rules! try_into_int_impl {
+  ($($int:ty),*) => {
+    $(
+      impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> TryFrom<SciValue<B,E>> for $int {
+        type Error = PrecisionLoss;
+
+        fn try_from(val:SciValue<B,E>) -> Result<$int, PrecisionLoss> {
+          val.exact_integer()
+        }
+      }
+    )*
+  }
+}
+
+try_into_int_impl!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/**
+ * Durations convert exactly, as a count of nanoseconds
+ * at an exponent of -9.  Only the 128-bit bases can hold
+ * every `Duration`.
+ */
+macro_rules! from_duration_impl {
+  ($($int:ty),*) => {
+    $(
+      impl<E:PrimInt + Signed + FromPrimitive> From<Duration> for SciValue<$int,E> {
+        fn from(val:Duration) -> SciValue<$int,E> {
+          let nanos = val.as_secs() as $int * 1_000_000_000 + val.subsec_nanos() as $int;
+          SciValue::wrap_with_exponent(nanos, exp_constant::<E>(-9, "from_duration"))
+        }
+      }
+    )*
+  }
+}
+
+from_duration_impl!(i128, u128);
+
+/**
+ * Zero, at the canonical exponent of 0.
+ */
+impl<B:PrimInt, E:PrimInt + Signed> Default for SciValue<B,E> {
+  fn default() -> SciValue<B,E> {
+    SciValue::wrap_with_exponent(B::zero(), E::zero())
+  }
+}
+
+/**
+ * A value is zero whenever its base is, whatever the
+ * exponent.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Zero for SciValue<B,E> {
+  fn zero() -> SciValue<B,E> {
+    SciValue::default()
+  }
+
+  fn is_zero(&self) -> bool {
+    self.base == B::zero()
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> One for SciValue<B,E> {
+  fn one() -> SciValue<B,E> {
+    SciValue::wrap_with_exponent(B::one(), E::zero())
+  }
+}
+
+
+impl<B:PrimInt, E:PrimInt + Signed> PartialEq for SciValue<B,E>{
+  fn eq(&self, rhs: &SciValue<B,E>) -> bool {
+    self.base == rhs.base && self.e_exp == rhs.e_exp
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Eq for SciValue<B,E>{}
+
+/**
+ * Hashes the reduced form, so values like `20e2` and
+ * `2e3` hash alike.  This is consistent with the exact
+ * equality above, and equally with the value equality of
+ * `OrderedSci`.
+ */
+impl<B:PrimInt + FromPrimitive + Hash, E:PrimInt + Signed + Hash> Hash for SciValue<B,E>{
+  fn hash<H:Hasher>(&self, state:&mut H) {
+    let reduced = self.reduce();
+    reduced.base.hash(state);
+    reduced.e_exp.hash(state);
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> PartialOrd for SciValue<B,E>{
+  fn partial_cmp(&self, other:&SciValue<B,E>) -> Option<Ordering>{
+    Some(self.cmp(other))
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Ord for SciValue<B,E>{
+  fn cmp(&self, other:&SciValue<B,E>) -> Ordering {
+    match self.e_exp.cmp(&other.e_exp) {
+      Ordering::Equal => self.base.cmp(&other.base),
+      retval          => retval,
+    }
+  }
+}
+
+/**
+ * Wrapper giving a `SciValue` a total ordering by
+ * numeric value, with equality and hashing to match.
+ *
+ * Unlike `SciValue` itself, `20e2` and `2e3` are equal
+ * keys, so this is suitable for use in a `BTreeMap`,
+ * `HashMap` or `BinaryHeap`.
+ */
+pub struct OrderedSci<B, E:PrimInt + Signed>(pub SciValue<B,E>);
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialEq for OrderedSci<B,E>{
+  fn eq(&self, rhs: &OrderedSci<B,E>) -> bool {
+    self.0.cmp_value(&rhs.0) == Ordering::Equal
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Eq for OrderedSci<B,E>{}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialOrd for OrderedSci<B,E>{
+  fn partial_cmp(&self, other:&OrderedSci<B,E>) -> Option<Ordering>{
+    Some(self.cmp(other))
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Ord for OrderedSci<B,E>{
+  fn cmp(&self, other:&OrderedSci<B,E>) -> Ordering {
+    self.0.cmp_value(&other.0)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive + Hash, E:PrimInt + Signed + Hash> Hash for OrderedSci<B,E>{
+  fn hash<H:Hasher>(&self, state:&mut H) {
+    //Equal values share a reduced form
+    self.0.hash(state);
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for OrderedSci<B,E>{
+  fn clone(&self) -> OrderedSci<B,E> {
+    OrderedSci(self.0)
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for OrderedSci<B,E>{
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    fmtr.write_str(format!("OrderedSci({:?})", self.0).as_str())
+  }
+}
+
+/**
+ * Ordering adapter comparing values by absolute value,
+ * for use in heaps and sorts.
+ */
+pub struct ByMagnitude<B, E:PrimInt + Signed>(pub SciValue<B,E>);
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialEq for ByMagnitude<B,E>{
+  fn eq(&self, rhs: &ByMagnitude<B,E>) -> bool {
+    self.0.cmp_magnitude(&rhs.0) == Ordering::Equal
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Eq for ByMagnitude<B,E>{}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialOrd for ByMagnitude<B,E>{
+  fn partial_cmp(&self, other:&ByMagnitude<B,E>) -> Option<Ordering>{
+    Some(self.cmp(other))
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Ord for ByMagnitude<B,E>{
+  fn cmp(&self, other:&ByMagnitude<B,E>) -> Ordering {
+    self.0.cmp_magnitude(&other.0)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for ByMagnitude<B,E>{
+  fn clone(&self) -> ByMagnitude<B,E> {
+    ByMagnitude(self.0)
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for ByMagnitude<B,E>{
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    fmtr.write_str(format!("ByMagnitude({:?})", self.0).as_str())
+  }
+}
+
+/**
+ * Ordering adapter comparing values by order of
+ * magnitude alone, so that all values within the
+ * same power of ten are equal.  Zero is less than
+ * every other value.
+ */
+pub struct ByExponent<B, E:PrimInt + Signed>(pub SciValue<B,E>);
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialEq for ByExponent<B,E>{
+  fn eq(&self, rhs: &ByExponent<B,E>) -> bool {
+    self.0.order_of_magnitude() == rhs.0.order_of_magnitude()
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Eq for ByExponent<B,E>{}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> PartialOrd for ByExponent<B,E>{
+  fn partial_cmp(&self, other:&ByExponent<B,E>) -> Option<Ordering>{
+    Some(self.cmp(other))
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Ord for ByExponent<B,E>{
+  fn cmp(&self, other:&ByExponent<B,E>) -> Ordering {
+    self.0.order_of_magnitude().cmp(&other.0.order_of_magnitude())
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for ByExponent<B,E>{
+  fn clone(&self) -> ByExponent<B,E> {
+    ByExponent(self.0)
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for ByExponent<B,E>{
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    fmtr.write_str(format!("ByExponent({:?})", self.0).as_str())
+  }
+}
+
+impl<B:PrimInt + FromPrimitive,E:PrimInt + Signed> Add for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn add(self, unmatched_rhs:SciValue<B,E>) -> SciValue<B,E> {
+    let (lhs, rhs) = match_exponents(self, unmatched_rhs).unwrap_or_else(|err| panic!("{}", err));
+    SciValue{base: lhs.base + rhs.base, e_exp: lhs.e_exp}
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Sub for SciValue<B,E>{
+  type Output = SciValue<B,E>;
+
+  fn sub(self, unmatched_rhs:SciValue<B,E>) -> SciValue<B,E> {
+    let (lhs, rhs) = match_exponents(self, unmatched_rhs).unwrap_or_else(|err| panic!("{}", err));
+    SciValue{base: lhs.base - rhs.base, e_exp: lhs.e_exp}
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Mul for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn mul(self, rhs:SciValue<B,E>) -> SciValue<B,E> {
+    SciValue{base: self.base * rhs.base, e_exp: self.e_exp + rhs.e_exp}
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Div for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn div(self, rhs:SciValue<B,E>) -> SciValue<B,E> {
+    let (base, exp) = self.scale_for_div(&rhs);
+    SciValue{base: base / rhs.base, e_exp: exp - rhs.e_exp}
+  }
+}
+
+/**
+ * Arithmetic with a bare base value, which is treated
+ * as having an exponent of zero.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Add<B> for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn add(self, rhs:B) -> SciValue<B,E> {
+    self + SciValue::wrap(rhs)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Sub<B> for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn sub(self, rhs:B) -> SciValue<B,E> {
+    self - SciValue::wrap(rhs)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Mul<B> for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn mul(self, rhs:B) -> SciValue<B,E> {
+    SciValue{base: self.base * rhs, e_exp: self.e_exp}
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Div<B> for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn div(self, rhs:B) -> SciValue<B,E> {
+    self / SciValue::wrap(rhs)
+  }
+}
+
+/**
+ * Binary operators on borrowed values, in every owned and
+ * borrowed combination, as the std numeric types have.
+ */
+macro_rules! ref_binop_impl {
+  ($op:ident, $method:ident, [$($b_bound:tt)*], [$($e_bound:tt)*]) => {
+    impl<'a, B:$($b_bound)*, E:$($e_bound)*> $op<&'a SciValue<B,E>> for SciValue<B,E> {
+      type Output = SciValue<B,E>;
+
+      fn $method(self, rhs:&'a SciValue<B,E>) -> SciValue<B,E> {
+        $op::$method(self, *rhs)
+      }
+    }
+
+    impl<'a, B:$($b_bound)*, E:$($e_bound)*> $op<SciValue<B,E>> for &'a SciValue<B,E> {
+      type Output = SciValue<B,E>;
+
+      fn $method(self, rhs:SciValue<B,E>) -> SciValue<B,E> {
+        $op::$method(*self, rhs)
+      }
+    }
+
+    impl<'a, 'b, B:$($b_bound)*, E:$($e_bound)*> $op<&'b SciValue<B,E>> for &'a SciValue<B,E> {
+      type Output = SciValue<B,E>;
+
+      fn $method(self, rhs:&'b SciValue<B,E>) -> SciValue<B,E> {
+        $op::$method(*self, *rhs)
+      }
+    }
+  }
+}
+
+ref_binop_impl!(Add, add, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+ref_binop_impl!(Sub, sub, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+ref_binop_impl!(Mul, mul, [PrimInt], [PrimInt + Signed]);
+ref_binop_impl!(Div, div, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+
+/**
+ * Conversions to primitives truncate toward zero, as
+ * they do for floats, and fail if the integer part
+ * doesn't fit.  `to_f64` applies the exponent, and
+ * rounds to the nearest `f64` wherever the value has
+ * more precision than one.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> ToPrimitive for SciValue<B,E> {
+  fn to_i64(&self) -> Option<i64> {
+    self.to_integer(RoundingMode::Truncate).ok().and_then(|int| int.to_i64())
+  }
+
+  fn to_u64(&self) -> Option<u64> {
+    self.to_integer(RoundingMode::Truncate).ok().and_then(|int| int.to_u64())
+  }
+
+  fn to_i128(&self) -> Option<i128> {
+    self.to_integer(RoundingMode::Truncate).ok().and_then(|int| int.to_i128())
+  }
+
+  fn to_u128(&self) -> Option<u128> {
+    self.to_integer(RoundingMode::Truncate).ok().and_then(|int| int.to_u128())
+  }
+
+  fn to_f64(&self) -> Option<f64> {
+    float_text(self).parse().ok()
+  }
+}
+
+/**
+ * Integers convert at an exponent of zero.  Floats take
+ * the shortest decimal form which reads back as the same
+ * float, so `0.1f64` gives `1e-1`; infinities and NaN
+ * have no value.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> FromPrimitive for SciValue<B,E> {
+  fn from_i64(val:i64) -> Option<SciValue<B,E>> {
+    <B as FromPrimitive>::from_i64(val).map(SciValue::wrap)
+  }
+
+  fn from_u64(val:u64) -> Option<SciValue<B,E>> {
+    <B as FromPrimitive>::from_u64(val).map(SciValue::wrap)
+  }
+
+  fn from_i128(val:i128) -> Option<SciValue<B,E>> {
+    <B as FromPrimitive>::from_i128(val).map(SciValue::wrap)
+  }
+
+  fn from_u128(val:u128) -> Option<SciValue<B,E>> {
+    <B as FromPrimitive>::from_u128(val).map(SciValue::wrap)
+  }
+
+  fn from_f64(val:f64) -> Option<SciValue<B,E>> {
+    if !val.is_finite() {
+      return None;
+    }
+    SciValue::from_rust_literal(&format!("{:e}", val)).ok()
+  }
+}
+
+/**
+ * Sums with the same exponent matching (and panics) as
+ * `+`, so the result has the lowest exponent of the
+ * values.  The sum of no values is the identity `0e0`.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Sum for SciValue<B,E> {
+  fn sum<I:Iterator<Item=SciValue<B,E>>>(mut iter:I) -> SciValue<B,E> {
+    match iter.next() {
+      Some(first) => iter.fold(first, |total, val| total + val),
+      None        => SciValue::default()
+    }
+  }
+}
+
+impl<'a, B:PrimInt + FromPrimitive, E:PrimInt + Signed> Sum<&'a SciValue<B,E>> for SciValue<B,E> {
+  fn sum<I:Iterator<Item=&'a SciValue<B,E>>>(iter:I) -> SciValue<B,E> {
+    iter.copied().sum()
+  }
+}
+
+/**
+ * The product of no values is the identity `1e0`.
+ */
+impl<B:PrimInt, E:PrimInt + Signed> Product for SciValue<B,E> {
+  fn product<I:Iterator<Item=SciValue<B,E>>>(mut iter:I) -> SciValue<B,E> {
+    match iter.next() {
+      Some(first) => iter.fold(first, |total, val| total * val),
+      None        => SciValue::one()
+    }
+  }
+}
+
+impl<'a, B:PrimInt, E:PrimInt + Signed> Product<&'a SciValue<B,E>> for SciValue<B,E> {
+  fn product<I:Iterator<Item=&'a SciValue<B,E>>>(iter:I) -> SciValue<B,E> {
+    iter.copied().product()
+  }
+}
+
+/**
+ * The num-traits forms of the checked operations, for
+ * generic code.  These are the inherent methods of the
+ * same names.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> CheckedAdd for SciValue<B,E> {
+  fn checked_add(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    SciValue::checked_add(self, rhs)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> CheckedSub for SciValue<B,E> {
+  fn checked_sub(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    SciValue::checked_sub(self, rhs)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> CheckedMul for SciValue<B,E> {
+  fn checked_mul(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    SciValue::checked_mul(self, rhs)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> CheckedDiv for SciValue<B,E> {
+  fn checked_div(&self, rhs:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+    SciValue::checked_div(self, rhs)
+  }
+}
+
+/**
+ * Raising to a power through num-traits.  Unlike the
+ * inherent `pow`, a power of zero always gives one, and
+ * overflow of the base or exponent panics.
+ */
+impl<B:PrimInt, E:PrimInt + Signed> Pow<u32> for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn pow(self, rhs:u32) -> SciValue<B,E> {
+    <E as NumCast>::from(rhs)
+      .and_then(|exp| self.checked_pow(exp))
+      .expect("attempt to raise a SciValue to a power with overflow")
+  }
+}
+
+/**
+ * Compound assignment, with the same semantics (and
+ * panics) as the binary operators, for owned and
+ * borrowed right hand sides.
+ */
+macro_rules! assign_impl {
+  ($assign:ident, $method:ident, $op:ident, $op_method:ident, [$($b_bound:tt)*], [$($e_bound:tt)*]) => {
+    impl<B:$($b_bound)*, E:$($e_bound)*> $assign for SciValue<B,E> {
+      fn $method(&mut self, rhs:SciValue<B,E>) {
+        *self = $op::$op_method(*self, rhs);
+      }
+    }
+
+    impl<'a, B:$($b_bound)*, E:$($e_bound)*> $assign<&'a SciValue<B,E>> for SciValue<B,E> {
+      fn $method(&mut self, rhs:&'a SciValue<B,E>) {
+        *self = $op::$op_method(*self, *rhs);
+      }
+    }
+  }
+}
+
+assign_impl!(AddAssign, add_assign, Add, add, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+assign_impl!(SubAssign, sub_assign, Sub, sub, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+assign_impl!(MulAssign, mul_assign, Mul, mul, [PrimInt], [PrimInt + Signed]);
+assign_impl!(DivAssign, div_assign, Div, div, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+
+/**
+ * The remainder of `div_rem`.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Rem for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn rem(self, rhs:SciValue<B,E>) -> SciValue<B,E> {
+    self.div_rem(rhs).1
+  }
+}
+
+/**
+ * Negates the base, overflowing as the base type does for
+ * its minimum value; see `checked_neg` for a fallible
+ * version.
+ */
+impl<B:PrimInt + Signed, E:PrimInt + Signed> Neg for SciValue<B,E> {
+  type Output = SciValue<B,E>;
+
+  fn neg(self) -> SciValue<B,E> {
+    SciValue{base: -self.base, e_exp: self.e_exp}
+  }
+}
+
+/**
+ * Signs are taken from the base alone.  `abs` keeps the
+ * exponent, and overflows for `B::MIN` as the base type
+ * would.
+ */
+impl<B, E> Signed for SciValue<B,E>
+    where B:PrimInt + Signed + FromPrimitive,
+          E:PrimInt + Signed + FromPrimitive {
+  fn abs(&self) -> SciValue<B,E> {
+    SciValue{base: self.base.abs(), e_exp: self.e_exp}
+  }
+
+  fn abs_sub(&self, other:&SciValue<B,E>) -> SciValue<B,E> {
+    if self.cmp_value(other) == Ordering::Greater {
+      *self - *other
+    } else {
+      SciValue::zero()
+    }
+  }
+
+  fn signum(&self) -> SciValue<B,E> {
+    SciValue::wrap(self.base.signum())
+  }
+
+  fn is_positive(&self) -> bool {
+    self.base > B::zero()
+  }
+
+  fn is_negative(&self) -> bool {
+    self.base < B::zero()
+  }
+}
+
+/**
+ * Sums a sequence of values, accumulating in the wider
+ * counterpart of their base type so that long sums
+ * don't overflow the type the values are stored in.
+ */
+pub fn sum_widening<B, E, I>(iter:I) -> SciValue<<B as Widen>::Wide,E>
+    where B:Widen, <B as Widen>::Wide:FromPrimitive, E:PrimInt + Signed, I:Iterator<Item=SciValue<B,E>> {
+  let mut widened = iter.map(|val| val.widen());
+  match widened.next() {
+    Some(first) => widened.fold(first, |total, val| total + val),
+    None        => SciValue::wrap(B::Wide::zero())
+  }
+}
+
+/**
+ * Computes the dot product of two slices of values.
+ *
+ * Each product is formed in the wider counterpart of the
+ * base type, so that it can't overflow, and the products
+ * are then summed grouped by exponent.  As every step is
+ * exact, catastrophic cancellation can't occur; the only
+ * remaining hazard - overflow - is checked throughout.
+ *
+ * Returns None if the slices differ in length or if
+ * the sum overflows even the wider type.
+ */
+pub fn dot_compensated<B, E>(lhs:&[SciValue<B,E>], rhs:&[SciValue<B,E>]) -> Option<SciValue<<B as Widen>::Wide,E>>
+    where B:Widen, <B as Widen>::Wide:FromPrimitive, E:PrimInt + Signed {
+  if lhs.len() != rhs.len() {
+    return None;
+  }
+
+  let mut products = Vec::with_capacity(lhs.len());
+  for (l, r) in lhs.iter().zip(rhs.iter()) {
+    match l.widen().checked_mul(&r.widen()) {
+      Some(product) => products.push(product),
+      None          => return None
+    }
+  }
+  products.sort_by_key(|val| Reverse(val.e_exp));
+
+  let mut total: Option<SciValue<<B as Widen>::Wide,E>> = None;
+  let mut pos = 0usize;
+  while pos < products.len() {
+    let group_exp = products[pos].e_exp;
+    let mut group = SciValue::wrap_with_exponent(B::Wide::zero(), group_exp);
+    while pos < products.len() && products[pos].e_exp == group_exp {
+      group = match group.base.checked_add(&products[pos].base) {
+        Some(base) => SciValue::wrap_with_exponent(base, group_exp),
+        None       => return None
+      };
+      pos += 1;
+    }
+    total = match total {
+      Some(sum) => match sum.checked_add(&group) {
+        Some(sum) => Some(sum),
+        None      => return None
+      },
+      None => Some(group)
+    };
+  }
+  Some(total.unwrap_or(SciValue::wrap(B::Wide::zero())))
+}
+
+/**
+ * Computes the mean of the values exactly, as a mean
+ * truncated toward zero plus the residue left over, so
+ * that the sum of the values is exactly
+ * `mean * count + residue`.  Both are expressed at the
+ * exponent of the sum.  Returns None for no values.
+ */
+pub fn mean_exact<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(values:&[SciValue<B,E>]) -> Option<(SciValue<B,E>, SciValue<B,E>)> {
+  if values.is_empty() {
+    return None;
+  }
+  let mut total = values[0];
+  total.accumulate(&values[1..]);
+  split_mean(total, values.len())
+}
+
+/**
+ * Splits a sum of `count` values into the truncated mean
+ * and residue `mean_exact` gives.
+ */
+fn split_mean<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(total:SciValue<B,E>, count:usize) -> Option<(SciValue<B,E>, SciValue<B,E>)> {
+  let count = <B as FromPrimitive>::from_usize(count)?;
+  Some((SciValue::wrap_with_exponent(total.base / count, total.e_exp),
+        SciValue::wrap_with_exponent(total.base % count, total.e_exp)))
+}
+
+/**
+ * The difference `a - b` between two ratios, in
+ * percentage points, rounded using `mode` to the decimal
+ * place of 10^exp.  A ratio of 0.25 is 25%, so
+ * `0.25` and `0.2` differ by 5 percentage points.  The
+ * result always has the exponent `exp`.
+ *
+ * Returns None if the result doesn't fit in the base and
+ * exponent types.
+ */
+pub fn percentage_points_diff<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(a:&SciValue<B,E>, b:&SciValue<B,E>, exp:E, mode:RoundingMode) -> Option<SciValue<B,E>> {
+  let diff = a.checked_sub(b)?;
+  //Scaling by 100 only moves the exponent
+  let points_exp = diff.e_exp.checked_add(&E::one()).and_then(|exp| exp.checked_add(&E::one()))?;
+  let points = SciValue::wrap_with_exponent(diff.base, points_exp);
+  points.round_to_exponent(exp, mode).rebalance_to_exponent(exp).ok()
+}
+
+/**
+ * Finds the smallest and largest values in a single
+ * pass, comparing by numeric value.  As with `Iterator`'s
+ * `min` and `max`, the first of several equal minimums
+ * and the last of several equal maximums are returned.
+ * Returns None for an empty sequence.
+ */
+pub fn minmax<B, E, I>(iter:I) -> Option<(SciValue<B,E>, SciValue<B,E>)>
+    where B:PrimInt + FromPrimitive, E:PrimInt + Signed, I:Iterator<Item=SciValue<B,E>> {
+  let mut iter = iter;
+  let first = iter.next()?;
+
+  let mut min = first;
+  let mut max = first;
+  for val in iter {
+    if val.cmp_value(&min) == Ordering::Less {
+      min = val;
+    } else if val.cmp_value(&max) != Ordering::Less {
+      max = val;
+    }
+  }
+  Some((min, max))
+}
+
+/**
+ * Floor of the square root of a non-negative integer,
+ * by Newton's method.
+ */
+fn isqrt_base<B:PrimInt + FromPrimitive>(val:B) -> B {
+  let type_b_2 = B::one() + B::one();
+  if val < type_b_2 {
+    return val;
+  }
+
+  //val/2 + 1 is always at least the root, and
+  //the estimate decreases from there
+  let mut estimate = val / type_b_2 + B::one();
+  let mut next     = (estimate + val / estimate) / type_b_2;
+  while next < estimate {
+    estimate = next;
+    next     = (estimate + val / estimate) / type_b_2;
+  }
+  estimate
+}
+
+/**
+ * Given the root and remainder of some value, computes
+ * the root and remainder of that value times 100, or
+ * None if doing so overflows.
+ */
+fn next_root_digit<B:PrimInt + FromPrimitive>(root:B, rem:B) -> Option<(B,B)> {
+  let type_b_10 = base_constant::<B>(10, "isqrt");
+  let type_b_20 = base_constant::<B>(20, "isqrt");
+  let type_b_100 = base_constant::<B>(100, "isqrt");
+
+  let target = rem.checked_mul(&type_b_100)?;
+  let double_root = root.checked_mul(&type_b_20)?;
+  let shifted_root = root.checked_mul(&type_b_10)?;
+
+  //Find the largest digit d where (20 * root + d) * d <= target
+  let mut digit = base_constant::<B>(9, "isqrt");
+  loop {
+    let used = double_root.checked_add(&digit).and_then(|val| val.checked_mul(&digit));
+    if let Some(used) = used {
+      if used <= target {
+        return Some((shifted_root + digit, target - used));
+      }
+    }
+    digit = digit - B::one();
+  }
+}
+
+/**
+ * Decides whether a truncated result should move one unit
+ * away from zero under the given mode, from the magnitude
+ * of the first dropped digit and whether any later dropped
+ * digit was nonzero.
+ */
+fn mode_rounds_away<B:PrimInt + FromPrimitive>(mode:RoundingMode, quot:B, digit:B, sticky:bool, negative:bool) -> bool {
+  let type_b_0 = B::zero();
+  let type_b_2 = base_constant::<B>(2, "round");
+  let type_b_5 = base_constant::<B>(5, "round");
+
+  let inexact = digit != type_b_0 || sticky;
+  match mode {
+    RoundingMode::Truncate         => false,
+    RoundingMode::Floor            => negative && inexact,
+    RoundingMode::Ceiling          => !negative && inexact,
+    RoundingMode::HalfEven         => digit > type_b_5 ||
+                                      (digit == type_b_5 && (sticky || quot % type_b_2 != type_b_0)),
+    RoundingMode::HalfAwayFromZero => digit >= type_b_5
+  }
+}
+
+/**
+ * Raises a base to a non-negative power by squaring,
+ * returning None on overflow.  Bases of 0, 1 and -1 can't
+ * overflow, so are worked out directly.
+ */
+fn checked_base_pow<B:PrimInt, E:PrimInt + Signed>(base:B, exp:E) -> Option<B> {
+  let type_e_2 = E::one() + E::one();
+  if exp == E::zero() {
+    return Some(B::one());
+  }
+  if base == B::zero() || base == B::one() {
+    return Some(base);
+  }
+  if B::min_value() < B::zero() && base == B::zero() - B::one() {
+    return Some(if exp % type_e_2 == E::zero() { B::one() } else { base });
+  }
+
+  let mut result    = B::one();
+  let mut square    = base;
+  let mut remaining = exp;
+  loop {
+    if remaining % type_e_2 == E::one() {
+      result = result.checked_mul(&square)?;
+    }
+    remaining = remaining / type_e_2;
+    if remaining == E::zero() {
+      return Some(result);
+    }
+    square = square.checked_mul(&square)?;
+  }
+}
+
+/**
+ * The most negative or most positive base at the given
+ * exponent.
+ */
+fn saturated<B:PrimInt, E:PrimInt + Signed>(negative:bool, exp:E) -> SciValue<B,E> {
+  SciValue::wrap_with_exponent(if negative { B::min_value() } else { B::max_value() }, exp)
+}
+
+/**
+ * Divides the base by 10^count, truncating toward zero.
+ *
+ * Returns the quotient, the last digit removed (with the
+ * sign of the base) and whether any digit removed before
+ * it was nonzero - everything needed to round the result.
+ */
+fn drop_digits<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(base:B, count:E) -> (B, B, bool) {
+  let type_b_0 = B::zero();
+  let type_b_10 = base_constant::<B>(10, "drop_digits");
+
+  let mut quot      = base;
+  let mut digit     = type_b_0;
+  let mut sticky    = false;
+  let mut remaining = count;
+  while remaining > E::zero() {
+    if quot == type_b_0 && digit == type_b_0 {
+      //Only zeros are left to drop
+      break;
+    }
+    sticky    = sticky || digit != type_b_0;
+    digit     = quot % type_b_10;
+    quot      = quot / type_b_10;
+    remaining = remaining - E::one();
+  }
+  (quot, digit, sticky)
+}
+
+/**
+ * The decimal digits of the base's absolute value,
+ * most significant first.  Empty for zero.
+ */
+fn decimal_digits<B:PrimInt + FromPrimitive>(base:B) -> Vec<u8> {
+  let type_b_0 = B::zero();
+  let type_b_10 = base_constant::<B>(10, "decimal_digits");
+
+  let mut digits = Vec::new();
+  let mut remaining = base;
+  while remaining != type_b_0 {
+    let digit = remaining % type_b_10;
+    let magnitude = if digit < type_b_0 { type_b_0 - digit } else { digit };
+    digits.push(magnitude.to_u8().expect("Couldn't convert a digit to u8"));
+    remaining = remaining / type_b_10;
+  }
+  digits.reverse();
+  digits
+}
+
+fn base_from_magnitude<B:PrimInt>(negative:bool, mag:u128) -> Option<B> {
+  if !negative {
+    <B as NumCast>::from(mag)
+  } else if mag <= 1u128 << 127 {
+    <B as NumCast>::from((mag as i128).wrapping_neg())
+  } else {
+    None
+  }
+}
+
+/**
+ * Converts an exponent to an i64, saturating exponents
+ * too large for it.  This still orders them correctly
+ * against all smaller exponents.
+ */
+fn saturating_exp_to_i64<E:PrimInt + Signed>(exp:E) -> i64 {
+  match exp.to_i64() {
+    Some(converted)                    => converted,
+    None if exp > E::zero()   => i64::MAX,
+    None                               => i64::MIN
+  }
+}
+
+/**
+ * The value as decimal text for the std float parsers,
+ * which round correctly, and only once.
+ */
+fn float_text<B:PrimInt, E:PrimInt + Signed>(val:&SciValue<B,E>) -> String {
+  let base = match val.base.to_i128() {
+    Some(base) => base.to_string(),
+    None       => val.base.to_u128().expect("Couldn't convert base to u128").to_string()
+  };
+  format!("{}e{}", base, saturating_exp_to_i64(val.e_exp))
+}
+
+/**
+ * Converts a small constant needed by an operation
+ * into the base type.
+ */
+fn base_constant<B:FromPrimitive>(val:i64, operation:&'static str) -> B {
+  match <B as FromPrimitive>::from_i64(val) {
+    Some(converted) => converted,
+    None            => panic!("{}", ConvertError{
+      operation,
+      target:    ConvertTarget::Base,
+      mantissa:  Some(val),
+      exponent:  None
+    })
+  }
 }
 
-impl<B: Int + Debug, E: SignedInt + Debug> Debug for SciValue<B,E> {
-  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
-    fmtr.write_str(format!("SciValue{}base : {:?}, e_exp : {:?}{}", "{", self.base, self.e_exp, "}").as_slice())
+/**
+ * Converts a small constant needed by an operation
+ * into the exponent type.
+ */
+fn exp_constant<E:FromPrimitive>(val:i64, operation:&'static str) -> E {
+  match <E as FromPrimitive>::from_i64(val) {
+    Some(converted) => converted,
+    None            => panic!("{}", ConvertError{
+      operation,
+      target:    ConvertTarget::Exponent,
+      mantissa:  None,
+      exponent:  Some(val)
+    })
   }
 }
 
-impl<B,E> Clone for SciValue<B,E> where B: Int, E:SignedInt {
-  fn clone(&self) -> SciValue<B,E> {
-    SciValue::wrap_with_exponent(self.base, self.e_exp)
+/**
+ * Computes 10^k in the base type, or None if it
+ * doesn't fit.
+ */
+pub fn pow10<B:PrimInt + FromPrimitive>(k:usize) -> Option<B> {
+  let type_b_10 = base_constant::<B>(10, "pow10");
+
+  let mut result = B::one();
+  for _ in 0usize..k {
+    result = result.checked_mul(&type_b_10)?;
   }
+  Some(result)
 }
 
+/**
+ * The largest k for which 10^k fits in the base type,
+ * i.e. the furthest a base can ever be scaled by
+ * exponent matching.  For instance 2 for `u8`, 18
+ * for `i64` and 19 for `u64`.
+ */
+pub fn max_exact_pow10<B:PrimInt + FromPrimitive>() -> usize {
+  let type_b_10 = base_constant::<B>(10, "max_exact_pow10");
 
-impl<B:Int, E:SignedInt> PartialEq for SciValue<B,E>{
-  fn eq(&self, rhs: &SciValue<B,E>) -> bool {
-    return self.base == rhs.base && self.e_exp == rhs.e_exp;
+  let mut k = 0usize;
+  let mut power = B::one();
+  while power <= B::max_value() / type_b_10 {
+    power = power * type_b_10;
+    k += 1;
   }
+  k
 }
 
-impl<B:Int, E:SignedInt> Eq for SciValue<B,E>{}
-
-impl<B:Int, E:SignedInt> PartialOrd for SciValue<B,E>{
-  fn partial_cmp(&self, other:&SciValue<B,E>) -> Option<Ordering>{
-    match self.e_exp.partial_cmp(&other.e_exp) {
-      Some(Ordering::Equal) => self.base.partial_cmp(&other.base),
-      retval@Some(_)        => retval,
-      None                  => None
-    }
+/**
+ * 10^exp for an exponent value, or None if it's
+ * negative or doesn't fit in the base type.
+ */
+fn checked_pow10<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(exp:E) -> Option<B> {
+  if exp < E::zero() {
+    return None;
   }
+  exp.to_usize().and_then(|k| pow10::<B>(k))
 }
 
-impl<B:Int, E:SignedInt> Ord for SciValue<B,E>{
-  fn cmp(&self, other:&SciValue<B,E>) -> Ordering {
-    match self.e_exp.cmp(&other.e_exp) {
-      Ordering::Equal => self.base.cmp(&other.base),
-      retval          => retval,
-    }
+/**
+ * Brings both values to the smaller of their exponents,
+ * returning None if the scaled base overflows.
+ */
+fn match_exponents_checked<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(lhs:&SciValue<B,E>, rhs:&SciValue<B,E>) -> Option<(SciValue<B,E>, SciValue<B,E>)> {
+  if lhs.e_exp > rhs.e_exp {
+    return match_exponents_checked(rhs, lhs).map(|(newrhs, newlhs)| (newlhs, newrhs));
   }
+  let scaled = rhs.e_exp.checked_sub(&lhs.e_exp)
+                        .and_then(|diff| checked_pow10::<B,E>(diff))
+                        .and_then(|scale| rhs.base.checked_mul(&scale));
+  scaled.map(|base| (*lhs, SciValue::wrap_with_exponent(base, lhs.e_exp)))
 }
 
-impl<B:Int + FromPrimitive,E:SignedInt> Add for SciValue<B,E> {
-  type Output = SciValue<B,E>;
+/**
+ * Brings the values to the lower of their exponents,
+ * wrapping the rescaled base on overflow and flagging
+ * whether it did.  Scaling by 10^k is a multiple of 2^k,
+ * so once k reaches the bit width the wrapped base is
+ * zero and further steps can be skipped.
+ */
+fn match_exponents_wrapping<B, E>(lhs:&SciValue<B,E>, rhs:&SciValue<B,E>) -> (SciValue<B,E>, SciValue<B,E>, bool)
+    where B:PrimInt + FromPrimitive + WrappingMul, E:PrimInt + Signed {
+  if lhs.e_exp > rhs.e_exp {
+    let (newrhs, newlhs, over) = match_exponents_wrapping(rhs, lhs);
+    return (newlhs, newrhs, over);
+  }
+  let type_b_10 = base_constant::<B>(10, "match_exponents");
+  let bits  = mem::size_of::<B>() * 8;
+  let steps = rhs.e_exp.checked_sub(&lhs.e_exp).and_then(|diff| diff.to_usize()).map_or(bits, |diff| diff.min(bits));
 
-  fn add(self, unmatched_rhs:SciValue<B,E>) -> SciValue<B,E> {
-    let (lhs, rhs) = match_exponents(self, unmatched_rhs);
-    SciValue{base: lhs.base + rhs.base, e_exp: lhs.e_exp}
+  let mut base = rhs.base;
+  let mut over = false;
+  for _ in 0..steps {
+    over = over || base.checked_mul(&type_b_10).is_none();
+    base = base.wrapping_mul(&type_b_10);
   }
+  (*lhs, SciValue::wrap_with_exponent(base, lhs.e_exp), over)
 }
 
-impl<B:Int + FromPrimitive + Debug, E:SignedInt + Debug> Sub for SciValue<B,E>{
-  type Output = SciValue<B,E>;
+/**
+ * The outcome of `SciValue::wide_sum`.  An overflowing sum
+ * records only whether it was negative.
+ */
+enum WideSum<B,E:PrimInt + Signed> {
+  Exact(SciValue<B,E>),
+  Truncated(SciValue<B,E>),
+  Overflow(bool)
+}
 
-  fn sub(self, unmatched_rhs:SciValue<B,E>) -> SciValue<B,E> {
-    let (lhs, rhs) = match_exponents(self, unmatched_rhs);
-    println!("Matched: {:?},{:?}", lhs, rhs);
-    SciValue{base: lhs.base - rhs.base, e_exp: lhs.e_exp}
+impl<B:PrimInt, E:PrimInt + Signed> WideSum<B,E> {
+  /**
+   * The saturated result, reporting to the observer how
+   * the operation degraded.  An exact sum only gets here
+   * once `checked_add` has failed, so it's always at a
+   * coarser exponent than the operands.
+   */
+  fn saturate(self, operation:&'static str) -> SciValue<B,E> {
+    let (kind, sum) = match self {
+      WideSum::Exact(sum)                                   => (OverflowKind::Widened, sum),
+      WideSum::Truncated(sum)                               => (OverflowKind::PrecisionLost, sum),
+      WideSum::Overflow(true) if B::min_value() == B::zero() => (OverflowKind::Saturated, SciValue::wrap_with_exponent(B::zero(), E::zero())),
+      WideSum::Overflow(negative)                           => (OverflowKind::Saturated, saturated(negative, E::max_value()))
+    };
+    observer::notify(kind, operation);
+    sum
   }
 }
 
-impl<B:Int, E:SignedInt> Mul for SciValue<B,E> {
-  type Output = SciValue<B,E>;
-
-  fn mul(self, rhs:SciValue<B,E>) -> SciValue<B,E> {
-    SciValue{base: self.base * rhs.base, e_exp: self.e_exp + rhs.e_exp}
-  }
+/**
+ * The decimal digits of a base's magnitude, least
+ * significant first.
+ */
+fn wide_digits<B:PrimInt + FromPrimitive>(base:B) -> Vec<u8> {
+  let mut digits = decimal_digits(base);
+  digits.reverse();
+  digits
 }
 
-impl<B:Int + FromPrimitive, E:SignedInt> Div for SciValue<B,E> {
-  type Output = SciValue<B,E>;
+fn cmp_digits(lhs:&[u8], rhs:&[u8]) -> Ordering {
+  let significant = |digits:&[u8]| digits.len() - digits.iter().rev().take_while(|&&digit| digit == 0).count();
+  let (lhs, rhs) = (&lhs[..significant(lhs)], &rhs[..significant(rhs)]);
+  lhs.len().cmp(&rhs.len()).then_with(|| lhs.iter().rev().cmp(rhs.iter().rev()))
+}
 
-  fn div(mut self, rhs:SciValue<B,E>) -> SciValue<B,E> {
-    let b_ten = <B as FromPrimitive>::from_int(10is).expect("Couldn't get a value of 10 for the base type");
+fn add_digits(lhs:&[u8], rhs:&[u8]) -> Vec<u8> {
+  let mut sum   = Vec::with_capacity(lhs.len().max(rhs.len()) + 1);
+  let mut carry = 0u8;
+  for place in 0..lhs.len().max(rhs.len()) {
+    let digit = lhs.get(place).copied().unwrap_or(0) + rhs.get(place).copied().unwrap_or(0) + carry;
+    sum.push(digit % 10);
+    carry = digit / 10;
+  }
+  if carry > 0 {
+    sum.push(carry);
+  }
+  sum
+}
 
-    while self.base % rhs.base != (<B as Int>::zero()) &&
-          self.base < (<B as Int>::max_value() / b_ten) {
-      self.base = self.base * b_ten;
-      self.e_exp = self.e_exp - <E as Int>::one();
+/**
+ * Subtracts `rhs` from `lhs`, whose magnitude must be at
+ * least as large.
+ */
+fn sub_digits(lhs:&[u8], rhs:&[u8]) -> Vec<u8> {
+  let mut diff   = Vec::with_capacity(lhs.len());
+  let mut borrow = 0u8;
+  for (place, &digit) in lhs.iter().enumerate() {
+    let take = rhs.get(place).copied().unwrap_or(0) + borrow;
+    if digit >= take {
+      diff.push(digit - take);
+      borrow = 0;
+    } else {
+      diff.push(digit + 10 - take);
+      borrow = 1;
     }
-    SciValue{base: self.base / rhs.base, e_exp: self.e_exp - rhs.e_exp}
   }
+  diff
 }
 
-fn match_exponents<B:Int + FromPrimitive,E:SignedInt>(lhs:SciValue<B,E>, rhs:SciValue<B,E>) -> (SciValue<B,E>, SciValue<B,E>) {
+/**
+ * A pair of values brought to a shared exponent, or the
+ * reason they couldn't be.
+ */
+type MatchedExponents<B,E> = Result<(SciValue<B,E>, SciValue<B,E>), ConvertError>;
+
+fn match_exponents<B:PrimInt + FromPrimitive,E:PrimInt + Signed>(lhs:SciValue<B,E>, rhs:SciValue<B,E>) -> MatchedExponents<B,E> {
   if lhs.e_exp == rhs.e_exp {
-    (lhs, rhs)
+    Ok((lhs, rhs))
   }else if lhs.e_exp > rhs.e_exp {
-    let (newrhs, newlhs) = match_exponents_rhs_greater(rhs, lhs);
-    (newlhs, newrhs)
+    let (newrhs, newlhs) = match_exponents_rhs_greater(rhs, lhs)?;
+    Ok((newlhs, newrhs))
   }else{
     match_exponents_rhs_greater(lhs, rhs)
   }
 }
 
-fn match_exponents_rhs_greater<B:Int + FromPrimitive,E:SignedInt>(lhs:SciValue<B,E>, rhs:SciValue<B,E>) -> (SciValue<B,E>, SciValue<B,E>) {
-    let extra_exp = rhs.e_exp - lhs.e_exp;
+fn match_exponents_rhs_greater<B:PrimInt + FromPrimitive,E:PrimInt + Signed>(lhs:SciValue<B,E>, rhs:SciValue<B,E>) -> MatchedExponents<B,E> {
+    let extra_exp = rhs.e_exp.checked_sub(&lhs.e_exp).ok_or(ConvertError{
+      operation: "match_exponents",
+      target:    ConvertTarget::Exponent,
+      mantissa:  None,
+      exponent:  rhs.e_exp.to_i64()
+    })?;
 
-    let ten_to_pow : Option<B> = extra_exp.to_uint().and_then(|usz| <B as FromPrimitive>::from_int(10is.pow(usz)));
-    let rhs_new_base           = rhs.base * ten_to_pow.expect("Couldn't convert exponent type to base type");
+    let ten_to_pow   = checked_pow10::<B,E>(extra_exp).ok_or(ConvertError{
+      operation: "match_exponents",
+      target:    ConvertTarget::Base,
+      mantissa:  rhs.base.to_i64(),
+      exponent:  extra_exp.to_i64()
+    })?;
+    let rhs_new_base = rhs.base * ten_to_pow;
 
-    (lhs, SciValue{base: rhs_new_base, e_exp: rhs.e_exp - extra_exp})
+    Ok((lhs, SciValue{base: rhs_new_base, e_exp: rhs.e_exp - extra_exp}))
 }
 
 #[cfg(test)]
 mod test{
-  use super::SciValue;
+  use super::{SciValue, OrderedSci, ByMagnitude, ByExponent, PrecisionLoss, RoundingMode};
+  use super::{ConvertError, ConvertTarget};
+  use super::{sum_widening, dot_compensated, mean_exact, minmax, pow10, max_exact_pow10};
+  use super::percentage_points_diff;
   use super::match_exponents;
+  use std::cmp::Ordering;
+  use std::ops::Bound;
+  use std::time::Duration;
+  use std::collections::{BTreeMap, BinaryHeap};
 
   #[test]
   fn test_equals(){
-    let v1 = SciValue::wrap_with_exponent(2us,2is);
-    let v2 = SciValue::wrap_with_exponent(2us,2is);
+    let v1 = SciValue::wrap_with_exponent(2usize,2isize);
+    let v2 = SciValue::wrap_with_exponent(2usize,2isize);
     assert_eq!(v1,v1);
     assert_eq!(v2,v1);
   }
 
   #[test]
   fn test_not_equals(){
-    let v1 = SciValue::wrap_with_exponent(2us,2is);
-    let v2 = SciValue::wrap_with_exponent(2us,3is);
-    let v3 = SciValue::wrap_with_exponent(3us,2is);
-    let v4 = SciValue::wrap_with_exponent(3us,3is);
+    let v1 = SciValue::wrap_with_exponent(2usize,2isize);
+    let v2 = SciValue::wrap_with_exponent(2usize,3isize);
+    let v3 = SciValue::wrap_with_exponent(3usize,2isize);
+    let v4 = SciValue::wrap_with_exponent(3usize,3isize);
 
     assert!(v1 != v2);
     assert!(v1 != v3);
@@ -239,95 +2441,851 @@ mod test{
 
   #[test]
   fn test_exponent_matching() {
-    let lhs          = SciValue::wrap_with_exponent(5us, 2is);
-    let rhs          = SciValue::wrap_with_exponent(5us, 4is);
-    let expected_rhs = SciValue::wrap_with_exponent(500us,2is);
+    let lhs          = SciValue::wrap_with_exponent(5usize, 2isize);
+    let rhs          = SciValue::wrap_with_exponent(5usize, 4isize);
+    let expected_rhs = SciValue::wrap_with_exponent(500usize,2isize);
+
+    assert_eq!(match_exponents(lhs, rhs), Ok((lhs, expected_rhs)));
+    assert_eq!(match_exponents(rhs, lhs), Ok((expected_rhs, lhs)));
+    assert_eq!(match_exponents(lhs, lhs), Ok((lhs, lhs)));
+  }
+
+  #[test]
+  fn exponent_matching_errors() {
+    let lhs = SciValue::wrap_with_exponent(5i32, 0isize);
+    let rhs = SciValue::wrap_with_exponent(3i32, 12isize);
+    assert_eq!(match_exponents(lhs, rhs), Err(ConvertError{
+      operation: "match_exponents",
+      target:    ConvertTarget::Base,
+      mantissa:  Some(3),
+      exponent:  Some(12)
+    }));
 
-    assert_eq!(match_exponents(lhs.clone(), rhs.clone()), (lhs.clone(), expected_rhs.clone()));
-    assert_eq!(match_exponents(rhs.clone(), lhs.clone()), (expected_rhs.clone(), lhs.clone()));
-    assert_eq!(match_exponents(lhs.clone(), lhs.clone()), (lhs.clone(), lhs.clone()));
+    let low  = SciValue::wrap_with_exponent(5i32, -100i8);
+    let high = SciValue::wrap_with_exponent(3i32, 100i8);
+    assert_eq!(match_exponents(low, high).map_err(|err| err.target), Err(ConvertTarget::Exponent));
   }
 
   #[test]
   fn test_simple_add() {
-    let lhs = SciValue::wrap_with_exponent(5us, 2is);
-    let rhs = SciValue::wrap_with_exponent(16us, 2is);
+    let lhs = SciValue::wrap_with_exponent(5usize, 2isize);
+    let rhs = SciValue::wrap_with_exponent(16usize, 2isize);
 
-    assert_eq!(lhs + rhs, SciValue::wrap_with_exponent(21us, 2is));
+    assert_eq!(lhs + rhs, SciValue::wrap_with_exponent(21usize, 2isize));
   }
 
   #[test]
   fn test_add() {
-    let lhs = SciValue::wrap_with_exponent(5us, 2is);
-    let rhs = SciValue::wrap_with_exponent(21us,5is);
+    let lhs = SciValue::wrap_with_exponent(5usize, 2isize);
+    let rhs = SciValue::wrap_with_exponent(21usize,5isize);
 
-    assert_eq!(lhs + rhs, SciValue::wrap_with_exponent(21005us, 2is));
+    assert_eq!(lhs + rhs, SciValue::wrap_with_exponent(21005usize, 2isize));
   }
 
   #[test]
   fn test_simple_sub() {
-    let lhs = SciValue::wrap_with_exponent(5us, 2is);
-    let rhs = SciValue::wrap_with_exponent(2us, 2is);
+    let lhs = SciValue::wrap_with_exponent(5usize, 2isize);
+    let rhs = SciValue::wrap_with_exponent(2usize, 2isize);
 
-    assert_eq!(lhs - rhs, SciValue::wrap_with_exponent(3us, 2is));
+    assert_eq!(lhs - rhs, SciValue::wrap_with_exponent(3usize, 2isize));
   }
 
   #[test]
   fn test_sub() {
-    let lhs = SciValue::wrap_with_exponent(-2is, 2is);
-    let rhs = SciValue::wrap_with_exponent(1is, 1is);
-    let v3  = SciValue::wrap_with_exponent(2is, 2is);
+    let lhs = SciValue::wrap_with_exponent(-2isize, 2isize);
+    let rhs = SciValue::wrap_with_exponent(1isize, 1isize);
+    let v3  = SciValue::wrap_with_exponent(2isize, 2isize);
 
-    assert_eq!(lhs.clone() - rhs.clone(), SciValue::wrap_with_exponent(-21is, 1is));
-    assert_eq!(rhs.clone() - lhs, SciValue::wrap_with_exponent(21is, 1is));
-    assert_eq!(rhs - v3, SciValue::wrap_with_exponent(-19is, 1is));
+    assert_eq!(lhs - rhs, SciValue::wrap_with_exponent(-21isize, 1isize));
+    assert_eq!(rhs - lhs, SciValue::wrap_with_exponent(21isize, 1isize));
+    assert_eq!(rhs - v3, SciValue::wrap_with_exponent(-19isize, 1isize));
   }
 
   #[test]
   fn test_mul() {
-    let lhs = SciValue::wrap_with_exponent(2, 1is);
-    let rhs = SciValue::wrap_with_exponent(10, 2is);
+    let lhs = SciValue::wrap_with_exponent(2, 1isize);
+    let rhs = SciValue::wrap_with_exponent(10, 2isize);
 
-    assert_eq!(lhs * rhs, SciValue::wrap_with_exponent(20, 3is));
+    assert_eq!(lhs * rhs, SciValue::wrap_with_exponent(20, 3isize));
   }
 
   #[test]
   fn test_simple_div(){
-    let lhs = SciValue::wrap_with_exponent(10, 1is);
-    let rhs = SciValue::wrap_with_exponent(2, 3is);
+    let lhs = SciValue::wrap_with_exponent(10, 1isize);
+    let rhs = SciValue::wrap_with_exponent(2, 3isize);
 
-    assert_eq!(lhs / rhs, SciValue::wrap_with_exponent(5, -2is));
+    assert_eq!(lhs / rhs, SciValue::wrap_with_exponent(5, -2isize));
   }
 
   #[test]
   fn test_div(){
-    let lhs = SciValue::wrap_with_exponent(1, 0is);
-    let rhs = SciValue::wrap_with_exponent(2, 0is);
+    let lhs = SciValue::wrap_with_exponent(1, 0isize);
+    let rhs = SciValue::wrap_with_exponent(2, 0isize);
+
+    assert_eq!(lhs / rhs, SciValue::wrap_with_exponent(5, -1isize));
 
-    assert_eq!(lhs / rhs, SciValue::wrap_with_exponent(5, -1is));
+    //Negative dividends stop scaling before the base overflows
+    assert_eq!(SciValue::<i32,i32>::wrap(-1) / SciValue::wrap(3), SciValue::wrap_with_exponent(-333333333, -9));
+    assert_eq!(SciValue::<i32,i32>::wrap(-1) / SciValue::wrap(-2), SciValue::wrap_with_exponent(5, -1));
+    assert_eq!(SciValue::<i32,i32>::wrap(-1) / SciValue::wrap(3), SciValue::<i32,i32>::wrap(-1).checked_div(&SciValue::wrap(3)).unwrap());
   }
 
   #[test]
   fn test_reduce(){
-    let val1 = SciValue::wrap_with_exponent(2, 10is);
+    let val1 = SciValue::wrap_with_exponent(2, 10isize);
     assert_eq!(val1.reduce(), val1);
 
-    let val2 = SciValue::wrap_with_exponent(200, 10is);
-    assert_eq!(val2.reduce(), SciValue::wrap_with_exponent(2, 12is));
+    let val2 = SciValue::wrap_with_exponent(200, 10isize);
+    assert_eq!(val2.reduce(), SciValue::wrap_with_exponent(2, 12isize));
+
+    let zero = SciValue::wrap_with_exponent(0, 10isize);
+    assert_eq!(zero.reduce(), SciValue::wrap_with_exponent(0, 0isize));
+  }
+
+  #[test]
+  fn value_comparison(){
+    let v1 = SciValue::wrap_with_exponent(20i32, 2isize);
+    let v2 = SciValue::wrap_with_exponent(2i32, 3isize);
+    let v3 = SciValue::wrap_with_exponent(21i32, 1isize);
+    let v4 = SciValue::wrap_with_exponent(-3i32, 5isize);
+
+    assert_eq!(v1.cmp_value(&v2), Ordering::Equal);
+    assert_eq!(v1.cmp_value(&v3), Ordering::Greater);
+    assert_eq!(v3.cmp_value(&v2), Ordering::Less);
+    assert_eq!(v4.cmp_value(&v3), Ordering::Less);
+    assert_eq!(SciValue::wrap_with_exponent(1i32, 100isize).cmp_value(&v1), Ordering::Greater);
+    assert_eq!(SciValue::wrap_with_exponent(-1i32, 100isize).cmp_value(&v1), Ordering::Less);
+  }
+
+  #[test]
+  fn ordered_keys(){
+    let mut map = BTreeMap::new();
+    map.insert(OrderedSci(SciValue::wrap_with_exponent(20i32, 2isize)), "first");
+    map.insert(OrderedSci(SciValue::wrap_with_exponent(2i32, 3isize)), "second");
+    map.insert(OrderedSci(SciValue::wrap_with_exponent(5i32, 2isize)), "third");
+    map.insert(OrderedSci(SciValue::wrap_with_exponent(-1i32, 4isize)), "fourth");
+
+    assert_eq!(map.len(), 3);
+    let values: Vec<&str> = map.values().copied().collect();
+    assert_eq!(values, vec!["fourth", "third", "second"]);
   }
 
   #[test]
   fn test_pow(){
-    let val1 = SciValue::wrap_with_exponent(2, 0is);
+    let val1 = SciValue::wrap_with_exponent(2, 0isize);
     assert_eq!(val1.pow(4), SciValue::wrap(16));
 
-    let val2 = SciValue::wrap_with_exponent(11, 2is);
-    assert_eq!(val2.pow(4), SciValue::wrap_with_exponent(14641, 8is));
+    let val2 = SciValue::wrap_with_exponent(11, 2isize);
+    assert_eq!(val2.pow(4), SciValue::wrap_with_exponent(14641, 8isize));
+  }
+
+  #[test]
+  fn equality_at_precision(){
+    let reading = SciValue::wrap_with_exponent(12345i32, -3isize);
+    let nominal = SciValue::wrap_with_exponent(123i32, -1isize);
+
+    assert!(reading.eq_at_exponent(&nominal, -1));
+    assert!(!reading.eq_at_exponent(&nominal, -2));
+    assert!(SciValue::wrap_with_exponent(-125i32, 0isize).eq_at_exponent(&SciValue::wrap_with_exponent(-13i32, 1isize), 1));
+    assert!(SciValue::wrap_with_exponent(5i32, 0isize).eq_at_exponent(&SciValue::wrap_with_exponent(1i32, 1isize), 1));
+    assert!(!SciValue::wrap_with_exponent(4i32, 0isize).eq_at_exponent(&SciValue::wrap_with_exponent(1i32, 1isize), 1));
+    assert!(SciValue::wrap_with_exponent(4i32, 0isize).eq_at_exponent(&SciValue::wrap_with_exponent(0i32, 0isize), 1));
+    assert!(SciValue::wrap_with_exponent(7i32, -100i8).eq_at_exponent(&SciValue::wrap_with_exponent(0i32, 0i8), 100));
+  }
+
+  #[test]
+  fn rounding_modes(){
+    let val = SciValue::wrap_with_exponent(-12345i32, -3isize);
+    assert_eq!(val.round_to_exponent(-1, RoundingMode::Truncate), SciValue::wrap_with_exponent(-123, -1isize));
+    assert_eq!(val.round_to_exponent(-1, RoundingMode::Floor), SciValue::wrap_with_exponent(-124, -1isize));
+    assert_eq!(val.round_to_exponent(-1, RoundingMode::Ceiling), SciValue::wrap_with_exponent(-123, -1isize));
+    assert_eq!(val.round_to_exponent(-1, RoundingMode::HalfEven), SciValue::wrap_with_exponent(-123, -1isize));
+    assert_eq!(val.round_to_exponent(-2, RoundingMode::HalfEven), SciValue::wrap_with_exponent(-1234, -2isize));
+    assert_eq!(val.round_to_exponent(-4, RoundingMode::Floor), val);
+
+    let tie = SciValue::wrap_with_exponent(135i32, -1isize);
+    assert_eq!(tie.round_to_exponent(0, RoundingMode::HalfEven), SciValue::wrap(14));
+    assert_eq!(SciValue::wrap_with_exponent(145i32, -1isize).round_to_exponent(0, RoundingMode::HalfEven), SciValue::wrap(14));
+    assert_eq!(SciValue::wrap_with_exponent(1451i32, -2isize).round_to_exponent(0, RoundingMode::HalfEven), SciValue::wrap(15));
+  }
+
+  #[test]
+  fn ratios(){
+    assert_eq!(SciValue::from_ratio(1i32, 3, 4, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(3333, -4isize)));
+    assert_eq!(SciValue::from_ratio(2i32, 3, 4, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(6667, -4isize)));
+    assert_eq!(SciValue::from_ratio(2i32, 3, 4, RoundingMode::Truncate), Some(SciValue::wrap_with_exponent(6666, -4isize)));
+    assert_eq!(SciValue::from_ratio(-1i32, 3, 2, RoundingMode::Floor), Some(SciValue::wrap_with_exponent(-34, -2isize)));
+    assert_eq!(SciValue::from_ratio(1i32, -3, 2, RoundingMode::Ceiling), Some(SciValue::wrap_with_exponent(-33, -2isize)));
+    assert_eq!(SciValue::from_ratio(1i32, 8, 2, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(12, -2isize)));
+    assert_eq!(SciValue::from_ratio(3i32, 8, 2, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(38, -2isize)));
+    assert_eq!(SciValue::from_ratio(7i32, 2, 0, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(4, 0isize)));
+    assert_eq!(SciValue::from_ratio(1i32, 0, 2, RoundingMode::HalfEven), None::<SciValue<i32,isize>>);
+    assert_eq!(SciValue::from_ratio(1i8, 3, 3, RoundingMode::HalfEven), None::<SciValue<i8,isize>>);
+  }
+
+  #[test]
+  fn minor_units(){
+    let price = SciValue::wrap_with_exponent(12345i32, -3isize);
+    assert_eq!(price.to_minor_units(2, RoundingMode::HalfEven), Ok(1234));
+    assert_eq!(price.to_minor_units(2, RoundingMode::Ceiling), Ok(1235));
+    assert_eq!(SciValue::wrap_with_exponent(5i32, 1isize).to_minor_units(2, RoundingMode::Truncate), Ok(5000));
+    assert_eq!(SciValue::wrap_with_exponent(5i16, 3isize).to_minor_units(2, RoundingMode::Truncate), Err(PrecisionLoss::Overflow));
+
+    assert_eq!(SciValue::from_minor_units(1999i32, 2), SciValue::wrap_with_exponent(1999, -2isize));
+  }
+
+  #[test]
+  fn ordering_adapters(){
+    let mut heap = BinaryHeap::new();
+    heap.push(ByMagnitude(SciValue::wrap_with_exponent(5i32, 2isize)));
+    heap.push(ByMagnitude(SciValue::wrap_with_exponent(-7i32, 2isize)));
+    heap.push(ByMagnitude(SciValue::wrap_with_exponent(60i32, 1isize)));
+    assert_eq!(heap.pop().map(|v| v.0), Some(SciValue::wrap_with_exponent(-7, 2isize)));
+    assert_eq!(heap.pop().map(|v| v.0), Some(SciValue::wrap_with_exponent(60, 1isize)));
+
+    assert!(ByMagnitude(SciValue::wrap_with_exponent(-20i32, 0isize)) == ByMagnitude(SciValue::wrap_with_exponent(2i32, 1isize)));
+
+    let mut sorted = [ByExponent(SciValue::wrap_with_exponent(5i32, 3isize)),
+                          ByExponent(SciValue::wrap_with_exponent(0i32, 9isize)),
+                          ByExponent(SciValue::wrap_with_exponent(-99i32, 0isize))];
+    sorted.sort();
+    let exps: Vec<isize> = sorted.iter().map(|v| v.0.e_exp).collect();
+    assert_eq!(exps, vec![9, 0, 3]);
+    assert!(ByExponent(SciValue::wrap_with_exponent(1i32, 2isize)) == ByExponent(SciValue::wrap_with_exponent(-999i32, 0isize)));
+  }
+
+  #[test]
+  fn test_square_cube(){
+    let val = SciValue::wrap_with_exponent(12i32, 3isize);
+    assert_eq!(val.square(), SciValue::wrap_with_exponent(144, 6isize));
+    assert_eq!(val.cube(), SciValue::wrap_with_exponent(1728, 9isize));
+    assert_eq!(val.checked_square(), Some(SciValue::wrap_with_exponent(144, 6isize)));
+    assert_eq!(val.checked_cube(), Some(SciValue::wrap_with_exponent(1728, 9isize)));
+
+    let big = SciValue::wrap_with_exponent(100000i32, 0isize);
+    assert_eq!(big.checked_square(), None);
+    assert_eq!(big.widening_square(), Some(SciValue::wrap_with_exponent(10000000000i64, 0isize)));
+    assert_eq!(big.widening_cube(), Some(SciValue::wrap_with_exponent(1000000000000000i64, 0isize)));
+
+    assert_eq!(SciValue::wrap_with_exponent(2i32, 100i8).checked_square(), None);
+    assert_eq!(SciValue::wrap_with_exponent(100i8, 0i8).widening_cube(), None);
+  }
+
+  #[test]
+  fn test_isqrt(){
+    assert_eq!(SciValue::wrap_with_exponent(16i32, 0isize).isqrt(), Some(SciValue::wrap(4)));
+    assert_eq!(SciValue::wrap_with_exponent(17i32, 0isize).isqrt(), Some(SciValue::wrap(4)));
+    assert_eq!(SciValue::wrap_with_exponent(0i32, 7isize).isqrt(), Some(SciValue::wrap(0)));
+    assert_eq!(SciValue::wrap_with_exponent(4i32, 6isize).isqrt(), Some(SciValue::wrap_with_exponent(2, 3isize)));
+    assert_eq!(SciValue::wrap_with_exponent(4i32, 7isize).isqrt(), Some(SciValue::wrap(6324)));
+    assert_eq!(SciValue::wrap_with_exponent(2i32, 12isize).isqrt(), Some(SciValue::wrap(1414213)));
+    assert_eq!(SciValue::wrap_with_exponent(4900i32, -2isize).isqrt(), Some(SciValue::wrap(7)));
+    assert_eq!(SciValue::wrap_with_exponent(49i32, -2isize).isqrt(), None);
+    assert_eq!(SciValue::wrap_with_exponent(-4i32, 0isize).isqrt(), None);
+    assert_eq!(SciValue::wrap_with_exponent(2i32, 40isize).isqrt(), None);
+  }
+
+  #[test]
+  fn test_sum_widening(){
+    let values: Vec<SciValue<i32,isize>> = (0..1000).map(|_| SciValue::wrap(2000000000)).collect();
+    assert_eq!(sum_widening(values.into_iter()), SciValue::wrap(2000000000000i64));
+
+    let mixed = vec![SciValue::wrap_with_exponent(5i8, 2isize), SciValue::wrap_with_exponent(120i8, 0isize)];
+    assert_eq!(sum_widening(mixed.into_iter()), SciValue::wrap_with_exponent(620i16, 0isize));
+
+    let empty: Vec<SciValue<i32,isize>> = vec![];
+    assert_eq!(sum_widening(empty.into_iter()), SciValue::wrap(0i64));
+
+    let large: Vec<SciValue<i64,i32>> = (0..4).map(|_| SciValue::wrap(i64::MAX)).collect();
+    assert_eq!(sum_widening(large.into_iter()), SciValue::wrap(4 * i64::MAX as i128));
+  }
+
+  #[test]
+  fn powers_of_ten(){
+    assert_eq!(pow10::<u8>(0), Some(1));
+    assert_eq!(pow10::<u8>(2), Some(100));
+    assert_eq!(pow10::<u8>(3), None);
+    assert_eq!(pow10::<i64>(18), Some(1000000000000000000));
+    assert_eq!(pow10::<i64>(19), None);
+
+    assert_eq!(max_exact_pow10::<u8>(), 2);
+    assert_eq!(max_exact_pow10::<i8>(), 2);
+    assert_eq!(max_exact_pow10::<i32>(), 9);
+    assert_eq!(max_exact_pow10::<i64>(), 18);
+    assert_eq!(max_exact_pow10::<u64>(), 19);
+  }
+
+  #[test]
+  fn construct_from_parts(){
+    assert_eq!(SciValue::from_parts(12i32, &[5], 3isize), Some(SciValue::wrap_with_exponent(125, 2isize)));
+    assert_eq!(SciValue::from_parts(-12i32, &[0, 5], 0isize), Some(SciValue::wrap_with_exponent(-1205, -2isize)));
+    assert_eq!(SciValue::from_parts(7i32, &[], -1isize), Some(SciValue::wrap_with_exponent(7, -1isize)));
+    assert_eq!(SciValue::from_parts(1i32, &[10], 0isize), None);
+    assert_eq!(SciValue::from_parts(1i8, &[2, 8], 0isize), None);
+    assert_eq!(SciValue::from_parts(1i32, &[1, 2], -127i8), None);
+  }
+
+  #[test]
+  fn bulk_accumulate(){
+    let mut total = SciValue::wrap_with_exponent(1i32, 3isize);
+    total.accumulate(&[SciValue::wrap_with_exponent(5, 0isize),
+                       SciValue::wrap_with_exponent(2, 2isize),
+                       SciValue::wrap_with_exponent(7, 0isize),
+                       SciValue::wrap_with_exponent(3, 2isize)]);
+    assert_eq!(total, SciValue::wrap_with_exponent(1512, 0isize));
+
+    let mut unchanged = SciValue::wrap_with_exponent(4i32, 1isize);
+    unchanged.accumulate(&[]);
+    assert_eq!(unchanged, SciValue::wrap_with_exponent(4, 1isize));
+  }
+
+  #[test]
+  fn magnitude_filters(){
+    let val = SciValue::wrap_with_exponent(-1234i32, 2isize);
+    assert!(val.magnitude_at_least(5));
+    assert!(!val.magnitude_at_least(6));
+    assert!(val.magnitude_less_than(6));
+    assert!(!val.magnitude_less_than(5));
+
+    let tiny = SciValue::wrap_with_exponent(9i32, -4isize);
+    assert!(tiny.magnitude_at_least(-4));
+    assert!(tiny.magnitude_less_than(-3));
+
+    let zero = SciValue::wrap_with_exponent(0i32, 10isize);
+    assert!(!zero.magnitude_at_least(-100));
+    assert!(zero.magnitude_less_than(-100));
+  }
+
+  #[test]
+  fn dot_product(){
+    let lhs = vec![SciValue::wrap_with_exponent(100000i32, 0isize), SciValue::wrap_with_exponent(3i32, -2isize)];
+    let rhs = vec![SciValue::wrap_with_exponent(300000i32, 0isize), SciValue::wrap_with_exponent(-4i32, 1isize)];
+    assert_eq!(dot_compensated(lhs.as_slice(), rhs.as_slice()),
+               Some(SciValue::wrap_with_exponent(299999999988i64, -1isize)));
+
+    let cancelling = vec![SciValue::wrap_with_exponent(5i32, 10isize), SciValue::wrap_with_exponent(1i32, -3isize)];
+    let weights    = vec![SciValue::wrap_with_exponent(1i32, 0isize), SciValue::wrap_with_exponent(1i32, 0isize)];
+    assert_eq!(dot_compensated(cancelling.as_slice(), weights.as_slice()),
+               Some(SciValue::wrap_with_exponent(50000000000001i64, -3isize)));
+
+    assert_eq!(dot_compensated(lhs.as_slice(), &rhs[..1]), None);
+    let empty: Vec<SciValue<i32,isize>> = vec![];
+    assert_eq!(dot_compensated(empty.as_slice(), empty.as_slice()), Some(SciValue::wrap(0i64)));
+  }
+
+  #[test]
+  fn exact_mean(){
+    let values: Vec<SciValue<i32,isize>> = vec![SciValue::wrap(1i32), SciValue::wrap(2), SciValue::wrap(4)];
+    assert_eq!(mean_exact(values.as_slice()), Some((SciValue::wrap(2), SciValue::wrap(1))));
+
+    let mixed = vec![SciValue::wrap_with_exponent(15i32, -1isize), SciValue::wrap_with_exponent(2, 0isize)];
+    assert_eq!(mean_exact(mixed.as_slice()),
+               Some((SciValue::wrap_with_exponent(17, -1isize), SciValue::wrap_with_exponent(1, -1isize))));
+
+    let negative: Vec<SciValue<i32,isize>> = vec![SciValue::wrap(-7i32), SciValue::wrap(0)];
+    assert_eq!(mean_exact(negative.as_slice()), Some((SciValue::wrap(-3), SciValue::wrap(-1))));
+
+    let empty: Vec<SciValue<i32,isize>> = vec![];
+    assert_eq!(mean_exact(empty.as_slice()), None);
+  }
+
+  #[test]
+  fn single_pass_minmax(){
+    let values = vec![SciValue::wrap_with_exponent(5i32, 2isize),
+                      SciValue::wrap_with_exponent(-1i32, 1isize),
+                      SciValue::wrap_with_exponent(60i32, 1isize),
+                      SciValue::wrap_with_exponent(-10i32, 0isize),
+                      SciValue::wrap_with_exponent(6i32, 2isize)];
+    assert_eq!(minmax(values.into_iter()),
+               Some((SciValue::wrap_with_exponent(-1, 1isize), SciValue::wrap_with_exponent(6, 2isize))));
+
+    let empty: Vec<SciValue<i32,isize>> = vec![];
+    assert_eq!(minmax(empty.into_iter()), None);
+  }
+
+  #[test]
+  fn checked_sign_changes(){
+    let val = SciValue::wrap_with_exponent(-25i8, 3isize);
+    assert_eq!(val.checked_neg(), Some(SciValue::wrap_with_exponent(25, 3isize)));
+    assert_eq!(val.checked_abs(), Some(SciValue::wrap_with_exponent(25, 3isize)));
+    assert_eq!(SciValue::wrap_with_exponent(25i8, 3isize).checked_abs(), Some(SciValue::wrap_with_exponent(25, 3isize)));
+
+    let min = SciValue::wrap_with_exponent(-128i8, 0isize);
+    assert_eq!(min.checked_neg(), None);
+    assert_eq!(min.checked_abs(), None);
   }
 
   #[test]
   fn value_extraction(){
-    assert_eq!(SciValue::wrap_with_exponent(21us, 2is).to_full_value(), Some(2100));
-    assert_eq!(SciValue::wrap_with_exponent(10us, -1is).to_full_value(), None);
+    assert_eq!(SciValue::wrap_with_exponent(21usize, 2isize).to_full_value(), Some(2100));
+    assert_eq!(SciValue::wrap_with_exponent(10usize, -1isize).to_full_value(), None);
+  }
+
+  #[test]
+  fn usize_conversion(){
+    assert_eq!(SciValue::wrap_with_exponent(21isize, 2isize).to_usize_checked(), Some(2100usize));
+    assert_eq!(SciValue::wrap_with_exponent(210isize, -1isize).to_usize_checked(), Some(21usize));
+    assert_eq!(SciValue::wrap_with_exponent(0isize, -5isize).to_usize_checked(), Some(0usize));
+    assert_eq!(SciValue::wrap_with_exponent(215isize, -1isize).to_usize_checked(), None);
+    assert_eq!(SciValue::wrap_with_exponent(-2isize, 0isize).to_usize_checked(), None);
+    assert_eq!(SciValue::wrap_with_exponent(1isize, 40isize).to_usize_checked(), None);
+  }
+
+  #[test]
+  fn rebalance(){
+    let val = SciValue::wrap_with_exponent(15i32, 2isize);
+    assert_eq!(val.rebalance_to_exponent(-1), Ok(SciValue::wrap_with_exponent(15000, -1isize)));
+    assert_eq!(val.rebalance_to_exponent(2), Ok(val));
+    assert_eq!(SciValue::wrap_with_exponent(1500i32, 0isize).rebalance_to_exponent(2),
+               Ok(SciValue::wrap_with_exponent(15, 2isize)));
+    assert_eq!(val.rebalance_to_exponent(3), Err(PrecisionLoss::Truncation));
+    assert_eq!(val.rebalance_to_exponent(-20), Err(PrecisionLoss::Overflow));
+    assert_eq!(SciValue::wrap_with_exponent(0i32, 5isize).rebalance_to_exponent(-9),
+               Ok(SciValue::wrap_with_exponent(0, -9isize)));
+  }
+
+  #[test]
+  fn display(){
+    assert_eq!(format!("{}", SciValue::wrap_with_exponent(5i32, 2isize)), "5e2");
+    assert_eq!(format!("{}", SciValue::wrap_with_exponent(-21i32, 1isize)), "-21e1");
+    assert_eq!(format!("{}", SciValue::wrap_with_exponent(3i64, -4i32)), "3e-4");
+    assert_eq!(format!("{}", SciValue::wrap_with_exponent(20i32, 1isize)), "20e1");
+  }
+
+  #[test]
+  fn prefix_shifts(){
+    let millivolts = SciValue::wrap_with_exponent(5i32, -3isize);
+    let volts      = SciValue::wrap(12i32);
+    assert_eq!(millivolts.prefix_difference(&volts), -3);
+    assert_eq!(volts.prefix_difference(&millivolts), 3);
+    assert_eq!(SciValue::wrap(5i32).with_prefix_shift(-3isize), millivolts);
+    assert_eq!(millivolts.with_prefix_shift(6), SciValue::wrap_with_exponent(5, 3isize));
+  }
+
+  #[test]
+  fn checked_arithmetic(){
+    let val = SciValue::wrap_with_exponent(15i32, 1isize);
+    assert_eq!(val.checked_add(&SciValue::wrap(3)), Some(SciValue::wrap(153)));
+    assert_eq!(val.checked_sub(&SciValue::wrap_with_exponent(2, 2isize)), Some(SciValue::wrap_with_exponent(-5, 1isize)));
+    assert_eq!(val.checked_mul(&SciValue::wrap_with_exponent(2, -3isize)), Some(SciValue::wrap_with_exponent(30, -2isize)));
+    assert_eq!(SciValue::wrap(1i32).checked_div(&SciValue::wrap(4)), Some(SciValue::wrap_with_exponent(25, -2isize)));
+    assert_eq!(val.checked_pow(3), Some(SciValue::wrap_with_exponent(3375, 3isize)));
+    assert_eq!(val.checked_pow(0), Some(SciValue::wrap(1)));
+
+    //Matching exponents overflows the base
+    assert_eq!(SciValue::wrap_with_exponent(1i8, 5i8).checked_add(&SciValue::wrap(1)), None);
+    assert_eq!(SciValue::<i8,i8>::wrap(-100).checked_sub(&SciValue::wrap(100)), None);
+    assert_eq!(SciValue::<i8,i8>::wrap(100).checked_mul(&SciValue::wrap(2)), None);
+    assert_eq!(SciValue::wrap_with_exponent(2i8, 100i8).checked_mul(&SciValue::wrap_with_exponent(2, 100)), None);
+    assert_eq!(val.checked_div(&SciValue::wrap(0)), None);
+    assert_eq!(SciValue::wrap_with_exponent(1i8, -128i8).checked_div(&SciValue::wrap_with_exponent(1, 1)), None);
+    assert_eq!(SciValue::<i8,i8>::wrap(-128).checked_div(&SciValue::wrap(-1)), None);
+    assert_eq!(SciValue::<i8,i8>::wrap(10).checked_pow(3), None);
+    assert_eq!(val.checked_pow(-1), None);
+
+    //Powers which can't overflow the base finish at once
+    assert_eq!(SciValue::<i64,i32>::wrap(1).checked_pow(2_000_000_000), Some(SciValue::wrap(1)));
+    assert_eq!(SciValue::<i64,i64>::wrap(-1).checked_pow(i64::MAX), Some(SciValue::wrap(-1)));
+    assert_eq!(SciValue::<i64,i64>::wrap(0).checked_pow(i64::MAX), Some(SciValue::wrap(0)));
+    assert_eq!(SciValue::<i64,i64>::wrap(2).checked_pow(i64::MAX), None);
+    assert_eq!(SciValue::<i64,i32>::wrap(-2).checked_pow(63), Some(SciValue::wrap(i64::MIN)));
+    assert_eq!(SciValue::wrap_with_exponent(1i64, 2i32).checked_pow(2_000_000_000), None);
+  }
+
+  #[test]
+  fn ratio_helpers(){
+    use super::RoundingMode::*;
+
+    let part = SciValue::wrap(3i32);
+    assert_eq!(part.ratio_of(&SciValue::wrap(8), -3isize, HalfEven), Some(SciValue::wrap_with_exponent(375, -3isize)));
+    assert_eq!(SciValue::wrap_with_exponent(15i32, 1isize).ratio_of(&SciValue::wrap_with_exponent(4, 2), -2, HalfEven),
+               Some(SciValue::wrap_with_exponent(38, -2isize)));
+    assert_eq!(SciValue::wrap(5i32).ratio_of(&SciValue::wrap(2), 1isize, Ceiling), Some(SciValue::wrap_with_exponent(1, 1isize)));
+    assert_eq!(part.ratio_of(&SciValue::wrap(0), 0isize, HalfEven), None);
+
+    let (after, before) = (SciValue::wrap_with_exponent(25i32, -2isize), SciValue::wrap_with_exponent(2, -1isize));
+    assert_eq!(percentage_points_diff(&after, &before, -1, HalfEven), Some(SciValue::wrap_with_exponent(50, -1isize)));
+    assert_eq!(percentage_points_diff(&before, &SciValue::wrap_with_exponent(1234, -4), 0, HalfEven),
+               Some(SciValue::wrap_with_exponent(8, 0isize)));
+  }
+
+  #[test]
+  fn digit_access(){
+    let val = SciValue::wrap_with_exponent(-1234i32, -2isize);
+    assert_eq!(val.digit_at(-2), 4);
+    assert_eq!(val.digit_at(1), 1);
+    assert_eq!(val.digit_at(2), 0);
+    assert_eq!(val.digit_at(-3), 0);
+    assert_eq!(SciValue::wrap_with_exponent(7i8, 0i8).digit_at(100), 0);
+
+    let mut val = val;
+    val.set_digit_at(0, 9).unwrap();
+    assert_eq!(val, SciValue::wrap_with_exponent(-1934, -2isize));
+    val.set_digit_at(-4, 5).unwrap();
+    assert_eq!(val, SciValue::wrap_with_exponent(-193405, -4isize));
+    val.set_digit_at(1, 0).unwrap();
+    assert_eq!(val, SciValue::wrap_with_exponent(-93405, -4isize));
+
+    let mut small = SciValue::wrap(120i8);
+    assert_eq!(small.set_digit_at(0, 8), Err(PrecisionLoss::Overflow));
+    assert_eq!(small.set_digit_at(-1, 1), Err(PrecisionLoss::Overflow));
+    assert_eq!(small, SciValue::wrap(120i8));
+    small.set_digit_at(0, 7).unwrap();
+    assert_eq!(small, SciValue::wrap(127i8));
+  }
+
+  #[test]
+  fn exact_integers(){
+    assert_eq!(SciValue::wrap_with_exponent(12i32, 3isize).to_integer_exact::<u64>(), Some(12000));
+    assert_eq!(SciValue::wrap_with_exponent(1200i32, -2isize).to_integer_exact::<i8>(), Some(12));
+    assert_eq!(SciValue::wrap_with_exponent(-5i32, 0isize).to_integer_exact::<u8>(), None);
+    assert_eq!(SciValue::wrap_with_exponent(13i32, 1isize).to_integer_exact::<i8>(), None);
+    assert_eq!(SciValue::wrap_with_exponent(0i32, -7isize).to_integer_exact::<i8>(), Some(0));
+
+    assert_eq!(i64::try_from(SciValue::wrap_with_exponent(-25i32, 2isize)), Ok(-2500));
+    assert_eq!(i64::try_from(SciValue::wrap_with_exponent(25i32, -1isize)), Err(PrecisionLoss::Truncation));
+    assert_eq!(u8::try_from(SciValue::wrap_with_exponent(3i32, 2isize)), Err(PrecisionLoss::Overflow));
+    let exact: u16 = SciValue::wrap_with_exponent(7i64, 2i8).try_into().unwrap();
+    assert_eq!(exact, 700);
+  }
+
+  #[test]
+  fn range_containment(){
+    let val = SciValue::wrap_with_exponent(20i32, 2i8);
+    let lo = SciValue::wrap_with_exponent(2, 3);
+    let hi = SciValue::wrap(5000);
+    assert!(val.in_range(lo..=hi));
+    assert!(val.in_range(lo..hi));
+    assert!(!val.in_range(SciValue::wrap(0)..lo));
+    assert!(val.in_range(..hi));
+    assert!(!val.in_range(..lo));
+    assert!(val.in_range(lo..));
+    assert!(val.in_range(..));
+    assert!(val.contains((Bound::Excluded(SciValue::wrap(1999)), Bound::Unbounded)));
+    assert!(!val.contains((Bound::Excluded(lo), Bound::Included(hi))));
+  }
+
+  #[test]
+  fn duration_conversion(){
+    let val: SciValue<u128,i32> = Duration::new(90, 5).into();
+    assert_eq!(val, SciValue::wrap_with_exponent(90000000005, -9));
+    assert_eq!(val.try_to_duration(), Some(Duration::new(90, 5)));
+
+    let max: SciValue<i128,i8> = Duration::MAX.into();
+    assert_eq!(max.try_to_duration(), Some(Duration::MAX));
+
+    assert_eq!(SciValue::wrap_with_exponent(15i32, -1i8).try_to_duration(), Some(Duration::from_millis(1500)));
+    assert_eq!(SciValue::wrap_with_exponent(2i64, 3i8).try_to_duration(), Some(Duration::from_secs(2000)));
+    assert_eq!(SciValue::wrap_with_exponent(1i64, -10i8).try_to_duration(), None);
+    assert_eq!(SciValue::<i64,i8>::wrap(-1).try_to_duration(), None);
+    assert_eq!(SciValue::wrap_with_exponent(1i64, 30i8).try_to_duration(), None);
+  }
+
+  #[test]
+  fn exact_addition(){
+    let val = SciValue::wrap_with_exponent(5i8, 1i8);
+    assert_eq!(val.add_exact(&SciValue::wrap(3)), Ok(SciValue::wrap(53)));
+    //Only fits once the trailing zeros are stripped
+    assert_eq!(SciValue::wrap(100i8).add_exact(&SciValue::wrap_with_exponent(3, 1)), Ok(SciValue::wrap_with_exponent(13, 1)));
+    assert_eq!(SciValue::wrap_with_exponent(10i8, 0).add_exact(&SciValue::wrap_with_exponent(1, 3)), Ok(SciValue::wrap_with_exponent(101, 1)));
+    //The sum itself has trailing zeros to strip
+    assert_eq!(SciValue::<i8,i8>::wrap(75).add_exact(&SciValue::wrap(75)), Ok(SciValue::wrap_with_exponent(15, 1)));
+    assert_eq!(SciValue::wrap_with_exponent(2i8, 2i8).add_exact(&SciValue::wrap(-99)), Ok(SciValue::wrap(101)));
+    assert_eq!(SciValue::wrap_with_exponent(1i8, 5i8).add_exact(&SciValue::wrap(1)), Err(PrecisionLoss::Truncation));
+    assert_eq!(SciValue::<i8,i8>::wrap(127).add_exact(&SciValue::wrap(1)), Err(PrecisionLoss::Truncation));
+    assert_eq!(SciValue::wrap_with_exponent(1i8, 100i8).add_exact(&SciValue::wrap_with_exponent(1, -100)), Err(PrecisionLoss::Truncation));
+    assert_eq!(SciValue::wrap_with_exponent(5i8, 127i8).add_exact(&SciValue::wrap_with_exponent(5, 127)), Ok(SciValue::wrap_with_exponent(10, 127)));
+    assert_eq!(SciValue::wrap_with_exponent(64i8, 127i8).add_exact(&SciValue::wrap_with_exponent(64, 127)), Err(PrecisionLoss::Overflow));
+    assert_eq!(SciValue::wrap(u128::MAX).add_exact(&SciValue::wrap_with_exponent(5, 1i8)), Err(PrecisionLoss::Truncation));
+  }
+
+  #[test]
+  fn checked_traits(){
+    use num_traits::{CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, Pow};
+
+    fn all_checked<T:CheckedAdd + CheckedSub + CheckedMul + CheckedDiv>(lhs:&T, rhs:&T) -> Option<[T; 4]> {
+      Some([lhs.checked_add(rhs)?, lhs.checked_sub(rhs)?, lhs.checked_mul(rhs)?, lhs.checked_div(rhs)?])
+    }
+    let val = SciValue::wrap_with_exponent(15i32, -1isize);
+    assert_eq!(all_checked(&val, &SciValue::wrap(2)), Some([SciValue::wrap_with_exponent(35, -1isize),
+                                                             SciValue::wrap_with_exponent(-5, -1isize),
+                                                             SciValue::wrap_with_exponent(30, -1isize),
+                                                             SciValue::wrap_with_exponent(75, -2isize)]));
+    assert_eq!(all_checked(&val, &SciValue::wrap(0)), None);
+    assert_eq!(Pow::pow(val, 2u32), SciValue::wrap_with_exponent(225, -2isize));
+    assert_eq!(Pow::pow(val, 0u32), SciValue::wrap(1));
+  }
+
+  #[test]
+  #[should_panic(expected = "with overflow")]
+  fn pow_trait_overflow(){
+    num_traits::Pow::pow(SciValue::<i8,i8>::wrap(10), 3u32);
+  }
+
+  #[test]
+  fn integer_rounding(){
+    use super::RoundingMode::*;
+
+    //Tenths from -2.6 to 2.6, covering ties on both sides of zero
+    let tenths = [-26i32, -25, -24, -15, -5, -4, 0, 4, 5, 15, 24, 25, 26];
+    let expected = [
+      (Truncate,         [-2, -2, -2, -1,  0,  0, 0, 0, 0, 1, 2, 2, 2]),
+      (Floor,            [-3, -3, -3, -2, -1, -1, 0, 0, 0, 1, 2, 2, 2]),
+      (Ceiling,          [-2, -2, -2, -1,  0,  0, 0, 1, 1, 2, 3, 3, 3]),
+      (HalfEven,         [-3, -2, -2, -2,  0,  0, 0, 0, 0, 2, 2, 2, 3]),
+      (HalfAwayFromZero, [-3, -3, -2, -2, -1,  0, 0, 0, 1, 2, 2, 3, 3])
+    ];
+    for &(mode, ref results) in expected.iter() {
+      for (tenth, result) in tenths.iter().zip(results.iter()) {
+        let val = SciValue::wrap_with_exponent(*tenth, -1isize);
+        assert_eq!(val.to_integer(mode), Ok(*result), "{:?} of {}", mode, val);
+        assert_eq!(val.round(mode).cmp_value(&SciValue::wrap(*result)), Ordering::Equal);
+      }
+    }
+
+    //Ties below the first dropped digit aren't ties
+    assert_eq!(SciValue::wrap_with_exponent(-2501i32, -3isize).to_integer(HalfEven), Ok(-3));
+    assert_eq!(SciValue::wrap_with_exponent(-2499i32, -3isize).to_integer(HalfAwayFromZero), Ok(-2));
+    assert_eq!(SciValue::wrap_with_exponent(12i32, 2isize).round(Floor), SciValue::wrap_with_exponent(12, 2isize));
+    assert_eq!(SciValue::wrap_with_exponent(12i8, 2i8).to_integer(Truncate), Err(PrecisionLoss::Overflow));
+  }
+
+  #[test]
+  fn saturating_arithmetic(){
+    let big = SciValue::wrap_with_exponent(100i8, 0i8);
+    assert_eq!(big.saturating_add(&SciValue::wrap(20)), SciValue::wrap(120));
+    //Sums which fit at a coarser exponent aren't clamped
+    assert_eq!(big.saturating_add(&big), SciValue::wrap_with_exponent(2, 2));
+    assert_eq!(SciValue::<i8,i8>::wrap(-100).saturating_add(&SciValue::wrap(-100)), SciValue::wrap_with_exponent(-2, 2));
+    assert_eq!(SciValue::<i32,i32>::wrap(0).saturating_add(&SciValue::wrap_with_exponent(5, 20)),
+               SciValue::wrap_with_exponent(5, 20));
+    //Matching exponents overflows, but the sum is exact
+    assert_eq!(big.saturating_add(&SciValue::wrap_with_exponent(-5, 3)), SciValue::wrap_with_exponent(-49, 2));
+    assert_eq!(big.saturating_sub(&SciValue::wrap_with_exponent(-5, 3)), SciValue::wrap_with_exponent(51, 2));
+    assert_eq!(big.saturating_sub(&SciValue::wrap_with_exponent(5, 3)), SciValue::wrap_with_exponent(-49, 2));
+    //Too many digits are truncated toward zero
+    assert_eq!(SciValue::<i8,i8>::wrap(127).saturating_add(&SciValue::wrap(1)), SciValue::wrap_with_exponent(12, 1));
+    assert_eq!(SciValue::<i8,i8>::wrap(-128).saturating_sub(&SciValue::wrap(1)), SciValue::wrap_with_exponent(-12, 1));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(1, 100).saturating_sub(&SciValue::wrap_with_exponent(1, -100)),
+               SciValue::wrap_with_exponent(99, 98));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(1, 100).saturating_add(&SciValue::wrap_with_exponent(1, -100)),
+               SciValue::wrap_with_exponent(1, 100));
+    //Only sums too large for any exponent are clamped
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(64, 127).saturating_add(&SciValue::wrap_with_exponent(64, 127)),
+               SciValue::wrap_with_exponent(127, 127));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(-64, 127).saturating_sub(&SciValue::wrap_with_exponent(64, 127)),
+               SciValue::wrap_with_exponent(-128, 127));
+    assert_eq!(SciValue::<u8,i8>::wrap(5).saturating_sub(&SciValue::wrap(6)), SciValue::wrap(0));
+
+    assert_eq!(big.saturating_mul(&SciValue::wrap_with_exponent(3, 2)), SciValue::wrap_with_exponent(127, 2));
+    assert_eq!(big.saturating_mul(&SciValue::wrap_with_exponent(-3, 2)), SciValue::wrap_with_exponent(-128, 2));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(2, 100).saturating_mul(&SciValue::wrap_with_exponent(3, 100)),
+               SciValue::wrap_with_exponent(6, 127));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(2, -100).saturating_mul(&SciValue::wrap_with_exponent(3, -100)),
+               SciValue::wrap_with_exponent(6, -128));
+
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(-3, 1).saturating_pow(3), SciValue::wrap_with_exponent(-27, 3));
+    assert_eq!(SciValue::<i8,i8>::wrap(-3).saturating_pow(5), SciValue::wrap(-128));
+    assert_eq!(SciValue::<i8,i8>::wrap(7).saturating_pow(0), SciValue::wrap(1));
+    assert_eq!(SciValue::<i8,i8>::wrap(7).saturating_pow(-2), SciValue::wrap(0));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(2, 100).saturating_pow(2), SciValue::wrap_with_exponent(4, 127));
+    assert_eq!(SciValue::<i32,i32>::wrap(1).saturating_pow(2_000_000_000), SciValue::wrap(1));
+    assert_eq!(SciValue::<i32,i32>::wrap(-2).saturating_pow(2_000_000_001), SciValue::wrap(i32::MIN));
+  }
+
+  #[test]
+  fn overflowing_arithmetic(){
+    let val = SciValue::wrap_with_exponent(15i8, 1i8);
+    assert_eq!(val.overflowing_add(&SciValue::wrap(3)), (SciValue::wrap(-103), true));
+    assert_eq!(val.overflowing_add(&SciValue::wrap_with_exponent(3, 1)), (SciValue::wrap_with_exponent(18, 1), false));
+    //Rescaling 15e1 to 150e0 alone wraps
+    assert_eq!(val.overflowing_sub(&SciValue::wrap(0)), (SciValue::wrap(-106), true));
+    assert_eq!(val.overflowing_sub(&SciValue::wrap_with_exponent(20, 1)), (SciValue::wrap_with_exponent(-5, 1), false));
+    assert_eq!(SciValue::<i8,i8>::wrap(1).overflowing_add(&SciValue::wrap_with_exponent(1, 100)), (SciValue::wrap(1), true));
+
+    assert_eq!(val.overflowing_mul(&SciValue::wrap_with_exponent(10, 127)), (SciValue::wrap_with_exponent(-106, -128), true));
+    assert_eq!(val.overflowing_mul(&SciValue::wrap(2)), (SciValue::wrap_with_exponent(30, 1), false));
+
+    assert_eq!(SciValue::<i8,i8>::wrap(-128).overflowing_div(&SciValue::wrap(-1)), (SciValue::wrap(-128), true));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(1, -128).overflowing_div(&SciValue::wrap_with_exponent(1, 1)),
+               (SciValue::wrap_with_exponent(1, 127), true));
+    assert_eq!(SciValue::<i8,i8>::wrap(1).overflowing_div(&SciValue::wrap(4)), (SciValue::wrap_with_exponent(25, -2), false));
+    assert_eq!(SciValue::<i8,i8>::wrap_with_exponent(1, -127).overflowing_div(&SciValue::wrap_with_exponent(3, 1)),
+               (SciValue::wrap_with_exponent(3, 127), true));
+  }
+
+  #[test]
+  fn negation(){
+    assert_eq!(-SciValue::wrap_with_exponent(5i32, 2isize), SciValue::wrap_with_exponent(-5, 2isize));
+    assert_eq!(-SciValue::wrap_with_exponent(-21i32, -1isize), SciValue::wrap_with_exponent(21, -1isize));
+    assert_eq!(-SciValue::wrap(0i32) + SciValue::wrap_with_exponent(3, 1isize), SciValue::wrap(30));
+  }
+
+  #[test]
+  fn signs(){
+    use num_traits::Signed;
+
+    let val = SciValue::wrap_with_exponent(-21i32, -1isize);
+    assert_eq!(val.abs(), SciValue::wrap_with_exponent(21, -1isize));
+    assert_eq!(val.signum(), SciValue::wrap(-1));
+    assert!(val.is_negative() && !val.is_positive());
+    assert!(!SciValue::wrap_with_exponent(0i32, 3isize).is_negative());
+    assert_eq!(SciValue::wrap(3i32).abs_sub(&val), SciValue::wrap_with_exponent(51, -1isize));
+    assert_eq!(val.abs_sub(&SciValue::wrap(3)), SciValue::wrap(0));
+  }
+
+  #[test]
+  fn remainders(){
+    let (quot, rem) = SciValue::wrap_with_exponent(75i32, -1isize).div_rem(SciValue::wrap(2));
+    assert_eq!((quot, rem), (SciValue::wrap(3), SciValue::wrap_with_exponent(15, -1isize)));
+    assert_eq!(SciValue::wrap_with_exponent(-75i32, -1isize) % SciValue::wrap(2), SciValue::wrap_with_exponent(-15, -1isize));
+    assert_eq!(SciValue::wrap_with_exponent(7i32, 2isize) % SciValue::wrap_with_exponent(-3, 1isize), SciValue::wrap_with_exponent(1, 1isize));
+    assert_eq!(SciValue::wrap_with_exponent(7i32, 2isize).div_rem(SciValue::wrap_with_exponent(-3, 1isize)).0, SciValue::wrap(-23));
+
+    //q * rhs + r reconstructs the dividend
+    let lhs = SciValue::wrap_with_exponent(12345i64, -3isize);
+    let rhs = SciValue::wrap_with_exponent(7i64, -1isize);
+    let (quot, rem) = lhs.div_rem(rhs);
+    assert_eq!((quot * rhs + rem).cmp_value(&lhs), Ordering::Equal);
+  }
+
+  #[test]
+  fn compound_assignment(){
+    let mut total = SciValue::wrap(0i32);
+    for val in [SciValue::wrap_with_exponent(15, -1isize), SciValue::wrap(2), SciValue::wrap_with_exponent(3, 1)].iter() {
+      total += val;
+    }
+    assert_eq!(total, SciValue::wrap_with_exponent(335, -1isize));
+
+    total -= SciValue::wrap_with_exponent(5, -1isize);
+    assert_eq!(total, SciValue::wrap_with_exponent(330, -1isize));
+    total *= SciValue::wrap_with_exponent(2, 1isize);
+    assert_eq!(total, SciValue::wrap_with_exponent(660, 0isize));
+    total /= &SciValue::wrap(4);
+    assert_eq!(total, SciValue::wrap_with_exponent(165, 0isize));
+    total -= &SciValue::wrap(65);
+    assert_eq!(total, SciValue::wrap(100));
+  }
+
+  #[test]
+  fn copy_values(){
+    let val = SciValue::wrap_with_exponent(12i32, -1isize);
+    let copied = val;
+    assert_eq!(val * copied, SciValue::wrap_with_exponent(144, -2isize));
+    assert_eq!(val + copied, SciValue::wrap_with_exponent(24, -1isize));
+  }
+
+  #[test]
+  fn borrowed_operators(){
+    let values = [SciValue::wrap_with_exponent(15i32, -1isize), SciValue::wrap(4)];
+    let (lhs, rhs) = (values.first().unwrap(), values.last().unwrap());
+    assert_eq!(lhs + rhs, SciValue::wrap_with_exponent(55, -1isize));
+    assert_eq!(lhs - values[1], SciValue::wrap_with_exponent(-25, -1isize));
+    assert_eq!(values[1] * lhs, SciValue::wrap_with_exponent(60, -1isize));
+    assert_eq!(lhs / rhs, SciValue::wrap_with_exponent(375, -3isize));
+    assert_eq!(values.iter().fold(SciValue::wrap(0), |total, val| total + val), SciValue::wrap_with_exponent(55, -1isize));
+  }
+
+  #[test]
+  fn hash_reduced_form(){
+    use std::collections::HashMap;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash_of = |val:SciValue<i32,isize>| {
+      let mut hasher = DefaultHasher::new();
+      val.hash(&mut hasher);
+      hasher.finish()
+    };
+    assert_eq!(hash_of(SciValue::wrap_with_exponent(20, 2)), hash_of(SciValue::wrap_with_exponent(2, 3)));
+    assert_eq!(hash_of(SciValue::wrap_with_exponent(0, 2)), hash_of(SciValue::wrap(0)));
+
+    let mut counts = HashMap::new();
+    *counts.entry(SciValue::wrap_with_exponent(2i32, 3isize)).or_insert(0) += 1;
+    *counts.entry(SciValue::wrap_with_exponent(2i32, 3isize)).or_insert(0) += 1;
+    assert_eq!(counts.get(&SciValue::wrap_with_exponent(2, 3)), Some(&2));
+  }
+
+  #[test]
+  fn default_zero(){
+    let mut total = SciValue::wrap_with_exponent(15i32, -1isize);
+    assert_eq!(std::mem::take(&mut total), SciValue::wrap_with_exponent(15, -1isize));
+    assert_eq!(total, SciValue::wrap(0));
+    assert_eq!(SciValue::<u8,i8>::default(), SciValue::wrap_with_exponent(0, 0));
+  }
+
+  #[test]
+  fn zero_and_one(){
+    use num_traits::{Zero, One};
+
+    fn total<T:Zero + Copy>(values:&[T]) -> T {
+      values.iter().fold(T::zero(), |total, val| total + *val)
+    }
+    fn product<T:One + Copy>(values:&[T]) -> T {
+      values.iter().fold(T::one(), |total, val| total * *val)
+    }
+
+    let values = [SciValue::wrap_with_exponent(15i32, -1isize), SciValue::wrap(2)];
+    assert_eq!(total(&values), SciValue::wrap_with_exponent(35, -1isize));
+    assert_eq!(product(&values), SciValue::wrap_with_exponent(30, -1isize));
+    assert!(SciValue::wrap_with_exponent(0i32, 9isize).is_zero());
+    assert!(!SciValue::wrap_with_exponent(1i32, 9isize).is_zero());
+    assert!(SciValue::<i32,isize>::one().is_one());
+  }
+
+  #[test]
+  fn primitive_conversions(){
+    use num_traits::{FromPrimitive, ToPrimitive};
+
+    let val = SciValue::wrap_with_exponent(-35i32, -1isize);
+    assert_eq!(val.to_i64(), Some(-3));
+    assert_eq!(val.to_u64(), None);
+    assert_eq!(val.to_f64(), Some(-3.5));
+    assert_eq!(SciValue::wrap_with_exponent(1i32, -1isize).to_f64(), Some(0.1));
+    assert_eq!(SciValue::wrap_with_exponent(5i32, 20isize).to_i32(), None);
+    assert_eq!(SciValue::wrap_with_exponent(5i32, 300isize).to_f64(), Some(5e300));
+    assert_eq!(SciValue::wrap_with_exponent(123i64, -320isize).to_f64(), Some(1.23e-318));
+
+    assert_eq!(SciValue::<i32,isize>::from_u64(42), Some(SciValue::wrap(42)));
+    assert_eq!(SciValue::<i8,isize>::from_u64(420), None);
+    assert_eq!(SciValue::<i32,isize>::from_f64(3.5), Some(SciValue::wrap_with_exponent(35, -1)));
+    assert_eq!(SciValue::<i32,isize>::from_f64(0.1), Some(SciValue::wrap_with_exponent(1, -1)));
+    assert_eq!(SciValue::<i32,isize>::from_f64(-2.5e10), Some(SciValue::wrap_with_exponent(-25, 9)));
+    assert_eq!(SciValue::<i32,isize>::from_f64(f64::NAN), None);
+  }
+
+  #[test]
+  fn iterator_sums(){
+    let values = vec![SciValue::wrap_with_exponent(15i32, -1isize), SciValue::wrap(2), SciValue::wrap_with_exponent(3, 1)];
+    assert_eq!(values.iter().sum::<SciValue<_,_>>(), SciValue::wrap_with_exponent(335, -1isize));
+    assert_eq!(values.iter().product::<SciValue<_,_>>(), SciValue::wrap_with_exponent(90, 0isize));
+    assert_eq!(values.into_iter().sum::<SciValue<_,_>>(), SciValue::wrap_with_exponent(335, -1isize));
+
+    //Starting from the first value keeps large exponents
+    let large = [SciValue::wrap_with_exponent(5i32, 30isize), SciValue::wrap_with_exponent(2, 30)];
+    assert_eq!(large.iter().sum::<SciValue<_,_>>(), SciValue::wrap_with_exponent(7, 30isize));
+    assert_eq!(Vec::<SciValue<i32,isize>>::new().into_iter().sum::<SciValue<_,_>>(), SciValue::wrap(0));
+    assert_eq!(Vec::<SciValue<i32,isize>>::new().into_iter().product::<SciValue<_,_>>(), SciValue::wrap(1));
+  }
+
+  #[test]
+  fn from_integers(){
+    fn total(values:&[SciValue<i64,i32>]) -> SciValue<i64,i32> {
+      values.iter().sum()
+    }
+    assert_eq!(total(&[5i64.into(), SciValue::wrap_with_exponent(25, -1)]), SciValue::wrap_with_exponent(75, -1));
+    assert_eq!(SciValue::<u8,i8>::from(200u8), SciValue::wrap(200));
+    let val: SciValue<u128,isize> = u128::MAX.into();
+    assert_eq!(val, SciValue::wrap(u128::MAX));
+  }
+
+  #[test]
+  fn scalar_operators(){
+    let val = SciValue::wrap_with_exponent(15i32, -1isize);
+    assert_eq!(val + 500, SciValue::wrap_with_exponent(5015, -1isize));
+    assert_eq!(val - 2, SciValue::wrap_with_exponent(-5, -1isize));
+    assert_eq!(val * 3, SciValue::wrap_with_exponent(45, -1isize));
+    assert_eq!(val / 4, SciValue::wrap_with_exponent(375, -3isize));
+    assert_eq!(SciValue::wrap_with_exponent(3i32, 2isize) + 1, SciValue::wrap(301));
   }
 }
@@ -0,0 +1,215 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+
+use super::{SciValue, RoundingMode, pow10};
+
+/// Digits kept after the point in the fixed-point working values.
+const FIXED_DIGITS: usize = 18;
+const FIXED_ONE: u128 = 1_000_000_000_000_000_000;
+
+/**
+ * The most digits the log scale conversions will produce.
+ * Each digit of a logarithm costs a tenth power of the
+ * working value, which multiplies its error by ten, so
+ * beyond this the fixed-point guard digits run out.
+ */
+pub const MAX_LOG_DIGITS: usize = 12;
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Converts a concentration-like value to its log scale
+   * reading, -log10(value), as pH is to hydrogen ion
+   * concentration.  The result is rounded using `mode` to
+   * the decimal place of 10^exp, so an `exp` of -2 gives
+   * a reading to two decimal places.
+   *
+   * The logarithm is computed digit by digit in integer
+   * arithmetic rather than through floating point, to at
+   * most `MAX_LOG_DIGITS` places.  Returns None for values
+   * that aren't positive, if more places are asked for, or
+   * if the result doesn't fit the value's types.
+   */
+  pub fn to_log_scale(&self, exp:E, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    let reduced = self.reduce();
+    if reduced.base <= B::zero() {
+      return None;
+    }
+    let target = exp.to_i64()?;
+    let places = if target < 0 { (-target) as usize + 1 } else { 1 };
+    if places > MAX_LOG_DIGITS + 1 {
+      return None;
+    }
+
+    let (int_part, frac, sticky) = log10_digits(reduced.base.to_u128()?, places);
+    let log_int = int_part.checked_add(reduced.e_exp.to_i64()?)? as i128;
+    let scaled  = log_int.checked_mul(pow10::<i128>(places)?)? + frac as i128;
+
+    //-log10 is just below -scaled if digits were left over,
+    //so append a trailing digit for the rounding to see
+    let negated = if sticky {
+      SciValue::wrap_with_exponent(-scaled * 10 - 1, -(places as i64) - 1)
+    } else {
+      SciValue::wrap_with_exponent(-scaled, -(places as i64))
+    };
+    let rounded = negated.round_to_exponent(target, mode);
+    Some(SciValue::wrap_with_exponent(B::from_i128(rounded.base)?, E::from_i64(rounded.e_exp)?))
+  }
+
+  /**
+   * Converts a log scale reading back to the value it
+   * stands for, 10^-reading, with `digits` significant
+   * digits rounded using `mode`.
+   *
+   * Readings with no fractional part give exact powers of
+   * ten.  Returns None if `digits` is zero or more than
+   * `MAX_LOG_DIGITS`, or if the result doesn't fit the
+   * value's types.
+   */
+  pub fn from_log_scale(reading:&SciValue<B,E>, digits:usize, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    if digits == 0 || digits > MAX_LOG_DIGITS {
+      return None;
+    }
+    //Split -reading into an integer part and a fraction in
+    //[0, 1) with at most FIXED_DIGITS - 3 places
+    let reduced  = reading.reduce();
+    let exp      = reduced.e_exp.to_i64()?;
+    let negated  = reduced.base.to_i128()?.checked_neg()?;
+    if exp >= 0 {
+      let power = negated.checked_mul(pow10::<i128>(exp as usize)?)?;
+      return Some(SciValue::wrap_with_exponent(B::one(), E::from_i128(power)?));
+    }
+    let places   = (-exp) as usize;
+    let kept     = places.min(FIXED_DIGITS - 3);
+    let divisor  = pow10::<i128>(places)?;
+    let int_part = negated.div_euclid(divisor);
+    //Nonzero, as the reading was reduced
+    let frac     = (negated.rem_euclid(divisor) / pow10::<i128>(places - kept)?) as u128;
+
+    //Largest mantissa whose logarithm doesn't exceed the fraction
+    let at_most = |candidate:u128, int_digits:i64| {
+      let (int_digit, cand_frac, sticky) = log10_digits(candidate, kept);
+      int_digit == int_digits && (cand_frac < frac || (cand_frac == frac && !sticky))
+    };
+    let (mut lo, mut hi) = (pow10::<u128>(digits - 1)?, pow10::<u128>(digits)? - 1);
+    while lo < hi {
+      let mid = lo + (hi - lo).div_ceil(2);
+      if at_most(mid, digits as i64 - 1) { lo = mid; } else { hi = mid - 1; }
+    }
+    let on_mantissa = { let (_, lo_frac, sticky) = log10_digits(lo, kept); lo_frac == frac && !sticky };
+    let round_up = match mode {
+      RoundingMode::Truncate | RoundingMode::Floor => false,
+      RoundingMode::Ceiling                        => !on_mantissa,
+      //The midpoint's logarithm is never exactly a decimal
+      //fraction, so there are no ties to break
+      RoundingMode::HalfEven | RoundingMode::HalfAwayFromZero => at_most(lo * 10 + 5, digits as i64)
+    };
+    let mantissa = if round_up { lo + 1 } else { lo };
+
+    let exp = int_part.checked_sub(digits as i128 - 1)?;
+    Some(SciValue::wrap_with_exponent(B::from_u128(mantissa)?, E::from_i128(exp)?))
+  }
+}
+
+/**
+ * Digits of log10(n), for positive n, as its integer
+ * part, the first `places` digits after the point and
+ * whether any later digit is nonzero.
+ *
+ * Each digit is found by raising the mantissa, held in
+ * fixed point, to the tenth power: the number of digits
+ * that gains is the next digit of the logarithm.
+ */
+fn log10_digits(n:u128, places:usize) -> (i64, u128, bool) {
+  let mut int_part = 0i64;
+  let mut remaining = n / 10;
+  while remaining > 0 {
+    int_part += 1;
+    remaining /= 10;
+  }
+
+  let shift = int_part as usize;
+  let mut mantissa = if shift <= FIXED_DIGITS {
+    n * pow10::<u128>(FIXED_DIGITS - shift).unwrap_or(0)
+  } else {
+    n / pow10::<u128>(shift - FIXED_DIGITS).unwrap_or(1)
+  };
+
+  let mut frac = 0u128;
+  for _ in 0..places {
+    let mut power = FIXED_ONE;
+    let mut digit = 0u128;
+    for _ in 0..10 {
+      power = power * mantissa / FIXED_ONE;
+      if power >= 10 * FIXED_ONE {
+        power /= 10;
+        digit += 1;
+      }
+    }
+    frac = frac * 10 + digit;
+    mantissa = power;
+  }
+  (int_part, frac, mantissa != FIXED_ONE)
+}
+
+#[cfg(test)]
+mod test{
+  use super::super::{SciValue, RoundingMode};
+
+  #[test]
+  fn to_log_scale(){
+    let neutral = SciValue::wrap_with_exponent(1i64, -7i32);
+    assert_eq!(neutral.to_log_scale(-2, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(700, -2)));
+    assert_eq!(SciValue::wrap_with_exponent(25i64, -8i32).to_log_scale(-2, RoundingMode::HalfEven),
+               Some(SciValue::wrap_with_exponent(660, -2)));
+    assert_eq!(SciValue::wrap_with_exponent(25i64, -8i32).to_log_scale(-4, RoundingMode::Truncate),
+               Some(SciValue::wrap_with_exponent(66020, -4)));
+    assert_eq!(SciValue::wrap_with_exponent(25i64, -8i32).to_log_scale(-4, RoundingMode::Floor),
+               Some(SciValue::wrap_with_exponent(66020, -4)));
+    assert_eq!(SciValue::wrap_with_exponent(25i64, -8i32).to_log_scale(-4, RoundingMode::Ceiling),
+               Some(SciValue::wrap_with_exponent(66021, -4)));
+    //Above one the reading goes negative
+    assert_eq!(SciValue::wrap(2i64).to_log_scale(-3i32, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(-301, -3)));
+    assert_eq!(SciValue::wrap(0i64).to_log_scale(-3i32, RoundingMode::HalfEven), None);
+    assert_eq!(SciValue::wrap(-5i64).to_log_scale(-3i32, RoundingMode::HalfEven), None);
+    assert_eq!(SciValue::wrap(2i64).to_log_scale(-13i32, RoundingMode::HalfEven), None);
+  }
+
+  #[test]
+  fn from_log_scale(){
+    let reading = SciValue::wrap_with_exponent(72i64, -1i32);
+    assert_eq!(SciValue::from_log_scale(&reading, 3, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(631, -10)));
+    assert_eq!(SciValue::from_log_scale(&reading, 5, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(63096, -12)));
+    assert_eq!(SciValue::from_log_scale(&reading, 5, RoundingMode::Truncate), Some(SciValue::wrap_with_exponent(63095, -12)));
+    assert_eq!(SciValue::from_log_scale(&reading, 5, RoundingMode::Ceiling), Some(SciValue::wrap_with_exponent(63096, -12)));
+    assert_eq!(SciValue::from_log_scale(&SciValue::wrap(7i64), 3, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(1, -7i32)));
+    assert_eq!(SciValue::from_log_scale(&SciValue::wrap_with_exponent(-3i64, -1i32), 4, RoundingMode::HalfEven),
+               Some(SciValue::wrap_with_exponent(1995, -3)));
+    assert_eq!(SciValue::from_log_scale(&reading, 0, RoundingMode::HalfEven), None);
+
+    //Round trips at matching precision
+    let conc = SciValue::wrap_with_exponent(347i64, -9i32);
+    let ph = conc.to_log_scale(-8, RoundingMode::HalfEven).unwrap();
+    assert_eq!(SciValue::from_log_scale(&ph, 3, RoundingMode::HalfEven), Some(conc));
+  }
+}
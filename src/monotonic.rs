@@ -0,0 +1,164 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! An order preserving mapping between values and `u128`
+//! keys, so sorting and range comparisons can be done by
+//! hardware or databases which only understand plain
+//! unsigned integers.
+//!
+//! Keys are laid out, from the most significant bit:
+//!
+//! | bits    | field                                         |
+//! |---------|-----------------------------------------------|
+//! | 127-126 | class: 0 negative, 1 zero, 2 positive          |
+//! | 125-67  | exponent of the leading digit, biased by 2^58 |
+//! | 66-0    | the digits, left aligned to exactly 20 digits |
+//!
+//! Zero is exactly `1 << 126`.  For negative values bits
+//! 125-0 hold the complement of those of the matching
+//! positive value, so larger magnitudes sort lower.  Keys
+//! compare as the values do, and equal values (such as
+//! `20e2` and `2e3`) share a key.
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+
+use super::{SciValue, decimal_digits, saturating_exp_to_i64};
+
+const CLASS_SHIFT: u32 = 126;
+const CLASS_NEGATIVE: u128 = 0;
+const CLASS_ZERO: u128 = 1;
+const CLASS_POSITIVE: u128 = 2;
+const BODY_MASK: u128 = (1 << CLASS_SHIFT) - 1;
+
+const DIGITS_BITS: u32 = 67;
+const DIGITS_MASK: u128 = (1 << DIGITS_BITS) - 1;
+const SIGNIFICAND_DIGITS: usize = 20;
+const EXPONENT_BIAS: i64 = 1 << 58;
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Encodes the value as an order preserving key.  Returns
+   * None if the reduced base has more than 20 digits, or
+   * if the exponent of its leading digit is outside
+   * `-2^58..2^58`.
+   */
+  pub fn to_monotonic_u128(&self) -> Option<u128> {
+    let reduced = self.reduce();
+    let digits = decimal_digits(reduced.base);
+    if digits.is_empty() {
+      return Some(CLASS_ZERO << CLASS_SHIFT);
+    }
+    if digits.len() > SIGNIFICAND_DIGITS {
+      return None;
+    }
+    let leading = saturating_exp_to_i64(reduced.e_exp).checked_add(digits.len() as i64 - 1)?;
+    if !(-EXPONENT_BIAS..EXPONENT_BIAS).contains(&leading) {
+      return None;
+    }
+
+    let significand = digits.iter()
+                            .chain(std::iter::repeat(&0u8).take(SIGNIFICAND_DIGITS - digits.len()))
+                            .fold(0u128, |acc, digit| acc * 10 + *digit as u128);
+    let body = (((leading + EXPONENT_BIAS) as u128) << DIGITS_BITS) | significand;
+    if reduced.base < B::zero() {
+      Some((CLASS_NEGATIVE << CLASS_SHIFT) | (!body & BODY_MASK))
+    } else {
+      Some((CLASS_POSITIVE << CLASS_SHIFT) | body)
+    }
+  }
+
+  /**
+   * Decodes a key made by `to_monotonic_u128`, giving the
+   * value in reduced form.  Returns None for bit patterns
+   * which aren't valid keys, or if the value doesn't fit
+   * the types.
+   */
+  pub fn from_monotonic_u128(key:u128) -> Option<SciValue<B,E>> {
+    let body = match key >> CLASS_SHIFT {
+      CLASS_ZERO if key & BODY_MASK == 0 => return Some(SciValue::wrap_with_exponent(B::zero(), E::zero())),
+      CLASS_NEGATIVE                     => !key & BODY_MASK,
+      CLASS_POSITIVE                     => key & BODY_MASK,
+      _                                  => return None
+    };
+    let mut significand = body & DIGITS_MASK;
+    if !(10u128.pow(SIGNIFICAND_DIGITS as u32 - 1)..10u128.pow(SIGNIFICAND_DIGITS as u32)).contains(&significand) {
+      return None;
+    }
+    let leading = (body >> DIGITS_BITS) as i64 - EXPONENT_BIAS;
+
+    let mut digits = SIGNIFICAND_DIGITS as i64;
+    while significand % 10 == 0 {
+      significand /= 10;
+      digits -= 1;
+    }
+    let base = if key >> CLASS_SHIFT == CLASS_NEGATIVE {
+      <B as FromPrimitive>::from_i128(-(significand as i128))
+    } else {
+      <B as FromPrimitive>::from_u128(significand)
+    };
+    let exp = <E as FromPrimitive>::from_i64(leading - (digits - 1));
+    match (base, exp) {
+      (Some(base), Some(exp)) => Some(SciValue::wrap_with_exponent(base, exp)),
+      _                       => None
+    }
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::super::SciValue;
+
+  #[test]
+  fn keys_preserve_order(){
+    let values: Vec<SciValue<i64,i32>> = vec![
+      SciValue::wrap(i64::MIN),
+      SciValue::wrap_with_exponent(-5, 3),
+      SciValue::wrap_with_exponent(-45, 2),
+      SciValue::wrap_with_exponent(-1, -20),
+      SciValue::wrap(0),
+      SciValue::wrap_with_exponent(1, -20),
+      SciValue::wrap_with_exponent(99, -1),
+      SciValue::wrap(10),
+      SciValue::wrap_with_exponent(101, -1),
+      SciValue::wrap(i64::MAX)
+    ];
+    let keys: Vec<u128> = values.iter().map(|val| val.to_monotonic_u128().unwrap()).collect();
+    for pair in keys.windows(2) {
+      assert!(pair[0] < pair[1]);
+    }
+    for (val, key) in values.iter().zip(keys.iter()) {
+      assert_eq!(SciValue::from_monotonic_u128(*key), Some(val.reduce()));
+    }
+    assert_eq!(SciValue::wrap_with_exponent(20i64, 2i32).to_monotonic_u128(), SciValue::wrap_with_exponent(2i64, 3i32).to_monotonic_u128());
+    assert_eq!(SciValue::<i64,i32>::wrap(0).to_monotonic_u128(), Some(1 << 126));
+  }
+
+  #[test]
+  fn invalid_keys(){
+    assert_eq!(SciValue::<i64,i32>::from_monotonic_u128(u128::MAX), None);
+    assert_eq!(SciValue::<i64,i32>::from_monotonic_u128((1 << 126) + 1), None);
+    assert_eq!(SciValue::<i64,i32>::from_monotonic_u128(2 << 126), None);
+    assert_eq!(SciValue::<i8,i8>::from_monotonic_u128(SciValue::<i64,i32>::wrap(1000).to_monotonic_u128().unwrap()), Some(SciValue::wrap_with_exponent(1, 3)));
+    assert_eq!(SciValue::<i8,i8>::from_monotonic_u128(SciValue::<i64,i32>::wrap(1234).to_monotonic_u128().unwrap()), None);
+    assert_eq!(SciValue::<i128,i32>::wrap(10i128.pow(25) + 1).to_monotonic_u128(), None);
+  }
+}
@@ -0,0 +1,315 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use std::fmt::{Display, Formatter, Error, Write};
+use std::error::Error as StdError;
+
+use super::{ExponentMarker, ParseSciValueError, ParseErrorKind};
+
+/**
+ * A convention for writing values in text, for
+ * `convert_notation`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationStyle {
+  /// Integer digits and an exponent, as `format_with`
+  /// writes them: `-15e-3`, or `-15D-3` with
+  /// `ExponentMarker::Fortran`.  Reading also accepts
+  /// decimal points, `+` signs and a lower case marker,
+  /// as in Fortran's `-1.50d-02`, and a missing exponent.
+  Standard(ExponentMarker),
+  /// LaTeX math mode with one digit before the point:
+  /// `-1.5 \times 10^{-2}`.  The exponent is left out when
+  /// it's zero.
+  Latex,
+  /// Engineering notation with an SI prefix after the
+  /// digits, from quecto to quetta: `-15m`.  Reading also
+  /// accepts a space before the prefix, and `u` or `μ`
+  /// for micro.
+  SiPrefix
+}
+
+/**
+ * Error converting text between notations.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationError {
+  /// The text isn't a value in the source notation.
+  Parse(ParseSciValueError),
+  /// The value is beyond what the target notation can
+  /// write, such as magnitudes outside the SI prefixes.
+  OutOfRange
+}
+
+impl Display for NotationError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match *self {
+      NotationError::Parse(ref err) => write!(fmtr, "couldn't read the value: {}", err),
+      NotationError::OutOfRange     => fmtr.write_str("value can't be written in the target notation")
+    }
+  }
+}
+
+impl StdError for NotationError {}
+
+const SI_PREFIXES: [(&str, i128); 22] = [("q", -30), ("r", -27), ("y", -24), ("z", -21), ("a", -18), ("f", -15),
+                                         ("p", -12), ("n", -9), ("\u{b5}", -6), ("m", -3), ("k", 3), ("M", 6),
+                                         ("G", 9), ("T", 12), ("P", 15), ("E", 18), ("Z", 21), ("Y", 24),
+                                         ("R", 27), ("Q", 30), ("u", -6), ("\u{3bc}", -6)];
+
+/**
+ * Re-writes a value from one notation in another, such as
+ * Fortran's `1.5D-3` as LaTeX's `1.5 \times 10^{-3}`.
+ * The digits are carried over as text, so any number of
+ * them converts exactly, as do exponents up to those of
+ * an `i128`.  Error offsets are into `text`.
+ */
+pub fn convert_notation(from:NotationStyle, to:NotationStyle, text:&str) -> Result<String, NotationError> {
+  let (negative, digits, exponent) = read(from, text).map_err(NotationError::Parse)?;
+  let mut out = String::new();
+  if negative {
+    out.push('-');
+  }
+  let leading = exponent + digits.len() as i128 - 1;
+  match to {
+    NotationStyle::Standard(marker) => {
+      push_digits(&mut out, &digits);
+      write!(out, "{}{}", marker.as_str(), exponent).expect("Couldn't write to a String");
+    },
+    NotationStyle::Latex            => {
+      push_digits(&mut out, &digits[..1]);
+      if digits.len() > 1 {
+        out.push('.');
+        push_digits(&mut out, &digits[1..]);
+      }
+      if leading != 0 {
+        write!(out, " \\times 10^{{{}}}", leading).expect("Couldn't write to a String");
+      }
+    },
+    NotationStyle::SiPrefix         => {
+      if digits != [0] && !(-30..=32).contains(&leading) {
+        return Err(NotationError::OutOfRange);
+      }
+      //Up to three digits before the point
+      let prefix = if digits == [0] { 0 } else { leading.div_euclid(3) * 3 };
+      let whole = (leading - prefix + 1) as usize;
+      if digits.len() > whole {
+        push_digits(&mut out, &digits[..whole]);
+        out.push('.');
+        push_digits(&mut out, &digits[whole..]);
+      } else {
+        push_digits(&mut out, &digits);
+        out.extend(std::iter::repeat('0').take(whole - digits.len()));
+      }
+      if let Some(&(symbol, _)) = SI_PREFIXES.iter().find(|&&(_, exp)| exp == prefix) {
+        out.push_str(symbol);
+      }
+    }
+  }
+  Ok(out)
+}
+
+fn push_digits(out:&mut String, digits:&[u8]) {
+  out.extend(digits.iter().map(|digit| (b'0' + digit) as char));
+}
+
+/**
+ * Reads the sign, the digits without leading or trailing
+ * zeros (a single 0 for zero), and the exponent applying
+ * to the last of those digits.
+ */
+fn read(style:NotationStyle, text:&str) -> Result<(bool, Vec<u8>, i128), ParseSciValueError> {
+  if text.is_empty() {
+    return Err(ParseSciValueError{kind: ParseErrorKind::Empty, offset: 0});
+  }
+  let bytes = text.as_bytes();
+  let mut pos = 0;
+  let negative = bytes[0] == b'-';
+  if negative || bytes[0] == b'+' {
+    pos += 1;
+  }
+  let mut digits = Vec::new();
+  let mut fraction: Option<i128> = None;
+  while pos < bytes.len() {
+    match bytes[pos] {
+      b'0'..=b'9'                 => {
+        digits.push(bytes[pos] - b'0');
+        fraction = fraction.map(|len| len + 1);
+      },
+      b'.' if fraction.is_none()  => fraction = Some(0),
+      _                           => break
+    }
+    pos += 1;
+  }
+  if digits.is_empty() {
+    return Err(ParseSciValueError{kind: ParseErrorKind::MissingDigits, offset: pos});
+  }
+
+  let written = match style {
+    NotationStyle::Standard(marker) => read_marker_exponent(text, pos, marker)?,
+    NotationStyle::Latex            => read_latex_exponent(text, pos)?,
+    NotationStyle::SiPrefix         => read_prefix(text, pos)?
+  };
+  let overflow = ParseSciValueError{kind: ParseErrorKind::ExponentOverflow, offset: pos};
+  let mut exponent = written.checked_sub(fraction.unwrap_or(0)).ok_or(overflow)?;
+
+  let significant = match digits.iter().position(|&digit| digit != 0) {
+    Some(first) => first,
+    None        => return Ok((false, vec![0], 0))
+  };
+  digits.drain(..significant);
+  while digits.last() == Some(&0) {
+    digits.pop();
+    exponent = exponent.checked_add(1).ok_or(overflow)?;
+  }
+  Ok((negative, digits, exponent))
+}
+
+fn read_marker_exponent(text:&str, pos:usize, marker:ExponentMarker) -> Result<i128, ParseSciValueError> {
+  if pos == text.len() {
+    return Ok(0);
+  }
+  let spelled: &[&str] = match marker {
+    ExponentMarker::Lower | ExponentMarker::Upper => &["e", "E"],
+    ExponentMarker::Fortran                       => &["D", "d"],
+    _                                             => &[marker.as_str()]
+  };
+  match spelled.iter().find(|spelling| text[pos..].starts_with(**spelling)) {
+    Some(spelling) => read_exponent(text, pos + spelling.len(), text.len()),
+    None           => Err(ParseSciValueError{kind: ParseErrorKind::InvalidDigit, offset: pos})
+  }
+}
+
+fn read_latex_exponent(text:&str, pos:usize) -> Result<i128, ParseSciValueError> {
+  let pos = skip_spaces(text, pos);
+  if pos == text.len() {
+    return Ok(0);
+  }
+  let pos = expect(text, pos, "\\times")?;
+  let pos = expect(text, skip_spaces(text, pos), "10^")?;
+  if !text[pos..].starts_with('{') {
+    return read_exponent(text, pos, text.len());
+  }
+  match text[pos..].find('}').map(|close| pos + close) {
+    Some(close) if close + 1 == text.len() => read_exponent(text, pos + 1, close),
+    Some(close)                            => Err(ParseSciValueError{kind: ParseErrorKind::InvalidDigit, offset: close + 1}),
+    None                                   => Err(ParseSciValueError{kind: ParseErrorKind::MissingDigits, offset: text.len()})
+  }
+}
+
+fn read_prefix(text:&str, pos:usize) -> Result<i128, ParseSciValueError> {
+  let pos = if text[pos..].starts_with(' ') { pos + 1 } else { pos };
+  if pos == text.len() {
+    return Ok(0);
+  }
+  match SI_PREFIXES.iter().find(|&&(symbol, _)| &text[pos..] == symbol) {
+    Some(&(_, exp)) => Ok(exp),
+    None            => Err(ParseSciValueError{kind: ParseErrorKind::InvalidDigit, offset: pos})
+  }
+}
+
+/**
+ * Reads a signed exponent filling `text[start..end]`.
+ */
+fn read_exponent(text:&str, start:usize, end:usize) -> Result<i128, ParseSciValueError> {
+  let field = &text[start..end];
+  let unsigned = field.strip_prefix(['-', '+']).unwrap_or(field);
+  let digits_at = start + field.len() - unsigned.len();
+  if unsigned.is_empty() {
+    return Err(ParseSciValueError{kind: ParseErrorKind::MissingDigits, offset: digits_at});
+  }
+  if let Some(bad) = unsigned.find(|chr:char| !chr.is_ascii_digit()) {
+    return Err(ParseSciValueError{kind: ParseErrorKind::InvalidDigit, offset: digits_at + bad});
+  }
+  field.parse().map_err(|_| ParseSciValueError{kind: ParseErrorKind::ExponentOverflow, offset: digits_at})
+}
+
+fn skip_spaces(text:&str, pos:usize) -> usize {
+  pos + text[pos..].len() - text[pos..].trim_start_matches(' ').len()
+}
+
+fn expect(text:&str, pos:usize, literal:&str) -> Result<usize, ParseSciValueError> {
+  if text[pos..].starts_with(literal) {
+    Ok(pos + literal.len())
+  } else {
+    Err(ParseSciValueError{kind: ParseErrorKind::InvalidDigit, offset: pos})
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::{convert_notation, NotationStyle, NotationError};
+  use super::super::{ExponentMarker, ParseSciValueError, ParseErrorKind, SciValue, FormatOptions};
+
+  const FORTRAN: NotationStyle = NotationStyle::Standard(ExponentMarker::Fortran);
+  const RUST: NotationStyle = NotationStyle::Standard(ExponentMarker::Lower);
+
+  #[test]
+  fn converted_notations(){
+    assert_eq!(convert_notation(FORTRAN, NotationStyle::Latex, "-1.50D-02"), Ok("-1.5 \\times 10^{-2}".to_string()));
+    assert_eq!(convert_notation(FORTRAN, NotationStyle::Latex, "+2.5d3"), Ok("2.5 \\times 10^{3}".to_string()));
+    assert_eq!(convert_notation(FORTRAN, NotationStyle::SiPrefix, "4.7D-6"), Ok("4.7\u{b5}".to_string()));
+    assert_eq!(convert_notation(FORTRAN, RUST, "0.000D+00"), Ok("0e0".to_string()));
+    assert_eq!(convert_notation(NotationStyle::Latex, FORTRAN, "1.5 \\times 10^{-3}"), Ok("15D-4".to_string()));
+    assert_eq!(convert_notation(NotationStyle::Latex, RUST, "7\\times 10^3"), Ok("7e3".to_string()));
+    assert_eq!(convert_notation(NotationStyle::Latex, NotationStyle::SiPrefix, "1.5"), Ok("1.5".to_string()));
+    assert_eq!(convert_notation(NotationStyle::SiPrefix, NotationStyle::Latex, "220 k"), Ok("2.2 \\times 10^{5}".to_string()));
+    assert_eq!(convert_notation(NotationStyle::SiPrefix, RUST, "3.3u"), Ok("33e-7".to_string()));
+
+    //Engineering notation keeps up to three whole digits
+    let si = |text| convert_notation(RUST, NotationStyle::SiPrefix, text);
+    assert_eq!(si("15e-3"), Ok("15m".to_string()));
+    assert_eq!(si("1e5"), Ok("100k".to_string()));
+    assert_eq!(si("-123456e-2"), Ok("-1.23456k".to_string()));
+    assert_eq!(si("1e-30"), Ok("1q".to_string()));
+    assert_eq!(si("999e30"), Ok("999Q".to_string()));
+    assert_eq!(si("1e33"), Err(NotationError::OutOfRange));
+    assert_eq!(si("1e-31"), Err(NotationError::OutOfRange));
+
+    //Any number of digits carries over
+    let digits = "1234567890123456789012345678901234567890123";
+    assert_eq!(convert_notation(RUST, FORTRAN, digits), Ok(format!("{}D0", digits)));
+    assert_eq!(convert_notation(RUST, NotationStyle::Standard(ExponentMarker::TimesTenPower), "-00500E+2"),
+               Ok("-5\u{d7}10^4".to_string()));
+
+    //Standard output matches format_with
+    let val = SciValue::wrap_with_exponent(-1500i32, -5isize);
+    let options = FormatOptions::new().with_exponent_marker(ExponentMarker::Upper);
+    assert_eq!(convert_notation(FORTRAN, NotationStyle::Standard(ExponentMarker::Upper), "-1.5D-2"), Ok(val.format_with(&options)));
+  }
+
+  #[test]
+  fn unreadable_notations(){
+    let err = |kind, offset| Err(NotationError::Parse(ParseSciValueError{kind, offset}));
+    assert_eq!(convert_notation(FORTRAN, RUST, ""), err(ParseErrorKind::Empty, 0));
+    assert_eq!(convert_notation(FORTRAN, RUST, "-.D3"), err(ParseErrorKind::MissingDigits, 2));
+    assert_eq!(convert_notation(FORTRAN, RUST, "1.5e3"), err(ParseErrorKind::InvalidDigit, 3));
+    assert_eq!(convert_notation(FORTRAN, RUST, "1.5D"), err(ParseErrorKind::MissingDigits, 4));
+    assert_eq!(convert_notation(FORTRAN, RUST, "1.5D+3x"), err(ParseErrorKind::InvalidDigit, 6));
+    assert_eq!(convert_notation(RUST, FORTRAN, "1e999999999999999999999999999999999999999"), err(ParseErrorKind::ExponentOverflow, 2));
+    assert_eq!(convert_notation(NotationStyle::Latex, RUST, "1.5 \\cdot 10^{3}"), err(ParseErrorKind::InvalidDigit, 4));
+    assert_eq!(convert_notation(NotationStyle::Latex, RUST, "1.5 \\times 10^{3} m"), err(ParseErrorKind::InvalidDigit, 17));
+    assert_eq!(convert_notation(NotationStyle::Latex, RUST, "1.5 \\times 10^{3"), err(ParseErrorKind::MissingDigits, 16));
+    assert_eq!(convert_notation(NotationStyle::SiPrefix, RUST, "15 km"), err(ParseErrorKind::InvalidDigit, 3));
+    assert_eq!(format!("{}", NotationError::OutOfRange), "value can't be written in the target notation");
+  }
+}
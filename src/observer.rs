@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Reporting of silent numeric degradation.
+//!
+//! The saturating operations, and the rounding in `TwoSci`,
+//! trade exactness for always returning a value.  With the
+//! `observer` feature a process wide observer can be
+//! registered with `set_overflow_observer`, and is called
+//! each time one of them does so, letting long running
+//! services count and alert on it.  Without the feature
+//! nothing is recorded, and the checks compile away.
+
+#[cfg(feature = "observer")]
+use std::sync::RwLock;
+
+/**
+ * The kinds of degradation an observer is told about.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowKind {
+  /// The result was clamped to the largest or smallest
+  /// value the types hold.
+  Saturated,
+  /// Digits of the result were rounded or truncated away.
+  PrecisionLost,
+  /// The result is exact, but only at a coarser exponent
+  /// than the operands had.
+  Widened
+}
+
+/**
+ * One report to the observer.
+ */
+#[cfg(feature = "observer")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowEvent {
+  pub kind: OverflowKind,
+  /// The operation which degraded, such as `"saturating_add"`.
+  pub operation: &'static str
+}
+
+/// A registered observer.
+#[cfg(feature = "observer")]
+pub type OverflowObserver = Box<dyn Fn(&OverflowEvent) + Send + Sync>;
+
+#[cfg(feature = "observer")]
+static OBSERVER: RwLock<Option<OverflowObserver>> = RwLock::new(None);
+
+/**
+ * Registers the observer called on every degraded
+ * operation, in whichever thread it happens, or removes it
+ * if given None.  Returns the observer it replaces.
+ */
+#[cfg(feature = "observer")]
+pub fn set_overflow_observer(observer:Option<OverflowObserver>) -> Option<OverflowObserver> {
+  let mut current = OBSERVER.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+  std::mem::replace(&mut *current, observer)
+}
+
+/**
+ * Tells the observer, if there is one, about a degraded
+ * operation.  Used throughout the crate, but not exported.
+ */
+pub fn notify(kind:OverflowKind, operation:&'static str) {
+  #[cfg(feature = "observer")]
+  {
+    let current = OBSERVER.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(ref observer) = *current {
+      observer(&OverflowEvent{kind, operation});
+    }
+  }
+  #[cfg(not(feature = "observer"))]
+  let _ = (kind, operation);
+}
+
+#[cfg(all(test, feature = "observer"))]
+mod test{
+  use std::sync::{Arc, Mutex};
+  use std::thread::{self, ThreadId};
+  use super::{set_overflow_observer, OverflowEvent, OverflowKind};
+  use super::super::{SciValue, TwoSci};
+
+  #[test]
+  fn observed_operations(){
+    //Other tests run alongside this one, so only events
+    //from this thread are kept
+    let events: Arc<Mutex<Vec<(ThreadId, OverflowEvent)>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorder = events.clone();
+    set_overflow_observer(Some(Box::new(move |event:&OverflowEvent| {
+      recorder.lock().unwrap().push((thread::current().id(), *event));
+    })));
+
+    let big = SciValue::<i8,i8>::wrap(100);
+    big.saturating_add(&SciValue::wrap(20));
+    big.saturating_add(&big);
+    SciValue::<i8,i8>::wrap(127).saturating_add(&SciValue::wrap(1));
+    SciValue::<i8,i8>::wrap_with_exponent(64, 127).saturating_sub(&SciValue::wrap_with_exponent(-64, 127));
+    big.saturating_mul(&big);
+    SciValue::<i8,i8>::wrap(3).saturating_pow(5);
+    let two_scales = TwoSci::new(SciValue::wrap_with_exponent(1i32, 20isize)).checked_add(&TwoSci::new(SciValue::wrap(1)));
+    two_scales.and_then(|sum| sum.checked_add(&TwoSci::new(SciValue::wrap_with_exponent(1, -20))));
+    assert!(set_overflow_observer(None).is_some());
+    big.saturating_mul(&big);
+
+    let here = thread::current().id();
+    let seen: Vec<(OverflowKind, &str)> = events.lock().unwrap().iter()
+                                                .filter(|&&(thread, _)| thread == here)
+                                                .map(|&(_, event)| (event.kind, event.operation))
+                                                .collect();
+    assert_eq!(seen, vec![
+      (OverflowKind::Widened, "saturating_add"),
+      (OverflowKind::PrecisionLost, "saturating_add"),
+      (OverflowKind::Saturated, "saturating_sub"),
+      (OverflowKind::Saturated, "saturating_mul"),
+      (OverflowKind::Saturated, "saturating_pow"),
+      (OverflowKind::PrecisionLost, "TwoSci::checked_add")
+    ]);
+  }
+}
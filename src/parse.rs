@@ -0,0 +1,506 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive, Num};
+use std::fmt::{Display, Formatter, Error};
+use std::error::Error as StdError;
+use std::str::FromStr;
+
+use super::{SciValue, base_constant, decimal_digits, saturating_exp_to_i64};
+
+/**
+ * What went wrong parsing a `SciValue` from a string.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+  /// The input was empty.
+  Empty,
+  /// The mantissa or exponent had a sign but no digits.
+  MissingDigits,
+  /// A character that isn't a decimal digit.
+  InvalidDigit,
+  /// The mantissa doesn't fit in the base type.
+  MantissaOverflow,
+  /// The exponent doesn't fit in the exponent type.
+  ExponentOverflow,
+  /// The mantissa has more digits than the limits allow.
+  TooManyDigits,
+  /// The exponent is outside the bounds the limits allow.
+  ExponentOutOfBounds
+}
+
+/**
+ * Error parsing a `SciValue` from a string, with the
+ * byte offset into the input at which parsing failed.
+ *
+ * For overflows the offset is that of the first digit
+ * which didn't fit; for missing digits it's where the
+ * digits should have started.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseSciValueError {
+  pub kind: ParseErrorKind,
+  pub offset: usize
+}
+
+impl Display for ParseSciValueError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    let reason = match self.kind {
+      ParseErrorKind::Empty               => return fmtr.write_str("cannot parse a value from an empty string"),
+      ParseErrorKind::MissingDigits       => "expected digits",
+      ParseErrorKind::InvalidDigit        => "invalid digit",
+      ParseErrorKind::MantissaOverflow    => "mantissa doesn't fit in the base type",
+      ParseErrorKind::ExponentOverflow    => "exponent doesn't fit in the exponent type",
+      ParseErrorKind::TooManyDigits       => "too many mantissa digits",
+      ParseErrorKind::ExponentOutOfBounds => "exponent out of bounds"
+    };
+    write!(fmtr, "{} at offset {}", reason, self.offset)
+  }
+}
+
+impl StdError for ParseSciValueError {}
+
+/**
+ * Bounds enforced while parsing input which may be hostile,
+ * so that the cost of a parse and the range of the values
+ * it produces don't depend on the sender's goodwill.
+ *
+ * Digit counts include leading zeros, and the exponent is
+ * checked as written, before any reduction.  With the
+ * `serde` feature, the same limits can be enforced on
+ * deserialized values by `SciValue::deserialize_with_limits`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+  pub max_mantissa_digits: usize,
+  pub min_exponent: i64,
+  pub max_exponent: i64
+}
+
+impl ParseLimits {
+  /// Limits accepting anything which fits the target types.
+  pub fn unlimited() -> ParseLimits {
+    ParseLimits{max_mantissa_digits: usize::MAX, min_exponent: i64::MIN, max_exponent: i64::MAX}
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Parses the value as `from_str` does, but rejects
+   * mantissas with more than `limits.max_mantissa_digits`
+   * digits before reading them, and exponents outside
+   * `limits.min_exponent..=limits.max_exponent`.
+   */
+  pub fn from_str_with_limits(text:&str, limits:&ParseLimits) -> Result<SciValue<B,E>, ParseSciValueError> {
+    if text.is_empty() {
+      return Err(ParseSciValueError{kind: ParseErrorKind::Empty, offset: 0});
+    }
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+      Some(pos) => (&text[..pos], Some((&text[pos + 1..], pos + 1))),
+      None      => (text, None)
+    };
+
+    let base = parse_field::<B>(mantissa, 0, 10, limits.max_mantissa_digits, ParseErrorKind::MantissaOverflow)?;
+    let (exp, exp_offset) = match exponent {
+      Some((digits, offset)) => (parse_field::<E>(digits, offset, 10, usize::MAX, ParseErrorKind::ExponentOverflow)?, offset),
+      None                   => (E::zero(), text.len())
+    };
+    //Saturating keeps exponents beyond an i64 in order
+    //against the limits, so unlimited accepts every exponent
+    let exp_val = saturating_exp_to_i64(exp);
+    if exp_val >= limits.min_exponent && exp_val <= limits.max_exponent {
+      Ok(SciValue::wrap_with_exponent(base, exp))
+    } else {
+      Err(ParseSciValueError{kind: ParseErrorKind::ExponentOutOfBounds, offset: exp_offset})
+    }
+  }
+
+  /**
+   * Parses the value as `from_str` does and reduces it, so
+   * `"12300e-1"` and `"123e1"` both give `123e1`.  Fails
+   * with `ExponentOverflow` if stripping the trailing
+   * zeros would take the exponent past the type's maximum.
+   */
+  pub fn from_str_normalized(text:&str) -> Result<SciValue<B,E>, ParseSciValueError> {
+    let val: SciValue<B,E> = text.parse()?;
+    let zeros = decimal_digits(val.base).iter().rev().take_while(|&&digit| digit == 0).count();
+    match <E as FromPrimitive>::from_usize(zeros).and_then(|zeros| val.e_exp.checked_add(&zeros)) {
+      Some(_) => Ok(val.reduce()),
+      None    => {
+        let offset = text.find(['e', 'E']).map(|pos| pos + 1).unwrap_or(text.len());
+        Err(ParseSciValueError{kind: ParseErrorKind::ExponentOverflow, offset})
+      }
+    }
+  }
+
+  /**
+   * Parses a decimal numeric literal as written in Rust
+   * source, such as `1_000_000u64` or `2.5e3_f64`.  Type
+   * suffixes and `_` separators are dropped, and a decimal
+   * point moves into the exponent, so `2.5e3` reads as
+   * `25e2`.  Hex, octal and binary literals aren't
+   * supported.  Error offsets are into the original text.
+   */
+  pub fn from_rust_literal(text:&str) -> Result<SciValue<B,E>, ParseSciValueError> {
+    let body = RUST_SUFFIXES.iter()
+                            .find(|suffix| text.len() > suffix.len() && text.ends_with(*suffix))
+                            .map_or(text, |suffix| &text[..text.len() - suffix.len()]);
+
+    //Rebuild the literal without separators or the point,
+    //remembering where each character came from
+    let mut cleaned   = String::with_capacity(body.len());
+    let mut offsets   = Vec::with_capacity(body.len() + 1);
+    let mut frac_len  = None;
+    let mut exp_start = None;
+    for (idx, ch) in body.char_indices() {
+      match ch {
+        '_' if idx > 0 => continue,
+        '.' if frac_len.is_none() && exp_start.is_none() && idx > 0 => {
+          frac_len = Some(0usize);
+          continue;
+        },
+        'e' | 'E' if exp_start.is_none() => exp_start = Some(cleaned.len()),
+        _ if exp_start.is_none() => frac_len = frac_len.map(|len| len + 1),
+        _ => {}
+      }
+      cleaned.push(ch);
+      offsets.push(idx);
+    }
+    offsets.push(body.len());
+
+    let parsed: SciValue<B,E> = cleaned.parse().map_err(|err:ParseSciValueError| {
+      ParseSciValueError{kind: err.kind, offset: offsets[err.offset]}
+    })?;
+    let shift = E::from_usize(frac_len.unwrap_or(0));
+    match shift.and_then(|shift| parsed.e_exp.checked_sub(&shift)) {
+      Some(exp) => Ok(SciValue::wrap_with_exponent(parsed.base, exp)),
+      None      => Err(ParseSciValueError{
+        kind:   ParseErrorKind::ExponentOverflow,
+        offset: offsets[exp_start.map_or(cleaned.len(), |start| start + 1)]
+      })
+    }
+  }
+}
+
+/**
+ * A problem `parse_lenient` repaired, with the byte
+ * offset into the input at which it was found.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseWarning {
+  /// Whitespace around the value was dropped.
+  TrimmedWhitespace,
+  /// More than one sign, read as negative if any was `-`.
+  RepeatedSign(usize),
+  /// A comma was read as the decimal point.
+  CommaDecimal(usize),
+  /// Everything from here on wasn't part of the value.
+  TrailingGarbage(usize)
+}
+
+impl Display for ParseWarning {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match *self {
+      ParseWarning::TrimmedWhitespace       => fmtr.write_str("trimmed surrounding whitespace"),
+      ParseWarning::RepeatedSign(offset)    => write!(fmtr, "collapsed repeated signs at offset {}", offset),
+      ParseWarning::CommaDecimal(offset)    => write!(fmtr, "read comma as decimal point at offset {}", offset),
+      ParseWarning::TrailingGarbage(offset) => write!(fmtr, "ignored trailing characters from offset {}", offset)
+    }
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Parses messy input on a best-effort basis, for
+   * cleaning up real-world exports.  Surrounding
+   * whitespace, repeated signs, a comma used as the
+   * decimal point and trailing garbage are all repaired,
+   * with a warning for each repair.  Decimal points are
+   * accepted as by `from_rust_literal`.
+   *
+   * The value is None if no digits could be found, or if
+   * what was found doesn't fit the types.
+   */
+  pub fn parse_lenient(text:&str) -> (Option<SciValue<B,E>>, Vec<ParseWarning>) {
+    let mut warnings = Vec::new();
+    let trimmed = text.trim();
+    if trimmed.len() != text.len() {
+      warnings.push(ParseWarning::TrimmedWhitespace);
+    }
+    let start = text.len() - text.trim_start().len();
+    let bytes = trimmed.as_bytes();
+    let mut pos = 0usize;
+    let mut cleaned = String::with_capacity(trimmed.len() + 1);
+
+    let mut negative = false;
+    while pos < bytes.len() && (bytes[pos] == b'-' || bytes[pos] == b'+') {
+      negative = negative || bytes[pos] == b'-';
+      pos += 1;
+    }
+    if pos > 1 {
+      warnings.push(ParseWarning::RepeatedSign(start));
+    }
+    if negative {
+      cleaned.push('-');
+    }
+
+    let mut digits = 0usize;
+    let mut has_point = false;
+    while pos < bytes.len() {
+      match bytes[pos] {
+        ch if ch.is_ascii_digit() => {
+          digits += 1;
+          cleaned.push(ch as char);
+        },
+        ch @ (b'.' | b',') if !has_point => {
+          if ch == b',' {
+            warnings.push(ParseWarning::CommaDecimal(start + pos));
+          }
+          if digits == 0 {
+            cleaned.push('0');
+          }
+          has_point = true;
+          cleaned.push('.');
+        },
+        _ => break
+      }
+      pos += 1;
+    }
+
+    //An exponent only counts if it has digits
+    if digits > 0 && pos < bytes.len() && (bytes[pos] == b'e' || bytes[pos] == b'E') {
+      let sign_len = match bytes.get(pos + 1) {
+        Some(&b'-') | Some(&b'+') => 1,
+        _                         => 0
+      };
+      let exp_digits = bytes[pos + 1 + sign_len..].iter().take_while(|ch| ch.is_ascii_digit()).count();
+      if exp_digits > 0 {
+        let end = pos + 1 + sign_len + exp_digits;
+        cleaned.push_str(&trimmed[pos..end]);
+        pos = end;
+      }
+    }
+    if pos < bytes.len() {
+      warnings.push(ParseWarning::TrailingGarbage(start + pos));
+    }
+    if digits == 0 {
+      return (None, warnings);
+    }
+    (SciValue::from_rust_literal(&cleaned).ok(), warnings)
+  }
+}
+
+/// Type suffixes a Rust numeric literal may end with.
+const RUST_SUFFIXES: [&str; 14] = ["i8", "i16", "i32", "i64", "i128", "isize",
+                                   "u8", "u16", "u32", "u64", "u128", "usize",
+                                   "f32", "f64"];
+
+/**
+ * Parses strings in the form produced by `Display`, such as
+ * `5e2`, `-21e1` or `3e-4`.  The exponent marker may be `e`
+ * or `E`, either part may carry a leading `+`, and a bare
+ * integer is read with an exponent of zero.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> FromStr for SciValue<B,E> {
+  type Err = ParseSciValueError;
+
+  fn from_str(text:&str) -> Result<SciValue<B,E>, ParseSciValueError> {
+    SciValue::from_str_with_limits(text, &ParseLimits::unlimited())
+  }
+}
+
+/**
+ * In radix 10 this accepts the full notation, as
+ * `from_str` does.  The exponent is always a power of
+ * ten, so other radices only accept a bare integer,
+ * which is given an exponent of zero.
+ *
+ * Panics if `radix` isn't in `2..=36`.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Num for SciValue<B,E> {
+  type FromStrRadixErr = ParseSciValueError;
+
+  fn from_str_radix(text:&str, radix:u32) -> Result<SciValue<B,E>, ParseSciValueError> {
+    assert!((2..=36).contains(&radix), "from_str_radix: radix {} is not in 2..=36", radix);
+    if radix == 10 {
+      return text.parse();
+    }
+    if text.is_empty() {
+      return Err(ParseSciValueError{kind: ParseErrorKind::Empty, offset: 0});
+    }
+    parse_field::<B>(text, 0, radix, usize::MAX, ParseErrorKind::MantissaOverflow).map(SciValue::wrap)
+  }
+}
+
+/**
+ * Parses an optionally signed decimal integer into `T`,
+ * accumulating towards the sign of the value so the most
+ * negative value of a signed type still parses.  Errors
+ * are reported at `start` plus the offset in `text`, with
+ * `overflow` as the kind if the value doesn't fit.  More
+ * than `max_digits` digits fails before any are read.
+ */
+fn parse_field<T:PrimInt + FromPrimitive>(text:&str, start:usize, radix:u32, max_digits:usize, overflow:ParseErrorKind) -> Result<T, ParseSciValueError> {
+  let (negative, skip) = match text.as_bytes().first() {
+    Some(&b'-') => (true, 1),
+    Some(&b'+') => (false, 1),
+    _           => (false, 0)
+  };
+  if text.len() == skip {
+    return Err(ParseSciValueError{kind: ParseErrorKind::MissingDigits, offset: start + skip});
+  }
+  if text.len() - skip > max_digits {
+    return Err(ParseSciValueError{kind: ParseErrorKind::TooManyDigits, offset: start + skip + max_digits});
+  }
+
+  let type_t_radix = base_constant::<T>(radix as i64, "from_str");
+  let mut value = T::zero();
+  for (idx, ch) in text.bytes().enumerate().skip(skip) {
+    let digit = match (ch as char).to_digit(radix) {
+      Some(digit) => base_constant::<T>(digit as i64, "from_str"),
+      None        => return Err(ParseSciValueError{kind: ParseErrorKind::InvalidDigit, offset: start + idx})
+    };
+    value = value.checked_mul(&type_t_radix)
+                 .and_then(|scaled| if negative { scaled.checked_sub(&digit) } else { scaled.checked_add(&digit) })
+                 .ok_or(ParseSciValueError{kind: overflow, offset: start + idx})?;
+  }
+  Ok(value)
+}
+
+#[cfg(test)]
+mod test{
+  use super::{ParseSciValueError, ParseErrorKind, ParseLimits};
+  use super::super::SciValue;
+
+  #[test]
+  fn parse_notation(){
+    assert_eq!("5e2".parse::<SciValue<i64,i32>>(), Ok(SciValue::wrap_with_exponent(5, 2)));
+    assert_eq!("-21e1".parse::<SciValue<i64,i32>>(), Ok(SciValue::wrap_with_exponent(-21, 1)));
+    assert_eq!("3E-4".parse::<SciValue<i64,i32>>(), Ok(SciValue::wrap_with_exponent(3, -4)));
+    assert_eq!("+7e+3".parse::<SciValue<i64,i32>>(), Ok(SciValue::wrap_with_exponent(7, 3)));
+    assert_eq!("42".parse::<SciValue<i64,i32>>(), Ok(SciValue::wrap(42)));
+    assert_eq!("-128e-128".parse::<SciValue<i8,i8>>(), Ok(SciValue::wrap_with_exponent(-128, -128)));
+
+    let val = SciValue::wrap_with_exponent(-905i32, -12isize);
+    assert_eq!(format!("{}", val).parse::<SciValue<i32,isize>>(), Ok(val));
+  }
+
+  fn error(kind:ParseErrorKind, offset:usize) -> ParseSciValueError {
+    ParseSciValueError{kind, offset}
+  }
+
+  #[test]
+  fn parse_errors(){
+    assert_eq!("".parse::<SciValue<i64,i32>>(), Err(error(ParseErrorKind::Empty, 0)));
+    assert_eq!("e2".parse::<SciValue<i64,i32>>(), Err(error(ParseErrorKind::MissingDigits, 0)));
+    assert_eq!("-e2".parse::<SciValue<i64,i32>>(), Err(error(ParseErrorKind::MissingDigits, 1)));
+    assert_eq!("1.5e2".parse::<SciValue<i64,i32>>(), Err(error(ParseErrorKind::InvalidDigit, 1)));
+    assert_eq!("5e".parse::<SciValue<i64,i32>>(), Err(error(ParseErrorKind::MissingDigits, 2)));
+    assert_eq!("5e2e3".parse::<SciValue<i64,i32>>(), Err(error(ParseErrorKind::InvalidDigit, 3)));
+    assert_eq!("1290e0".parse::<SciValue<i8,i8>>(), Err(error(ParseErrorKind::MantissaOverflow, 2)));
+    assert_eq!("-1e0".parse::<SciValue<u8,i8>>(), Err(error(ParseErrorKind::MantissaOverflow, 1)));
+    assert_eq!("1e-200".parse::<SciValue<i8,i8>>(), Err(error(ParseErrorKind::ExponentOverflow, 5)));
+  }
+
+  #[test]
+  fn limits(){
+    let limits = ParseLimits{max_mantissa_digits: 4, min_exponent: -10, max_exponent: 10};
+    assert_eq!(SciValue::<i64,i32>::from_str_with_limits("-1234e-10", &limits), Ok(SciValue::wrap_with_exponent(-1234, -10)));
+    assert_eq!(SciValue::<i64,i32>::from_str_with_limits("00001e2", &limits), Err(error(ParseErrorKind::TooManyDigits, 4)));
+    assert_eq!(SciValue::<i64,i32>::from_str_with_limits("-12345", &limits), Err(error(ParseErrorKind::TooManyDigits, 5)));
+    assert_eq!(SciValue::<i64,i32>::from_str_with_limits("5e11", &limits), Err(error(ParseErrorKind::ExponentOutOfBounds, 2)));
+    assert_eq!(SciValue::<i64,i32>::from_str_with_limits("5e-11", &limits), Err(error(ParseErrorKind::ExponentOutOfBounds, 2)));
+
+    let positive = ParseLimits{min_exponent: 1, ..ParseLimits::unlimited()};
+    assert_eq!(SciValue::<i64,i32>::from_str_with_limits("5", &positive), Err(error(ParseErrorKind::ExponentOutOfBounds, 1)));
+
+    //Exponents wider than an i64 are only rejected by a real limit
+    assert_eq!(SciValue::<i64,i128>::from_str_with_limits("1e100000000000000000000", &ParseLimits::unlimited()),
+               Ok(SciValue::wrap_with_exponent(1, 100000000000000000000)));
+    assert_eq!(SciValue::<i64,i128>::from_str_with_limits("1e-100000000000000000000", &ParseLimits::unlimited()),
+               Ok(SciValue::wrap_with_exponent(1, -100000000000000000000)));
+    assert_eq!(SciValue::<i64,i128>::from_str_with_limits("1e100000000000000000000", &limits),
+               Err(error(ParseErrorKind::ExponentOutOfBounds, 2)));
+  }
+
+  #[test]
+  fn normalized_parsing(){
+    assert_eq!(SciValue::<i64,i32>::from_str_normalized("12300e-1"), Ok(SciValue::wrap_with_exponent(123, 1)));
+    assert_eq!(SciValue::<i64,i32>::from_str_normalized("123e1"), Ok(SciValue::wrap_with_exponent(123, 1)));
+    assert_eq!(SciValue::<i64,i32>::from_str_normalized("-0e5"), Ok(SciValue::wrap(0)));
+    assert_eq!(SciValue::<i8,i8>::from_str_normalized("10e127"), Err(error(ParseErrorKind::ExponentOverflow, 3)));
+    assert_eq!(SciValue::<i8,i8>::from_str_normalized("1x"), Err(error(ParseErrorKind::InvalidDigit, 1)));
+  }
+
+  #[test]
+  fn rust_literals(){
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("1_000_000u64"), Ok(SciValue::wrap(1000000)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("2.5e3_f64"), Ok(SciValue::wrap_with_exponent(25, 2)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("0.000_125"), Ok(SciValue::wrap_with_exponent(125, -6)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("7."), Ok(SciValue::wrap(7)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("1E-3f32"), Ok(SciValue::wrap_with_exponent(1, -3)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("42"), Ok(SciValue::wrap(42)));
+
+    //Offsets are into the original literal
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("1_0x5"), Err(error(ParseErrorKind::InvalidDigit, 3)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("_1"), Err(error(ParseErrorKind::InvalidDigit, 0)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("1.2.3"), Err(error(ParseErrorKind::InvalidDigit, 3)));
+    assert_eq!(SciValue::<i8,i8>::from_rust_literal("1_2_8i8"), Err(error(ParseErrorKind::MantissaOverflow, 4)));
+    assert_eq!(SciValue::<i8,i8>::from_rust_literal("1.5e-128"), Err(error(ParseErrorKind::ExponentOverflow, 4)));
+    assert_eq!(SciValue::<i64,i32>::from_rust_literal("u8"), Err(error(ParseErrorKind::InvalidDigit, 0)));
+  }
+
+  #[test]
+  fn error_messages(){
+    assert_eq!(format!("{}", error(ParseErrorKind::InvalidDigit, 3)), "invalid digit at offset 3");
+    assert_eq!(format!("{}", error(ParseErrorKind::Empty, 0)), "cannot parse a value from an empty string");
+  }
+
+  #[test]
+  fn lenient_parsing(){
+    use super::ParseWarning::*;
+
+    assert_eq!(SciValue::<i64,i32>::parse_lenient("5e2"), (Some(SciValue::wrap_with_exponent(5, 2)), vec![]));
+    assert_eq!(SciValue::<i64,i32>::parse_lenient(" 3,14 "), (Some(SciValue::wrap_with_exponent(314, -2)), vec![TrimmedWhitespace, CommaDecimal(2)]));
+    assert_eq!(SciValue::<i64,i32>::parse_lenient("--12.5e-1kg"),
+               (Some(SciValue::wrap_with_exponent(-125, -2)), vec![RepeatedSign(0), TrailingGarbage(9)]));
+    assert_eq!(SciValue::<i64,i32>::parse_lenient(".5E"), (Some(SciValue::wrap_with_exponent(5, -1)), vec![TrailingGarbage(2)]));
+    assert_eq!(SciValue::<i64,i32>::parse_lenient("n/a"), (None, vec![TrailingGarbage(0)]));
+    assert_eq!(SciValue::<i8,i8>::parse_lenient("300"), (None, vec![]));
+    assert_eq!(format!("{}", CommaDecimal(4)), "read comma as decimal point at offset 4");
+  }
+
+  #[test]
+  fn num_radix(){
+    use num_traits::{Num, pow};
+
+    fn parse_num<T:Num>(text:&str, radix:u32) -> Option<T> {
+      T::from_str_radix(text, radix).ok()
+    }
+    assert_eq!(parse_num::<SciValue<i64,i32>>("-21e1", 10), Some(SciValue::wrap_with_exponent(-21, 1)));
+    assert_eq!(parse_num::<SciValue<i64,i32>>("ff", 16), Some(SciValue::wrap(255)));
+    assert_eq!(parse_num::<SciValue<i64,i32>>("-101", 2), Some(SciValue::wrap(-5)));
+    assert_eq!(SciValue::<i8,i8>::from_str_radix("80", 16), Err(error(ParseErrorKind::MantissaOverflow, 1)));
+    assert_eq!(SciValue::<i8,i8>::from_str_radix("12", 2), Err(error(ParseErrorKind::InvalidDigit, 1)));
+    assert_eq!(pow(SciValue::wrap_with_exponent(2i64, -1i32), 3), SciValue::wrap_with_exponent(8, -3));
+  }
+}
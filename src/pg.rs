@@ -0,0 +1,228 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Display, Formatter, Error};
+use std::error::Error as StdError;
+use std::iter;
+
+use super::{SciValue, base_constant, decimal_digits};
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_DSCALE_MASK: u16 = 0x3FFF;
+
+/**
+ * Error converting between a `SciValue` and PostgreSQL's
+ * binary NUMERIC format.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgNumericError {
+  /// The input ended before the encoded value did.
+  Truncated,
+  /// The input was the NUMERIC NaN, which has no `SciValue` equivalent.
+  NaN,
+  /// The sign field held an unknown value.
+  InvalidSign(u16),
+  /// A base-10000 digit was out of range.
+  InvalidDigit(u16),
+  /// The value doesn't fit in the target types.
+  OutOfRange
+}
+
+impl Display for PgNumericError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match *self {
+      PgNumericError::Truncated           => fmtr.write_str("NUMERIC value is truncated"),
+      PgNumericError::NaN                 => fmtr.write_str("NUMERIC NaN can't be represented"),
+      PgNumericError::InvalidSign(sign)   => write!(fmtr, "invalid NUMERIC sign {:x}", sign),
+      PgNumericError::InvalidDigit(digit) => write!(fmtr, "invalid NUMERIC digit {}", digit),
+      PgNumericError::OutOfRange          => fmtr.write_str("NUMERIC value out of range")
+    }
+  }
+}
+
+impl StdError for PgNumericError {}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Encodes the value in PostgreSQL's binary NUMERIC
+   * wire format: a header of digit count, weight, sign
+   * and display scale, followed by base-10000 digits, all
+   * big-endian.  The display scale is the number of digits
+   * after the decimal point in the stored form.
+   */
+  pub fn to_pg_numeric(&self) -> Result<Vec<u8>, PgNumericError> {
+    let negative = self.base < B::zero();
+    let exp = self.e_exp.to_i64().ok_or(PgNumericError::OutOfRange)?;
+
+    let dscale = if exp < 0 { exp.checked_neg().ok_or(PgNumericError::OutOfRange)? } else { 0 };
+    if dscale > NUMERIC_DSCALE_MASK as i64 {
+      return Err(PgNumericError::OutOfRange);
+    }
+
+    let mut digits = decimal_digits(self.base);
+    if digits.is_empty() {
+      return Ok(encode(&[], 0, NUMERIC_POS, dscale as u16));
+    }
+
+    //Pad with zeros so that the exponent is a multiple of
+    //four and the digits split evenly into base-10000 groups
+    let group_exp = if exp >= 0 { exp / 4 } else { -(3i64.checked_sub(exp).ok_or(PgNumericError::OutOfRange)? / 4) };
+    digits.extend(iter::repeat(0u8).take((exp - group_exp * 4) as usize));
+    let mut padded: Vec<u8> = iter::repeat(0u8).take((4 - digits.len() % 4) % 4).collect();
+    padded.extend(digits);
+
+    let mut groups: Vec<u16> = padded.chunks(4).map(|chunk| {
+      chunk.iter().fold(0u16, |group, digit| group * 10 + *digit as u16)
+    }).collect();
+    let weight = group_exp + groups.len() as i64 - 1;
+    while groups.last() == Some(&0) {
+      groups.pop();
+    }
+
+    if weight < i16::MIN as i64 || weight > i16::MAX as i64 {
+      return Err(PgNumericError::OutOfRange);
+    }
+    let sign = if negative { NUMERIC_NEG } else { NUMERIC_POS };
+    Ok(encode(groups.as_slice(), weight as i16, sign, dscale as u16))
+  }
+
+  /**
+   * Decodes a value from PostgreSQL's binary NUMERIC wire
+   * format.  Where possible the result keeps the display
+   * scale of the input, so `123.45` decodes as `12345e-2`.
+   */
+  pub fn from_pg_numeric(bytes:&[u8]) -> Result<SciValue<B,E>, PgNumericError> {
+    if bytes.len() < 8 {
+      return Err(PgNumericError::Truncated);
+    }
+    let ndigits = read_u16(bytes, 0) as usize;
+    let weight  = read_u16(bytes, 2) as i16 as i64;
+    let sign    = read_u16(bytes, 4);
+    let dscale  = (read_u16(bytes, 6) & NUMERIC_DSCALE_MASK) as i64;
+
+    if sign == NUMERIC_NAN {
+      return Err(PgNumericError::NaN);
+    }
+    if sign != NUMERIC_POS && sign != NUMERIC_NEG {
+      return Err(PgNumericError::InvalidSign(sign));
+    }
+    if bytes.len() < 8 + 2 * ndigits {
+      return Err(PgNumericError::Truncated);
+    }
+
+    let type_b_10 = base_constant::<B>(10, "from_pg_numeric");
+    let mut base = B::zero();
+    for idx in 0..ndigits {
+      let group = read_u16(bytes, 8 + 2 * idx);
+      if group > 9999 {
+        return Err(PgNumericError::InvalidDigit(group));
+      }
+      for place in [1000u16, 100, 10, 1].iter() {
+        let digit = base_constant::<B>(((group / *place) % 10) as i64, "from_pg_numeric");
+        let next  = base.checked_mul(&type_b_10).and_then(|scaled| {
+          if sign == NUMERIC_NEG { scaled.checked_sub(&digit) } else { scaled.checked_add(&digit) }
+        });
+        base = next.ok_or(PgNumericError::OutOfRange)?;
+      }
+    }
+
+    let exp = if ndigits == 0 { 0 } else { (weight - (ndigits as i64 - 1)) * 4 };
+    let decoded = SciValue::wrap_with_exponent(base, <E as FromPrimitive>::from_i64(exp).ok_or(PgNumericError::OutOfRange)?);
+    match <E as FromPrimitive>::from_i64(-dscale) {
+      Some(scale_exp) => Ok(decoded.rebalance_to_exponent(scale_exp).unwrap_or(decoded)),
+      None            => Ok(decoded)
+    }
+  }
+}
+
+fn encode(groups:&[u16], weight:i16, sign:u16, dscale:u16) -> Vec<u8> {
+  let mut bytes = Vec::with_capacity(8 + 2 * groups.len());
+  for field in [groups.len() as u16, weight as u16, sign, dscale].iter().chain(groups.iter()) {
+    bytes.push((*field >> 8) as u8);
+    bytes.push(*field as u8);
+  }
+  bytes
+}
+
+fn read_u16(bytes:&[u8], offset:usize) -> u16 {
+  ((bytes[offset] as u16) << 8) | bytes[offset + 1] as u16
+}
+
+#[cfg(test)]
+mod test{
+  use super::PgNumericError;
+  use super::super::SciValue;
+
+  #[test]
+  fn encode_fraction(){
+    //123.45: one digit group before the point, one after
+    assert_eq!(SciValue::wrap_with_exponent(12345i64, -2isize).to_pg_numeric(),
+               Ok(vec![0, 2, 0, 0, 0, 0, 0, 2, 0, 123, 0x11, 0x94]));
+  }
+
+  #[test]
+  fn encode_large_and_negative(){
+    //-5000000 = -500 * 10000^1
+    assert_eq!(SciValue::wrap_with_exponent(-5i64, 6isize).to_pg_numeric(),
+               Ok(vec![0, 1, 0, 1, 0x40, 0, 0, 0, 0x01, 0xF4]));
+    assert_eq!(SciValue::wrap_with_exponent(0i64, -3isize).to_pg_numeric(),
+               Ok(vec![0, 0, 0, 0, 0, 0, 0, 3]));
+  }
+
+  #[test]
+  fn encode_out_of_range(){
+    assert_eq!(SciValue::wrap_with_exponent(5i64, i64::MIN).to_pg_numeric(), Err(PgNumericError::OutOfRange));
+    assert_eq!(SciValue::wrap_with_exponent(0i64, i64::MIN).to_pg_numeric(), Err(PgNumericError::OutOfRange));
+    assert_eq!(SciValue::wrap_with_exponent(5i64, i64::MAX).to_pg_numeric(), Err(PgNumericError::OutOfRange));
+  }
+
+  #[test]
+  fn round_trip(){
+    let values = vec![SciValue::wrap_with_exponent(12345i64, -2isize),
+                      SciValue::wrap_with_exponent(-987654321i64, -7isize),
+                      SciValue::wrap_with_exponent(42i64, 9isize),
+                      SciValue::wrap_with_exponent(1i64, -30isize)];
+    for val in values.into_iter() {
+      let encoded = val.to_pg_numeric().unwrap();
+      assert_eq!(SciValue::from_pg_numeric(encoded.as_slice()).map(|decoded: SciValue<i64,isize>| decoded.reduce()), Ok(val.reduce()));
+    }
+  }
+
+  #[test]
+  fn decode_keeps_scale(){
+    let decoded: SciValue<i64,isize> = SciValue::from_pg_numeric(&[0, 2, 0, 0, 0, 0, 0, 2, 0, 123, 0x11, 0x94]).unwrap();
+    assert_eq!(decoded, SciValue::wrap_with_exponent(12345, -2isize));
+  }
+
+  #[test]
+  fn decode_errors(){
+    let nan: Result<SciValue<i64,isize>, PgNumericError> = SciValue::from_pg_numeric(&[0, 0, 0, 0, 0xC0, 0, 0, 0]);
+    assert_eq!(nan, Err(PgNumericError::NaN));
+    let short: Result<SciValue<i64,isize>, PgNumericError> = SciValue::from_pg_numeric(&[0, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(short, Err(PgNumericError::Truncated));
+    let overflow: Result<SciValue<i8,isize>, PgNumericError> = SciValue::from_pg_numeric(&[0, 1, 0, 0, 0, 0, 0, 0, 0x03, 0xE8]);
+    assert_eq!(overflow, Err(PgNumericError::OutOfRange));
+  }
+}
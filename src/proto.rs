@@ -0,0 +1,405 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! A stable mapping of `SciValue`s onto fields which any
+//! serialization format can carry, intended for protobuf:
+//!
+//! ```text
+//! message SciValue {
+//!   bool   negative  = 1;
+//!   bytes  magnitude = 2;
+//!   sint64 exponent  = 3;
+//! }
+//! ```
+//!
+//! `magnitude` holds the absolute value of the base as a
+//! big-endian unsigned integer with no leading zero bytes,
+//! so zero is the empty string.  The value represented is
+//! `(-1 if negative) * magnitude * 10^exponent`.
+//!
+//! `SignMagnitude` has the same fields as the struct prost
+//! generates for this message, and converts to and from
+//! `SciValue` with `TryFrom`.  With the `protobuf` feature
+//! it can also be written and read in the protobuf wire
+//! format directly, without a protobuf dependency.
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+#[cfg(feature = "protobuf")]
+use std::fmt::{Display, Formatter, Error};
+#[cfg(feature = "protobuf")]
+use std::error::Error as StdError;
+
+use super::{SciValue, PrecisionLoss, base_constant};
+
+#[cfg(feature = "protobuf")]
+const WIRE_VARINT: u64 = 0;
+#[cfg(feature = "protobuf")]
+const WIRE_FIXED64: u64 = 1;
+#[cfg(feature = "protobuf")]
+const WIRE_LEN: u64 = 2;
+#[cfg(feature = "protobuf")]
+const WIRE_FIXED32: u64 = 5;
+
+/**
+ * The parts of a value in the wire mapping.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignMagnitude {
+  pub negative: bool,
+  pub magnitude: Vec<u8>,
+  pub exponent: i64
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Splits the value into the sign, magnitude and
+   * exponent fields of the wire mapping.  Returns None
+   * only if the exponent doesn't fit in an i64.
+   */
+  pub fn to_sign_magnitude(&self) -> Option<SignMagnitude> {
+    let type_b_0 = B::zero();
+    let type_b_16 = base_constant::<B>(16, "to_sign_magnitude");
+
+    //Work in nibbles, as 256 doesn't fit in every base type
+    let mut nibbles = Vec::new();
+    let mut remaining = self.base;
+    while remaining != type_b_0 {
+      let nibble = remaining % type_b_16;
+      let magnitude = if nibble < type_b_0 { type_b_0 - nibble } else { nibble };
+      nibbles.push(magnitude.to_u8().expect("Couldn't convert a nibble to u8"));
+      remaining = remaining / type_b_16;
+    }
+    if nibbles.len() % 2 == 1 {
+      nibbles.push(0);
+    }
+
+    let mut magnitude: Vec<u8> = nibbles.chunks(2).map(|pair| pair[1] << 4 | pair[0]).collect();
+    magnitude.reverse();
+
+    self.e_exp.to_i64().map(|exponent| {
+      SignMagnitude{negative: self.base < type_b_0, magnitude, exponent}
+    })
+  }
+
+  /**
+   * A deterministic byte key for the numeric value, for
+   * deduplication and hashing across machines.  Equal
+   * values give equal keys whatever their base type,
+   * exponent type or split between base and exponent.
+   *
+   * The key is a sign byte (0 for zero, 1 for positive,
+   * 0xFF for negative), the reduced exponent as an 8 byte
+   * big-endian integer, and then the reduced magnitude
+   * as in the wire mapping.  A reduced exponent outside
+   * the range of an i64 is written in 16 bytes instead,
+   * marked by a sign byte of 2 for positive or 0xFE for
+   * negative values.
+   */
+  pub fn canonical_bytes(&self) -> Vec<u8> {
+    let reduced = self.reduce();
+    let magnitude = SciValue::<B,E>::wrap(reduced.base).to_sign_magnitude()
+                      .expect("A zero exponent always fits in an i64").magnitude;
+    let negative = reduced.base < B::zero();
+
+    let mut bytes = Vec::with_capacity(17 + magnitude.len());
+    match reduced.e_exp.to_i64() {
+      Some(exponent) => {
+        bytes.push(if magnitude.is_empty() { 0x00 } else if negative { 0xFF } else { 0x01 });
+        bytes.extend(exponent.to_be_bytes().iter());
+      },
+      None           => {
+        //Reduced zero always has exponent 0, so only nonzero
+        //values get here
+        let exponent = reduced.e_exp.to_i128().expect("Couldn't convert exponent to i128");
+        bytes.push(if negative { 0xFE } else { 0x02 });
+        bytes.extend(exponent.to_be_bytes().iter());
+      }
+    }
+    bytes.extend(magnitude);
+    bytes
+  }
+
+  /**
+   * Rebuilds a value from the fields of the wire mapping.
+   * Leading zero bytes in the magnitude are accepted.
+   * Returns None if the value doesn't fit in the base and
+   * exponent types.
+   */
+  pub fn from_sign_magnitude(parts:&SignMagnitude) -> Option<SciValue<B,E>> {
+    let type_b_16 = base_constant::<B>(16, "from_sign_magnitude");
+
+    let mut base = B::zero();
+    for byte in parts.magnitude.iter() {
+      for nibble in [*byte >> 4, *byte & 0x0F].iter() {
+        let nibble_val = base_constant::<B>(*nibble as i64, "from_sign_magnitude");
+        let next = base.checked_mul(&type_b_16).and_then(|scaled| {
+          if parts.negative { scaled.checked_sub(&nibble_val) } else { scaled.checked_add(&nibble_val) }
+        });
+        base = next?;
+      }
+    }
+
+    <E as FromPrimitive>::from_i64(parts.exponent).map(|exp| SciValue::wrap_with_exponent(base, exp))
+  }
+}
+
+/**
+ * Fails with `Overflow` if the exponent doesn't fit
+ * in an i64.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> TryFrom<SciValue<B,E>> for SignMagnitude {
+  type Error = PrecisionLoss;
+
+  fn try_from(val:SciValue<B,E>) -> Result<SignMagnitude, PrecisionLoss> {
+    val.to_sign_magnitude().ok_or(PrecisionLoss::Overflow)
+  }
+}
+
+/**
+ * Fails with `Overflow` if the value doesn't fit in the
+ * base and exponent types.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> TryFrom<SignMagnitude> for SciValue<B,E> {
+  type Error = PrecisionLoss;
+
+  fn try_from(parts:SignMagnitude) -> Result<SciValue<B,E>, PrecisionLoss> {
+    SciValue::from_sign_magnitude(&parts).ok_or(PrecisionLoss::Overflow)
+  }
+}
+
+/**
+ * Error decoding a message from the protobuf wire format.
+ */
+#[cfg(feature = "protobuf")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtoDecodeError {
+  /// The input ended inside a field.
+  Truncated,
+  /// A varint ran past ten bytes.
+  InvalidVarint,
+  /// A field used a wire type protobuf doesn't define.
+  UnknownWireType(u8),
+  /// A known field was sent with the wrong wire type.
+  WrongWireType(u32)
+}
+
+#[cfg(feature = "protobuf")]
+impl Display for ProtoDecodeError {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    match *self {
+      ProtoDecodeError::Truncated             => fmtr.write_str("protobuf message is truncated"),
+      ProtoDecodeError::InvalidVarint         => fmtr.write_str("protobuf varint is too long"),
+      ProtoDecodeError::UnknownWireType(wire) => write!(fmtr, "unknown protobuf wire type {}", wire),
+      ProtoDecodeError::WrongWireType(field)  => write!(fmtr, "field {} has the wrong wire type", field)
+    }
+  }
+}
+
+#[cfg(feature = "protobuf")]
+impl StdError for ProtoDecodeError {}
+
+#[cfg(feature = "protobuf")]
+impl SignMagnitude {
+  /**
+   * Writes the message in the protobuf wire format.  As in
+   * proto3, fields holding their default value are left out.
+   */
+  pub fn encode_to_vec(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(self.magnitude.len() + 14);
+    if self.negative {
+      push_varint(&mut bytes, 1 << 3 | WIRE_VARINT);
+      push_varint(&mut bytes, 1);
+    }
+    if !self.magnitude.is_empty() {
+      push_varint(&mut bytes, 2 << 3 | WIRE_LEN);
+      push_varint(&mut bytes, self.magnitude.len() as u64);
+      bytes.extend_from_slice(&self.magnitude);
+    }
+    if self.exponent != 0 {
+      push_varint(&mut bytes, 3 << 3 | WIRE_VARINT);
+      push_varint(&mut bytes, ((self.exponent << 1) ^ (self.exponent >> 63)) as u64);
+    }
+    bytes
+  }
+
+  /**
+   * Reads a message in the protobuf wire format.  Unknown
+   * fields are skipped, and a repeated field keeps its
+   * last value, as protobuf parsers do.
+   */
+  pub fn decode(mut bytes:&[u8]) -> Result<SignMagnitude, ProtoDecodeError> {
+    let mut parts = SignMagnitude{negative: false, magnitude: Vec::new(), exponent: 0};
+    while !bytes.is_empty() {
+      let key = read_varint(&mut bytes)?;
+      let field = (key >> 3) as u32;
+      match (field, key & 0x07) {
+        (1, WIRE_VARINT) => parts.negative = read_varint(&mut bytes)? != 0,
+        (2, WIRE_LEN)    => parts.magnitude = read_len(&mut bytes)?.to_vec(),
+        (3, WIRE_VARINT) => {
+          let zigzag = read_varint(&mut bytes)?;
+          parts.exponent = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+        },
+        (1..=3, _)       => return Err(ProtoDecodeError::WrongWireType(field)),
+        (_, wire)        => skip_field(&mut bytes, wire)?
+      }
+    }
+    Ok(parts)
+  }
+}
+
+#[cfg(feature = "protobuf")]
+fn push_varint(bytes:&mut Vec<u8>, mut val:u64) {
+  while val >= 0x80 {
+    bytes.push((val as u8) | 0x80);
+    val >>= 7;
+  }
+  bytes.push(val as u8);
+}
+
+#[cfg(feature = "protobuf")]
+fn read_varint(bytes:&mut &[u8]) -> Result<u64, ProtoDecodeError> {
+  let mut val = 0u64;
+  for idx in 0..10 {
+    let (byte, rest) = bytes.split_first().ok_or(ProtoDecodeError::Truncated)?;
+    *bytes = rest;
+    val |= ((*byte & 0x7F) as u64) << (7 * idx);
+    if *byte & 0x80 == 0 {
+      return Ok(val);
+    }
+  }
+  Err(ProtoDecodeError::InvalidVarint)
+}
+
+#[cfg(feature = "protobuf")]
+fn read_len<'a>(bytes:&mut &'a [u8]) -> Result<&'a [u8], ProtoDecodeError> {
+  let len = read_varint(bytes)?;
+  take(bytes, usize::try_from(len).unwrap_or(usize::MAX))
+}
+
+#[cfg(feature = "protobuf")]
+fn take<'a>(bytes:&mut &'a [u8], len:usize) -> Result<&'a [u8], ProtoDecodeError> {
+  if bytes.len() < len {
+    return Err(ProtoDecodeError::Truncated);
+  }
+  let (field, rest) = bytes.split_at(len);
+  *bytes = rest;
+  Ok(field)
+}
+
+#[cfg(feature = "protobuf")]
+fn skip_field(bytes:&mut &[u8], wire:u64) -> Result<(), ProtoDecodeError> {
+  match wire {
+    WIRE_VARINT  => read_varint(bytes).map(|_| ()),
+    WIRE_FIXED64 => take(bytes, 8).map(|_| ()),
+    WIRE_LEN     => read_len(bytes).map(|_| ()),
+    WIRE_FIXED32 => take(bytes, 4).map(|_| ()),
+    wire         => Err(ProtoDecodeError::UnknownWireType(wire as u8))
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::SignMagnitude;
+  use super::super::{SciValue, PrecisionLoss};
+
+  #[test]
+  fn to_parts(){
+    assert_eq!(SciValue::wrap_with_exponent(-300i32, -2isize).to_sign_magnitude(),
+               Some(SignMagnitude{negative: true, magnitude: vec![0x01, 0x2C], exponent: -2}));
+    assert_eq!(SciValue::wrap_with_exponent(0i32, 5isize).to_sign_magnitude(),
+               Some(SignMagnitude{negative: false, magnitude: vec![], exponent: 5}));
+    assert_eq!(SciValue::wrap_with_exponent(-128i8, 0isize).to_sign_magnitude(),
+               Some(SignMagnitude{negative: true, magnitude: vec![0x80], exponent: 0}));
+  }
+
+  #[test]
+  fn round_trip(){
+    for val in vec![SciValue::wrap_with_exponent(-300i32, -2isize),
+                    SciValue::wrap_with_exponent(2147483647i32, 40isize),
+                    SciValue::wrap_with_exponent(0i32, 0isize)].into_iter() {
+      let parts = val.to_sign_magnitude().unwrap();
+      assert_eq!(SciValue::from_sign_magnitude(&parts), Some(val));
+    }
+  }
+
+  #[test]
+  fn canonical_keys(){
+    let key = SciValue::wrap_with_exponent(20i32, 2isize).canonical_bytes();
+    assert_eq!(key, vec![0x01, 0, 0, 0, 0, 0, 0, 0, 3, 0x02]);
+    assert_eq!(SciValue::wrap_with_exponent(2i64, 3i8).canonical_bytes(), key);
+
+    assert_eq!(SciValue::wrap_with_exponent(-300i32, -4isize).canonical_bytes(),
+               vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, 0x03]);
+    assert_eq!(SciValue::wrap_with_exponent(0i32, 7isize).canonical_bytes(), SciValue::wrap_with_exponent(0i8, -2i8).canonical_bytes());
+
+    //Exponents beyond an i64 get the wide form rather than a panic
+    let wide = SciValue::wrap_with_exponent(50i64, 1i128 << 80).canonical_bytes();
+    let mut expected = vec![0x02, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0x05];
+    assert_eq!(wide, expected);
+    expected[0] = 0xFE;
+    assert_eq!(SciValue::wrap_with_exponent(-5i8, (1i128 << 80) + 1).canonical_bytes(), expected);
+  }
+
+  #[test]
+  fn out_of_range(){
+    let too_big = SignMagnitude{negative: false, magnitude: vec![0x01, 0x00], exponent: 0};
+    let res: Option<SciValue<i8,isize>> = SciValue::from_sign_magnitude(&too_big);
+    assert_eq!(res, None);
+
+    let min = SignMagnitude{negative: true, magnitude: vec![0x00, 0x80], exponent: 0};
+    assert_eq!(SciValue::from_sign_magnitude(&min), Some(SciValue::wrap_with_exponent(-128i8, 0isize)));
+  }
+
+  #[test]
+  fn conversions(){
+    let parts = SignMagnitude::try_from(SciValue::wrap_with_exponent(-300i32, -2i8)).unwrap();
+    assert_eq!(parts, SignMagnitude{negative: true, magnitude: vec![0x01, 0x2C], exponent: -2});
+    assert_eq!(SciValue::try_from(parts.clone()), Ok(SciValue::wrap_with_exponent(-300i32, -2i8)));
+
+    let res: Result<SciValue<i8,i8>, PrecisionLoss> = SciValue::try_from(parts);
+    assert_eq!(res, Err(PrecisionLoss::Overflow));
+    assert_eq!(SignMagnitude::try_from(SciValue::wrap_with_exponent(1i8, i128::MAX)), Err(PrecisionLoss::Overflow));
+  }
+
+  #[cfg(feature = "protobuf")]
+  #[test]
+  fn protobuf_wire_format(){
+    use super::ProtoDecodeError;
+
+    let parts = SignMagnitude{negative: true, magnitude: vec![0x01, 0x2C], exponent: -2};
+    let encoded = parts.encode_to_vec();
+    assert_eq!(encoded, vec![0x08, 0x01, 0x12, 0x02, 0x01, 0x2C, 0x18, 0x03]);
+    assert_eq!(SignMagnitude::decode(encoded.as_slice()), Ok(parts));
+
+    let zero = SignMagnitude{negative: false, magnitude: vec![], exponent: 0};
+    assert_eq!(zero.encode_to_vec(), Vec::<u8>::new());
+    assert_eq!(SignMagnitude::decode(&[]), Ok(zero));
+
+    //Unknown fields of every wire type are skipped
+    let extended = [0x18, 0x04, 0x20, 0x96, 0x01, 0x29, 0, 0, 0, 0, 0, 0, 0, 0, 0x32, 0x01, 0xFF, 0x3D, 0, 0, 0, 0, 0x12, 0x01, 0x07];
+    assert_eq!(SignMagnitude::decode(&extended), Ok(SignMagnitude{negative: false, magnitude: vec![0x07], exponent: 2}));
+
+    assert_eq!(SignMagnitude::decode(&[0x12, 0x03, 0x01]), Err(ProtoDecodeError::Truncated));
+    assert_eq!(SignMagnitude::decode(&[0x1A, 0x00]), Err(ProtoDecodeError::WrongWireType(3)));
+    assert_eq!(SignMagnitude::decode(&[0x23]), Err(ProtoDecodeError::UnknownWireType(3)));
+  }
+}
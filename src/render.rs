@@ -0,0 +1,280 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error, Write};
+
+use super::{SciValue, base_constant, decimal_digits, saturating_exp_to_i64};
+
+/**
+ * A custom notation for writing values out, for house
+ * styles the crate doesn't provide itself.
+ *
+ * Renderers are given the value's sign, the decimal
+ * digits of its reduced base (most significant first,
+ * and a single 0 for zero), and the exponent applying
+ * to the last of those digits, as in the value itself.
+ * Closures with the same signature are renderers too.
+ */
+pub trait NotationRenderer {
+  fn render(&self, negative:bool, digits:&[u8], exponent:i64) -> String;
+}
+
+impl<F:Fn(bool, &[u8], i64) -> String> NotationRenderer for F {
+  fn render(&self, negative:bool, digits:&[u8], exponent:i64) -> String {
+    self(negative, digits, exponent)
+  }
+}
+
+/**
+ * What separates the digits from the exponent in the
+ * standard notation, for the conventions of whatever
+ * reads the output.  Serialized by name in snake case,
+ * as `RoundingMode` is.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ExponentMarker {
+  /// `15e-3`, as Rust and most languages write it.
+  Lower,
+  /// `15E-3`, as spreadsheets and older C tools write it.
+  Upper,
+  /// `15×10^-3`, for plotting packages and reports.
+  TimesTenPower,
+  /// `15⏨-3`, the Algol decimal exponent symbol.
+  DecimalExponent,
+  /// `15D-3`, Fortran's double precision exponent.
+  Fortran
+}
+
+impl ExponentMarker {
+  /// The marker as written.
+  pub fn as_str(&self) -> &'static str {
+    match *self {
+      ExponentMarker::Lower           => "e",
+      ExponentMarker::Upper           => "E",
+      ExponentMarker::TimesTenPower   => "\u{d7}10^",
+      ExponentMarker::DecimalExponent => "\u{23e8}",
+      ExponentMarker::Fortran         => "D"
+    }
+  }
+}
+
+/**
+ * How `SciValue::format_with` writes values out.
+ *
+ * With the `serde` feature the options can be read from
+ * configuration files.  A renderer is code, so it's never
+ * serialized and always deserializes as None, and missing
+ * fields take their defaults.
+ */
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FormatOptions {
+  /// The notation to use, or None for the standard
+  /// e-notation of the reduced value, such as `-15e2`.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub renderer: Option<Box<dyn NotationRenderer>>,
+  /// The marker the standard notation writes before the
+  /// exponent.  Custom renderers pick their own.
+  pub exponent_marker: ExponentMarker
+}
+
+impl FormatOptions {
+  /// The standard notation.
+  pub fn new() -> FormatOptions {
+    FormatOptions{renderer: None, exponent_marker: ExponentMarker::Lower}
+  }
+
+  /// Writes the standard notation with a different exponent marker.
+  pub fn with_exponent_marker(mut self, marker:ExponentMarker) -> FormatOptions {
+    self.exponent_marker = marker;
+    self
+  }
+
+  /// Registers a custom notation in place of the standard one.
+  pub fn with_renderer<R:NotationRenderer + 'static>(mut self, renderer:R) -> FormatOptions {
+    self.renderer = Some(Box::new(renderer));
+    self
+  }
+
+  /**
+   * Writes the values out as `format_with` does, joined by
+   * `sep`, for CSV fields and log lines.  The standard
+   * notation is written straight into the one string
+   * returned, rather than into a string per value; custom
+   * renderers still return their own.
+   */
+  pub fn format_slice<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(&self, values:&[SciValue<B,E>], sep:&str) -> String {
+    let mut out = String::new();
+    for (idx, val) in values.iter().enumerate() {
+      if idx > 0 {
+        out.push_str(sep);
+      }
+      match self.renderer {
+        Some(ref renderer) => out.push_str(&val.render_with(renderer.as_ref())),
+        None               => write_standard(&mut out, val, self.exponent_marker)
+      }
+    }
+    out
+  }
+}
+
+impl Default for FormatOptions {
+  fn default() -> FormatOptions {
+    FormatOptions::new()
+  }
+}
+
+impl Debug for FormatOptions {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    let renderer = if self.renderer.is_some() { "Some(..)" } else { "None" };
+    write!(fmtr, "FormatOptions{{renderer : {}, exponent_marker : {:?}}}", renderer, self.exponent_marker)
+  }
+}
+
+/**
+ * Appends the standard e-notation of the reduced value,
+ * working out its digits on the stack.
+ */
+fn write_standard<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(out:&mut String, val:&SciValue<B,E>, marker:ExponentMarker) {
+  let type_b_10 = base_constant::<B>(10, "write_standard");
+  let reduced = val.reduce();
+  if reduced.base < B::zero() {
+    out.push('-');
+  }
+
+  //39 digits hold the magnitude of any primitive base
+  let mut digits = [0u8; 39];
+  let mut len = 0;
+  let mut remaining = reduced.base;
+  loop {
+    let digit = (remaining % type_b_10).to_i8().expect("Couldn't convert a digit to i8");
+    digits[len] = b'0' + digit.unsigned_abs();
+    len += 1;
+    remaining = remaining / type_b_10;
+    if remaining == B::zero() {
+      break;
+    }
+  }
+  out.extend(digits[..len].iter().rev().map(|&digit| digit as char));
+  out.push_str(marker.as_str());
+  write!(out, "{}", saturating_exp_to_i64(reduced.e_exp)).expect("Couldn't write to a String");
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> SciValue<B,E> {
+  /**
+   * Writes the value out using the notation registered in
+   * the options.
+   */
+  pub fn format_with(&self, options:&FormatOptions) -> String {
+    match options.renderer {
+      Some(ref renderer) => self.render_with(renderer.as_ref()),
+      None               => {
+        let mut out = String::new();
+        write_standard(&mut out, self, options.exponent_marker);
+        out
+      }
+    }
+  }
+
+  /**
+   * Writes the value out using a custom notation.
+   */
+  pub fn render_with<R:NotationRenderer + ?Sized>(&self, renderer:&R) -> String {
+    let reduced = self.reduce();
+    let mut digits = decimal_digits(reduced.base);
+    if digits.is_empty() {
+      digits.push(0);
+    }
+    renderer.render(reduced.base < B::zero(), digits.as_slice(), saturating_exp_to_i64(reduced.e_exp))
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::{NotationRenderer, FormatOptions, ExponentMarker};
+  use super::super::SciValue;
+
+  /// Normalized notation with the point after the first digit.
+  struct Normalized;
+
+  impl NotationRenderer for Normalized {
+    fn render(&self, negative:bool, digits:&[u8], exponent:i64) -> String {
+      let text: String = digits.iter().map(|digit| (b'0' + digit) as char).collect();
+      let (lead, rest) = text.split_at(1);
+      let point = if rest.is_empty() { "" } else { "." };
+      format!("{}{}{}{}x10^{}", if negative { "-" } else { "" }, lead, point, rest, exponent + rest.len() as i64)
+    }
+  }
+
+  #[test]
+  fn custom_renderers(){
+    assert_eq!(SciValue::wrap_with_exponent(-1500i32, -5isize).render_with(&Normalized), "-1.5x10^-2");
+    assert_eq!(SciValue::wrap_with_exponent(0i32, 7isize).render_with(&Normalized), "0x10^0");
+
+    //Quoted so spreadsheets don't reinterpret the value
+    let spreadsheet = |negative:bool, digits:&[u8], exponent:i64| {
+      let text: String = digits.iter().map(|digit| (b'0' + digit) as char).collect();
+      format!("'{}{}E{}", if negative { "-" } else { "" }, text, exponent)
+    };
+    assert_eq!(SciValue::wrap_with_exponent(1230u32, 4isize).render_with(&spreadsheet), "'123E5");
+  }
+
+  #[test]
+  fn registered_renderers(){
+    let val = SciValue::wrap_with_exponent(-1500i32, -5isize);
+    assert_eq!(val.format_with(&FormatOptions::new()), "-15e-3");
+    assert_eq!(val.format_with(&FormatOptions::new().with_renderer(Normalized)), "-1.5x10^-2");
+
+    let options = FormatOptions::default().with_renderer(|_:bool, digits:&[u8], _:i64| format!("{} digits", digits.len()));
+    assert_eq!(val.format_with(&options), "2 digits");
+    assert_eq!(format!("{:?}", options), "FormatOptions{renderer : Some(..), exponent_marker : Lower}");
+  }
+
+  #[test]
+  fn exponent_markers(){
+    let val = SciValue::wrap_with_exponent(-1500i32, -5isize);
+    let marked = |marker| val.format_with(&FormatOptions::new().with_exponent_marker(marker));
+    assert_eq!(marked(ExponentMarker::Lower), "-15e-3");
+    assert_eq!(marked(ExponentMarker::Upper), "-15E-3");
+    assert_eq!(marked(ExponentMarker::TimesTenPower), "-15\u{d7}10^-3");
+    assert_eq!(marked(ExponentMarker::DecimalExponent), "-15\u{23e8}-3");
+    assert_eq!(marked(ExponentMarker::Fortran), "-15D-3");
+
+    //Custom renderers aren't affected
+    let options = FormatOptions::new().with_renderer(Normalized).with_exponent_marker(ExponentMarker::Fortran);
+    assert_eq!(val.format_with(&options), "-1.5x10^-2");
+    assert_eq!(FormatOptions::new().with_exponent_marker(ExponentMarker::Upper).format_slice(&[val, val], ";"), "-15E-3;-15E-3");
+  }
+
+  #[test]
+  fn formatted_slices(){
+    let values = [SciValue::wrap_with_exponent(-1500i32, -5isize), SciValue::wrap(0), SciValue::wrap(i32::MIN)];
+    assert_eq!(FormatOptions::new().format_slice(&values, ","), "-15e-3,0e0,-2147483648e0");
+    assert_eq!(FormatOptions::new().with_renderer(Normalized).format_slice(&values[..2], " | "), "-1.5x10^-2 | 0x10^0");
+    assert_eq!(FormatOptions::new().format_slice::<i32,isize>(&[], ","), "");
+    assert_eq!(FormatOptions::new().format_slice(&[SciValue::wrap_with_exponent(u128::MAX, 1i8)], ","),
+               format!("{}e1", u128::MAX));
+  }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+
+use super::{SciValue, FloatConversion, decimal_digits, saturating_exp_to_i64};
+
+/**
+ * A storage type a value might be converted to.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetType {
+  /// IEEE 754 single precision.
+  F32,
+  /// IEEE 754 double precision.
+  F64,
+  /// IEEE 754 decimal64, with 16 digit coefficients.
+  Decimal64,
+  /// A 64 bit signed integer.
+  I64
+}
+
+impl TargetType {
+  /**
+   * Significant digits the type always preserves, along
+   * with the range of leading digit exponents it can
+   * represent without clamping.  For the binary floating
+   * point types these are the guaranteed round trip
+   * digits and normal exponents, as in C's `FLT_DIG`.
+   */
+  fn limits(&self) -> (usize, i64, i64) {
+    match *self {
+      TargetType::F32       => (6, -37, 37),
+      TargetType::F64       => (15, -307, 307),
+      TargetType::Decimal64 => (16, -383, 384),
+      TargetType::I64       => (19, 0, 18)
+    }
+  }
+}
+
+/**
+ * What converting a value to a `TargetType` would lose.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConversionReport {
+  pub target: TargetType,
+  /// Significant digits in the value, after reduction.
+  pub significant_digits: usize,
+  /// Trailing significant digits the target can't hold.
+  pub digits_dropped: usize,
+  /// The value's exponent is outside the target's range,
+  /// so it would overflow or underflow regardless of digits.
+  pub exponent_clamped: bool,
+  /// The target holds exactly the value.  For the binary
+  /// float types this depends on the value's binary
+  /// expansion, so `0.1` is inexact however few digits it
+  /// has, while `2^-30` is exact despite its 21 digits.
+  pub exact: bool
+}
+
+impl ConversionReport {
+  /**
+   * Whether the conversion keeps the value exactly.
+   */
+  pub fn is_lossless(&self) -> bool {
+    self.exact
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciValue<B,E> {
+  /**
+   * Reports exactly what converting this value to the
+   * given type would lose.  Zero converts losslessly to
+   * every target.
+   */
+  pub fn conversion_report(&self, target:TargetType) -> ConversionReport {
+    let reduced = self.reduce();
+    let digits  = decimal_digits(reduced.base).len();
+    let mut report = ConversionReport{
+      target,
+      significant_digits: digits,
+      digits_dropped: 0,
+      exponent_clamped: false,
+      exact: true
+    };
+    if digits == 0 {
+      return report;
+    }
+
+    let exp = saturating_exp_to_i64(reduced.e_exp);
+    let leading = exp.saturating_add(digits as i64 - 1);
+    let (precision, min_leading, max_leading) = target.limits();
+    match target {
+      TargetType::I64 => {
+        //Digits below the units place are the ones lost
+        report.digits_dropped   = if exp < 0 { digits.min(usize::try_from(exp.unsigned_abs()).unwrap_or(usize::MAX)) } else { 0 };
+        report.exponent_clamped = leading > max_leading || !integer_part_fits_i64(&reduced);
+      },
+      _               => {
+        report.digits_dropped   = digits.saturating_sub(precision);
+        report.exponent_clamped = leading < min_leading || leading > max_leading;
+      }
+    }
+    report.exact = match target {
+      TargetType::F32 => self.to_f32_with_status().1 == FloatConversion::Exact,
+      TargetType::F64 => self.to_f64_with_status().1 == FloatConversion::Exact,
+      _               => report.digits_dropped == 0 && !report.exponent_clamped
+    };
+    report
+  }
+}
+
+/**
+ * Whether the integer part of a reduced value with at
+ * most 19 digits before the point fits in an `i64`.
+ */
+fn integer_part_fits_i64<B:PrimInt, E:PrimInt + Signed>(reduced:&SciValue<B,E>) -> bool {
+  let base = match reduced.base.to_i128() {
+    Some(base) => base,
+    None       => return false
+  };
+  let exp = saturating_exp_to_i64(reduced.e_exp);
+  let int_part = if exp >= 0 {
+    10i128.checked_pow(exp as u32).and_then(|scale| base.checked_mul(scale))
+  } else if exp < -38 {
+    Some(0)
+  } else {
+    Some(base / 10i128.pow((-exp) as u32))
+  };
+  int_part.is_some_and(|int_part| i64::try_from(int_part).is_ok())
+}
+
+#[cfg(test)]
+mod test{
+  use super::{TargetType, ConversionReport};
+  use super::super::SciValue;
+
+  #[test]
+  fn float_reports(){
+    let val = SciValue::wrap_with_exponent(12345678900i64, -2i32);
+    assert_eq!(val.conversion_report(TargetType::F32), ConversionReport{
+      target: TargetType::F32,
+      significant_digits: 9,
+      digits_dropped: 3,
+      exponent_clamped: false,
+      exact: false
+    });
+    assert!(val.conversion_report(TargetType::F64).is_lossless());
+    assert!(SciValue::wrap_with_exponent(5i64, 39i32).conversion_report(TargetType::F32).exponent_clamped);
+    assert!(SciValue::wrap_with_exponent(5i64, 39i32).conversion_report(TargetType::Decimal64).is_lossless());
+    assert!(SciValue::wrap_with_exponent(0i64, 1000i32).conversion_report(TargetType::F32).is_lossless());
+    //Exactness depends on the binary expansion, not the digit count
+    assert!(!SciValue::wrap_with_exponent(1i64, -1i32).conversion_report(TargetType::F64).is_lossless());
+    assert!(SciValue::wrap_with_exponent(5i64, -1i32).conversion_report(TargetType::F64).is_lossless());
+    assert!(SciValue::wrap_with_exponent(931322574615478515625i128, -30i32).conversion_report(TargetType::F32).is_lossless());
+  }
+
+  #[test]
+  fn integer_reports(){
+    let report = SciValue::wrap_with_exponent(-12345i64, -3i32).conversion_report(TargetType::I64);
+    assert_eq!((report.digits_dropped, report.exponent_clamped), (3, false));
+    assert_eq!(SciValue::wrap_with_exponent(5i64, -9i32).conversion_report(TargetType::I64).digits_dropped, 1);
+    assert!(SciValue::<i64,i32>::wrap(i64::MAX).conversion_report(TargetType::I64).is_lossless());
+    assert!(SciValue::wrap_with_exponent(922337203685477581i64, 1i32).conversion_report(TargetType::I64).exponent_clamped);
+    assert!(SciValue::wrap_with_exponent(1u128, 19i32).conversion_report(TargetType::I64).exponent_clamped);
+  }
+}
@@ -0,0 +1,165 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::collections::BTreeMap;
+
+use super::{SciValue, RoundingMode, base_constant, mode_rounds_away};
+
+/**
+ * How the values falling in one bucket are combined.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregator {
+  /// The exact sum.
+  Sum,
+  /// The exact sum divided by the count.  The quotient
+  /// is carried to as many digits as the base holds, as
+  /// `checked_div` does, and rounded with the given mode
+  /// only if it still doesn't divide evenly.
+  Mean(RoundingMode),
+  /// The smallest value, by value.
+  Min,
+  /// The largest value, by value.
+  Max
+}
+
+/**
+ * Downsamples a time series into buckets `bucket` wide,
+ * aligned to multiples of the width.  Each bucket present
+ * in the input gives one aggregate, keyed by the bucket's
+ * starting timestamp, in increasing order.  The input
+ * needn't be sorted.
+ *
+ * Sums are computed exactly, without any float drift, so
+ * this returns None if one would overflow, as it does if
+ * a bucket would start before `i64::MIN`.  Panics if
+ * `bucket` isn't positive.
+ */
+pub fn resample<B, E>(values:&[(i64, SciValue<B,E>)], bucket:i64, aggregator:Aggregator) -> Option<Vec<(i64, SciValue<B,E>)>>
+    where B:PrimInt + FromPrimitive, E:PrimInt + Signed {
+  assert!(bucket > 0, "resample: bucket width {} is not positive", bucket);
+  let mut buckets: BTreeMap<i64, Vec<SciValue<B,E>>> = BTreeMap::new();
+  for &(timestamp, val) in values.iter() {
+    let start = timestamp.div_euclid(bucket).checked_mul(bucket)?;
+    buckets.entry(start).or_default().push(val);
+  }
+
+  buckets.into_iter()
+         .map(|(start, members)| aggregate(&members, aggregator).map(|val| (start, val)))
+         .collect()
+}
+
+fn aggregate<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(members:&[SciValue<B,E>], aggregator:Aggregator) -> Option<SciValue<B,E>> {
+  let first = members[0];
+  let mut rest = members[1..].iter();
+  match aggregator {
+    Aggregator::Sum        => rest.try_fold(first, |total, val| total.checked_add(val)),
+    Aggregator::Mean(mode) => {
+      let total = rest.try_fold(first, |total, val| total.checked_add(val))?;
+      let count = <B as FromPrimitive>::from_usize(members.len())?;
+      let (base, exp) = total.scale_for_div(&SciValue::wrap(count));
+      let (quot, rem) = (base / count, base % count);
+
+      //The next digit of the quotient, and whether any
+      //after it is nonzero, decide the rounding
+      let scaled_rem = rem.to_i128()?.unsigned_abs() * 10;
+      let digit = base_constant::<B>((scaled_rem / members.len() as u128) as i64, "resample");
+      let sticky = scaled_rem % members.len() as u128 != 0;
+      let negative = base < B::zero();
+      let quot = match mode_rounds_away(mode, quot, digit, sticky, negative) {
+        false             => quot,
+        true if negative  => quot.checked_sub(&B::one())?,
+        true              => quot.checked_add(&B::one())?
+      };
+      Some(SciValue::wrap_with_exponent(quot, exp))
+    },
+    Aggregator::Min        => Some(*members.iter().min_by(|lhs, rhs| lhs.cmp_value(rhs)).unwrap()),
+    Aggregator::Max        => Some(*members.iter().max_by(|lhs, rhs| lhs.cmp_value(rhs)).unwrap())
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::{resample, Aggregator};
+  use super::super::{SciValue, RoundingMode};
+
+  fn series() -> Vec<(i64, SciValue<i64,i32>)> {
+    vec![
+      (12, SciValue::wrap_with_exponent(15, -1)),
+      (3,  SciValue::wrap(2)),
+      (-1, SciValue::wrap_with_exponent(7, 1)),
+      (9,  SciValue::wrap_with_exponent(25, -2)),
+      (14, SciValue::wrap_with_exponent(3, 0))
+    ]
+  }
+
+  #[test]
+  fn aggregates(){
+    assert_eq!(resample(&series(), 10, Aggregator::Sum), Some(vec![
+      (-10, SciValue::wrap_with_exponent(7, 1)),
+      (0,   SciValue::wrap_with_exponent(225, -2)),
+      (10,  SciValue::wrap_with_exponent(45, -1))
+    ]));
+    assert_eq!(resample(&series(), 10, Aggregator::Mean(RoundingMode::HalfEven)), Some(vec![
+      (-10, SciValue::wrap_with_exponent(7, 1)),
+      (0,   SciValue::wrap_with_exponent(1125, -3)),
+      (10,  SciValue::wrap_with_exponent(225, -2))
+    ]));
+    assert_eq!(resample(&series(), 10, Aggregator::Min).unwrap()[1], (0, SciValue::wrap_with_exponent(25, -2)));
+    assert_eq!(resample(&series(), 10, Aggregator::Max).unwrap()[2], (10, SciValue::wrap_with_exponent(3, 0)));
+    assert_eq!(resample::<i64,i32>(&[], 10, Aggregator::Sum), Some(vec![]));
+  }
+
+  #[test]
+  fn rounded_means(){
+    let halves = [(0, SciValue::wrap(1i64)), (1, SciValue::wrap(2i64))];
+    assert_eq!(resample::<i64,i32>(&halves, 10, Aggregator::Mean(RoundingMode::Truncate)),
+               Some(vec![(0, SciValue::wrap_with_exponent(15, -1))]));
+
+    //4 / 3 can only be carried to 13e-1 in an i8
+    let thirds = [(0, SciValue::wrap(1i8)), (1, SciValue::wrap(1i8)), (2, SciValue::wrap(2i8))];
+    let mean = |mode| resample::<i8,i8>(&thirds, 10, Aggregator::Mean(mode)).map(|buckets| buckets[0].1);
+    assert_eq!(mean(RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(13, -1)));
+    assert_eq!(mean(RoundingMode::Ceiling), Some(SciValue::wrap_with_exponent(14, -1)));
+    let negative = [(0, SciValue::wrap(-1i8)), (1, SciValue::wrap(-1i8)), (2, SciValue::wrap(-2i8))];
+    assert_eq!(resample::<i8,i8>(&negative, 10, Aggregator::Mean(RoundingMode::Floor)).map(|buckets| buckets[0].1),
+               Some(SciValue::wrap_with_exponent(-14, -1)));
+  }
+
+  #[test]
+  fn overflowing_sum(){
+    let values = [(0, SciValue::wrap(100i8)), (1, SciValue::wrap(100i8))];
+    assert_eq!(resample::<i8,i8>(&values, 5, Aggregator::Sum), None);
+    assert_eq!(resample::<i8,i8>(&values, 1, Aggregator::Sum).map(|buckets| buckets.len()), Some(2));
+  }
+
+  #[test]
+  fn extreme_timestamps(){
+    let values = [(i64::MIN, SciValue::wrap(1i64)), (i64::MAX, SciValue::wrap(2i64))];
+    assert_eq!(resample::<i64,i32>(&values, 10, Aggregator::Sum), None);
+    assert_eq!(resample::<i64,i32>(&values, 2, Aggregator::Sum), Some(vec![
+      (i64::MIN,     SciValue::wrap(1)),
+      (i64::MAX - 1, SciValue::wrap(2))
+    ]));
+  }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::collections::BTreeMap;
+
+use super::{SciValue, decimal_digits, saturating_exp_to_i64};
+
+/**
+ * Picks up to `per_decade` values at random from each
+ * order of magnitude present in `values`, for building
+ * small fixtures which still cover the full range of a
+ * dataset.  Values are bucketed by the exponent of their
+ * leading digit, with zeros in a bucket of their own.
+ *
+ * `rng` should return uniformly random `u64`s, such as
+ * `|| rng.next_u64()` with the `rand` crate.  The result
+ * is ordered from the zeros up through increasing
+ * magnitudes, and by order of selection within each.
+ */
+pub fn sample_stratified<B, E, R>(values:&[SciValue<B,E>], per_decade:usize, rng:&mut R) -> Vec<SciValue<B,E>>
+    where B:PrimInt + FromPrimitive, E:PrimInt + Signed, R:FnMut() -> u64 {
+  let mut buckets: BTreeMap<Option<i64>, Vec<usize>> = BTreeMap::new();
+  for (idx, val) in values.iter().enumerate() {
+    buckets.entry(leading_exponent(val)).or_default().push(idx);
+  }
+
+  let mut sample = Vec::new();
+  for (_, mut indices) in buckets {
+    //A partial Fisher-Yates shuffle, picking into the front
+    let count = per_decade.min(indices.len());
+    for pick in 0..count {
+      let remaining = (indices.len() - pick) as u64;
+      let chosen = pick + (rng() % remaining) as usize;
+      indices.swap(pick, chosen);
+      sample.push(values[indices[pick]]);
+    }
+  }
+  sample
+}
+
+/**
+ * The exponent of a value's leading digit, or None for
+ * zero.
+ */
+fn leading_exponent<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(val:&SciValue<B,E>) -> Option<i64> {
+  let reduced = val.reduce();
+  match decimal_digits(reduced.base).len() {
+    0      => None,
+    digits => Some(saturating_exp_to_i64(reduced.e_exp).saturating_add(digits as i64 - 1))
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::sample_stratified;
+  use super::super::SciValue;
+
+  /// A small xorshift generator, for repeatable tests.
+  fn xorshift(seed:u64) -> impl FnMut() -> u64 {
+    let mut state = seed;
+    move || {
+      state ^= state << 13;
+      state ^= state >> 7;
+      state ^= state << 17;
+      state
+    }
+  }
+
+  #[test]
+  fn covers_each_decade(){
+    let values: Vec<SciValue<i64,i32>> = [3, 7, 12, 45, 99, 250, 0, 5000, 8]
+      .iter().map(|base| SciValue::wrap(*base)).collect();
+    let sample = sample_stratified(&values, 1, &mut xorshift(42));
+    let decades: Vec<Option<i64>> = sample.iter().map(super::leading_exponent).collect();
+    assert_eq!(decades, vec![None, Some(0), Some(1), Some(2), Some(3)]);
+
+    //Each bucket is sampled without repeats
+    let sample = sample_stratified(&values, 5, &mut xorshift(7));
+    assert_eq!(sample.len(), values.len());
+    for val in values.iter() {
+      assert!(sample.contains(val));
+    }
+    assert_eq!(sample_stratified(&values, 0, &mut xorshift(1)), vec![]);
+  }
+}
@@ -0,0 +1,164 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Optional serde support, enabled with the `serde` feature.
+//!
+//! Values serialize as a struct of their stored base and
+//! exponent, `{ "base": 5, "exp": 2 }`, with no reduction.
+//!
+//! Plain deserialization accepts anything which fits the
+//! types.  For input which may be hostile, use
+//! `SciValue::deserialize_with_limits`, which enforces
+//! `ParseLimits` as `from_str_with_limits` does:
+//!
+//! ```ignore
+//! fn limited<'de, D:Deserializer<'de>>(deserializer:D) -> Result<SciValue<i64,i32>, D::Error> {
+//!   SciValue::deserialize_with_limits(deserializer, &LIMITS)
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Reading {
+//!   #[serde(deserialize_with = "limited")]
+//!   value: SciValue<i64,i32>
+//! }
+//! ```
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::Error as DeError;
+
+use super::{SciValue, ParseLimits, decimal_digits, saturating_exp_to_i64};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "SciValue")]
+struct Repr<B, E> {
+  base: B,
+  exp: E
+}
+
+impl<B:PrimInt + Serialize, E:PrimInt + Signed + Serialize> Serialize for SciValue<B,E> {
+  fn serialize<S:Serializer>(&self, serializer:S) -> Result<S::Ok, S::Error> {
+    Repr{base: self.base, exp: self.e_exp}.serialize(serializer)
+  }
+}
+
+impl<'de, B:PrimInt + Deserialize<'de>, E:PrimInt + Signed + Deserialize<'de>> Deserialize<'de> for SciValue<B,E> {
+  fn deserialize<D:Deserializer<'de>>(deserializer:D) -> Result<SciValue<B,E>, D::Error> {
+    Repr::deserialize(deserializer).map(|repr| SciValue::wrap_with_exponent(repr.base, repr.exp))
+  }
+}
+
+impl<'de, B:PrimInt + FromPrimitive + Deserialize<'de>, E:PrimInt + Signed + Deserialize<'de>> SciValue<B,E> {
+  /**
+   * Deserializes a value, failing if its base has more
+   * than `limits.max_mantissa_digits` digits or its exponent
+   * is outside `limits.min_exponent..=limits.max_exponent`.
+   */
+  pub fn deserialize_with_limits<D:Deserializer<'de>>(deserializer:D, limits:&ParseLimits) -> Result<SciValue<B,E>, D::Error> {
+    let val: SciValue<B,E> = SciValue::deserialize(deserializer)?;
+    if decimal_digits(val.base).len() > limits.max_mantissa_digits {
+      return Err(D::Error::custom("too many mantissa digits"));
+    }
+    let exp = saturating_exp_to_i64(val.e_exp);
+    if exp >= limits.min_exponent && exp <= limits.max_exponent {
+      Ok(val)
+    } else {
+      Err(D::Error::custom("exponent out of bounds"))
+    }
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use serde::de::value::{MapDeserializer, Error};
+  use serde::Deserialize;
+  use serde_test::{assert_tokens, assert_de_tokens, assert_ser_tokens, Token};
+  use super::super::{SciValue, ParseLimits, RoundingMode, FormatOptions, ExponentMarker};
+
+  #[test]
+  fn struct_round_trip(){
+    assert_tokens(&SciValue::wrap_with_exponent(-21i64, 1i32), &[
+      Token::Struct{name: "SciValue", len: 2},
+      Token::Str("base"),
+      Token::I64(-21),
+      Token::Str("exp"),
+      Token::I32(1),
+      Token::StructEnd
+    ]);
+
+    //Stored form is kept, not reduced
+    assert_de_tokens(&SciValue::wrap_with_exponent(500i32, 0i8), &[
+      Token::Map{len: Some(2)},
+      Token::Str("exp"),
+      Token::I8(0),
+      Token::Str("base"),
+      Token::I32(500),
+      Token::MapEnd
+    ]);
+  }
+
+  #[test]
+  fn deserialize_limits(){
+    fn parts(base:i64, exp:i64) -> MapDeserializer<'static, std::vec::IntoIter<(&'static str, i64)>, Error> {
+      MapDeserializer::new(vec![("base", base), ("exp", exp)].into_iter())
+    }
+    let limits = ParseLimits{max_mantissa_digits: 4, min_exponent: -10, max_exponent: 10};
+
+    let res: Result<SciValue<i64,i32>, Error> = SciValue::deserialize_with_limits(parts(-9999, 10), &limits);
+    assert_eq!(res, Ok(SciValue::wrap_with_exponent(-9999, 10)));
+    let res: Result<SciValue<i64,i32>, Error> = SciValue::deserialize_with_limits(parts(10000, 0), &limits);
+    assert_eq!(res.unwrap_err().to_string(), "too many mantissa digits");
+    let res: Result<SciValue<i64,i32>, Error> = SciValue::deserialize_with_limits(parts(1, -11), &limits);
+    assert_eq!(res.unwrap_err().to_string(), "exponent out of bounds");
+    let res: Result<SciValue<i64,i32>, Error> = SciValue::deserialize_with_limits(parts(0, 3), &ParseLimits::unlimited());
+    assert_eq!(res, Ok(SciValue::wrap_with_exponent(0, 3)));
+
+    //Exponents wider than an i64 pass unlimited limits
+    let wide = MapDeserializer::new(vec![("base", 1i128), ("exp", 1i128 << 80)].into_iter());
+    let res: Result<SciValue<i128,i128>, Error> = SciValue::deserialize_with_limits(wide, &ParseLimits::unlimited());
+    assert_eq!(res, Ok(SciValue::wrap_with_exponent(1, 1i128 << 80)));
+    let wide = MapDeserializer::new(vec![("base", 1i128), ("exp", 1i128 << 80)].into_iter());
+    let res: Result<SciValue<i128,i128>, Error> = SciValue::deserialize_with_limits(wide, &limits);
+    assert_eq!(res.unwrap_err().to_string(), "exponent out of bounds");
+  }
+
+  #[test]
+  fn configuration_types(){
+    assert_tokens(&RoundingMode::HalfEven, &[Token::UnitVariant{name: "RoundingMode", variant: "half_even"}]);
+    assert_tokens(&RoundingMode::Truncate, &[Token::UnitVariant{name: "RoundingMode", variant: "truncate"}]);
+    assert_tokens(&ExponentMarker::TimesTenPower, &[Token::UnitVariant{name: "ExponentMarker", variant: "times_ten_power"}]);
+
+    let options = FormatOptions::new().with_exponent_marker(ExponentMarker::Fortran);
+    assert_ser_tokens(&options, &[
+      Token::Struct{name: "FormatOptions", len: 1},
+      Token::Str("exponent_marker"),
+      Token::UnitVariant{name: "ExponentMarker", variant: "fortran"},
+      Token::StructEnd
+    ]);
+
+    let tokens = MapDeserializer::<_, Error>::new(vec![("exponent_marker", "upper")].into_iter());
+    let options = FormatOptions::deserialize(tokens).unwrap();
+    assert_eq!((options.renderer.is_none(), options.exponent_marker), (true, ExponentMarker::Upper));
+    let empty = MapDeserializer::<_, Error>::new(Vec::<(&str, &str)>::new().into_iter());
+    assert_eq!(FormatOptions::deserialize(empty).unwrap().exponent_marker, ExponentMarker::Lower);
+  }
+}
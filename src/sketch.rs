@@ -0,0 +1,152 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::collections::BTreeMap;
+
+use super::{SciValue, OrderedSci, base_constant, drop_digits};
+
+/**
+ * Approximate multiset of values, answering quantile
+ * queries over streams too large to keep in full.
+ *
+ * Values are bucketed by exponent and their leading
+ * digits, so memory grows with the spread of magnitudes
+ * seen rather than the number of values.
+ */
+pub struct SciSketch<B, E:PrimInt + Signed> {
+  digits: usize,
+  count: u64,
+  buckets: BTreeMap<OrderedSci<B,E>, u64>
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciSketch<B,E> {
+  /**
+   * Creates an empty sketch keeping the given number
+   * of leading digits (at least 1) of each value.
+   */
+  pub fn new(digits:usize) -> SciSketch<B,E> {
+    SciSketch{digits: if digits == 0 { 1 } else { digits }, count: 0, buckets: BTreeMap::new()}
+  }
+
+  pub fn insert(&mut self, val:&SciValue<B,E>) {
+    let key = OrderedSci(self.bucket_for(val));
+    *self.buckets.entry(key).or_insert(0) += 1;
+    self.count += 1;
+  }
+
+  /// The number of values inserted.
+  pub fn count(&self) -> u64 {
+    self.count
+  }
+
+  /// The number of distinct buckets in use.
+  pub fn bucket_count(&self) -> usize {
+    self.buckets.len()
+  }
+
+  /**
+   * Finds the approximate value at quantile `q`, where `q`
+   * runs from 0 (the minimum) to 1 (the maximum).  The
+   * result is accurate to the number of leading digits
+   * kept, truncated toward zero.  Returns None if the
+   * sketch is empty.
+   */
+  pub fn quantile(&self, q:f64) -> Option<SciValue<B,E>> {
+    if self.count == 0 {
+      return None;
+    }
+    let clamped = q.clamp(0.0, 1.0);
+    let target  = (clamped * ((self.count - 1) as f64) + 0.5) as u64;
+
+    let mut seen = 0u64;
+    for (key, bucket_count) in self.buckets.iter() {
+      seen += *bucket_count;
+      if seen > target {
+        return Some(key.0);
+      }
+    }
+    self.buckets.keys().next_back().map(|key| key.0)
+  }
+
+  /**
+   * Truncates the value to the kept number of
+   * significant digits.
+   */
+  fn bucket_for(&self, val:&SciValue<B,E>) -> SciValue<B,E> {
+    let type_b_0 = B::zero();
+    let type_b_10 = base_constant::<B>(10, "sketch_insert");
+
+    let mut sig_digits = 0usize;
+    let mut remaining  = val.base;
+    while remaining != type_b_0 {
+      sig_digits += 1;
+      remaining  = remaining / type_b_10;
+    }
+    if sig_digits <= self.digits {
+      return val.reduce();
+    }
+
+    let extra = <E as FromPrimitive>::from_usize(sig_digits - self.digits).expect("Couldn't convert digit count to exponent type");
+    let (prefix, _, _) = drop_digits(val.base, extra);
+    SciValue::wrap_with_exponent(prefix, val.e_exp + extra).reduce()
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::SciSketch;
+  use super::super::SciValue;
+  use std::cmp::Ordering;
+
+  #[test]
+  fn quantiles(){
+    let mut sketch = SciSketch::new(2);
+    for val in 1i32..101 {
+      sketch.insert(&SciValue::wrap_with_exponent(val, 0isize));
+    }
+
+    assert_eq!(sketch.count(), 100);
+    assert_eq!(sketch.quantile(0.0), Some(SciValue::wrap(1)));
+    assert_eq!(sketch.quantile(0.5), Some(SciValue::wrap(51)));
+    assert_eq!(sketch.quantile(1.0).unwrap().cmp_value(&SciValue::wrap(100)), Ordering::Equal);
+  }
+
+  #[test]
+  fn bucketing(){
+    let mut sketch = SciSketch::new(3);
+    sketch.insert(&SciValue::wrap_with_exponent(12345i64, 0isize));
+    sketch.insert(&SciValue::wrap_with_exponent(12399i64, 0isize));
+    sketch.insert(&SciValue::wrap_with_exponent(1234i64, 1isize));
+    sketch.insert(&SciValue::wrap_with_exponent(-5i64, -20isize));
+
+    assert_eq!(sketch.bucket_count(), 2);
+    assert_eq!(sketch.quantile(0.0), Some(SciValue::wrap_with_exponent(-5, -20isize)));
+    assert_eq!(sketch.quantile(1.0), Some(SciValue::wrap_with_exponent(123, 2isize)));
+  }
+
+  #[test]
+  fn empty(){
+    let sketch: SciSketch<i32,isize> = SciSketch::new(2);
+    assert_eq!(sketch.quantile(0.5), None);
+  }
+}
@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error};
+
+use super::{SciValue, PrecisionLoss, RoundingMode, split_mean};
+
+/**
+ * Accumulates the count, mean and variance of a stream of
+ * values, for statistics over data too large to hold at
+ * once.  Accumulators over separate shards merge into one
+ * for the whole data set.
+ *
+ * The sum and the sum of squares are kept exactly.  The
+ * usual parallel Welford merge exists to stop floating
+ * point rounding building up in the variance; with exact
+ * sums merging is just adding them, and the result is the
+ * same however the data was sharded.
+ */
+pub struct RunningStats<B, E:PrimInt + Signed> {
+  count: usize,
+  sum: SciValue<B,E>,
+  sum_squares: SciValue<B,E>
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> RunningStats<B,E> {
+  /// An accumulator which has seen no values.
+  pub fn new() -> RunningStats<B,E> {
+    let zero = SciValue::wrap_with_exponent(B::zero(), E::zero());
+    RunningStats{count: 0, sum: zero, sum_squares: zero}
+  }
+
+  /**
+   * Adds a value.  Fails with `Overflow`, leaving the
+   * accumulator unchanged, if the sums overflow.
+   */
+  pub fn push(&mut self, val:&SciValue<B,E>) -> Result<(), PrecisionLoss> {
+    let square = val.checked_mul(val).ok_or(PrecisionLoss::Overflow)?;
+    self.add_sums(1, val, &square)
+  }
+
+  /**
+   * Adds in everything another accumulator has seen, as if
+   * its values had been pushed here.  Fails with
+   * `Overflow`, leaving the accumulator unchanged, if the
+   * sums overflow.
+   */
+  pub fn merge(&mut self, other:&RunningStats<B,E>) -> Result<(), PrecisionLoss> {
+    self.add_sums(other.count, &other.sum, &other.sum_squares)
+  }
+
+  fn add_sums(&mut self, count:usize, sum:&SciValue<B,E>, sum_squares:&SciValue<B,E>) -> Result<(), PrecisionLoss> {
+    let count = self.count.checked_add(count).ok_or(PrecisionLoss::Overflow)?;
+    let sum = self.sum.checked_add(sum).ok_or(PrecisionLoss::Overflow)?;
+    let sum_squares = self.sum_squares.checked_add(sum_squares).ok_or(PrecisionLoss::Overflow)?;
+    *self = RunningStats{count, sum, sum_squares};
+    Ok(())
+  }
+
+  /// The number of values seen.
+  pub fn count(&self) -> usize {
+    self.count
+  }
+
+  /// The exact sum of the values seen.
+  pub fn sum(&self) -> SciValue<B,E> {
+    self.sum
+  }
+
+  /**
+   * The mean as `mean_exact` gives it, truncated toward
+   * zero with the residue left over.  Returns None if no
+   * values have been seen.
+   */
+  pub fn mean(&self) -> Option<(SciValue<B,E>, SciValue<B,E>)> {
+    if self.count == 0 {
+      return None;
+    }
+    split_mean(self.sum, self.count)
+  }
+
+  /**
+   * The population variance, rounded using `mode` to the
+   * decimal place of 10^exp.  Returns None if no values
+   * have been seen or the result doesn't fit the types.
+   */
+  pub fn variance(&self, exp:E, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    let count = self.count_value()?;
+    self.spread(&count.checked_mul(&count)?, exp, mode)
+  }
+
+  /**
+   * The sample variance, with Bessel's correction, rounded
+   * using `mode` to the decimal place of 10^exp.  Returns
+   * None if fewer than two values have been seen or the
+   * result doesn't fit the types.
+   */
+  pub fn sample_variance(&self, exp:E, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    if self.count < 2 {
+      return None;
+    }
+    let count = self.count_value()?;
+    let less_one = count.checked_sub(&SciValue::wrap(B::one()))?;
+    self.spread(&count.checked_mul(&less_one)?, exp, mode)
+  }
+
+  fn count_value(&self) -> Option<SciValue<B,E>> {
+    if self.count == 0 {
+      return None;
+    }
+    Some(SciValue::wrap(<B as FromPrimitive>::from_usize(self.count)?))
+  }
+
+  /**
+   * `(n * sum_squares - sum^2) / den`, the variance for a
+   * denominator of n^2 or n(n - 1).
+   */
+  fn spread(&self, den:&SciValue<B,E>, exp:E, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    let count = self.count_value()?;
+    let scaled = count.checked_mul(&self.sum_squares)?;
+    let deviations = scaled.checked_sub(&self.sum.checked_mul(&self.sum)?)?;
+    deviations.ratio_of(den, exp, mode)
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Default for RunningStats<B,E> {
+  fn default() -> RunningStats<B,E> {
+    RunningStats::new()
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for RunningStats<B,E> {
+  fn clone(&self) -> RunningStats<B,E> {
+    RunningStats{count: self.count, sum: self.sum, sum_squares: self.sum_squares}
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for RunningStats<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    write!(fmtr, "RunningStats{{count : {}, sum : {:?}, sum_squares : {:?}}}", self.count, self.sum, self.sum_squares)
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::RunningStats;
+  use super::super::{SciValue, PrecisionLoss, RoundingMode, mean_exact};
+
+  fn stats_of(values:&[SciValue<i64,i32>]) -> RunningStats<i64,i32> {
+    let mut stats = RunningStats::new();
+    for val in values {
+      stats.push(val).unwrap();
+    }
+    stats
+  }
+
+  #[test]
+  fn running_statistics(){
+    let values: Vec<SciValue<i64,i32>> = [2, 4, 4, 4, 5, 5, 7, 9].iter().map(|&val| SciValue::wrap(val)).collect();
+    let stats = stats_of(&values);
+    assert_eq!(stats.count(), 8);
+    assert_eq!(stats.sum(), SciValue::wrap(40));
+    assert_eq!(stats.mean(), mean_exact(&values));
+    assert_eq!(stats.variance(0, RoundingMode::HalfEven), Some(SciValue::wrap(4)));
+    assert_eq!(stats.sample_variance(-3, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(4571, -3)));
+
+    let tenths = stats_of(&[SciValue::wrap_with_exponent(1, -1), SciValue::wrap_with_exponent(3, -1)]);
+    assert_eq!(tenths.mean(), Some((SciValue::wrap_with_exponent(2, -1), SciValue::wrap_with_exponent(0, -1))));
+    assert_eq!(tenths.variance(-2, RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(1, -2)));
+
+    let empty = RunningStats::<i64,i32>::new();
+    assert_eq!((empty.mean(), empty.variance(0, RoundingMode::HalfEven)), (None, None));
+    assert_eq!(stats_of(&values[..1]).sample_variance(0, RoundingMode::HalfEven), None);
+
+    let mut small = RunningStats::<i8,i8>::new();
+    small.push(&SciValue::wrap(11)).unwrap();
+    assert_eq!(small.push(&SciValue::wrap(12)), Err(PrecisionLoss::Overflow));
+    assert_eq!(small.count(), 1);
+  }
+
+  #[test]
+  fn merged_shards(){
+    let values: Vec<SciValue<i64,i32>> = (1..=20).map(|val| SciValue::wrap_with_exponent(val * 7 % 13, -(val % 3) as i32)).collect();
+    let whole = stats_of(&values);
+    for split in [0, 1, 7, 20] {
+      let mut merged = stats_of(&values[..split]);
+      merged.merge(&stats_of(&values[split..])).unwrap();
+      assert_eq!(merged.count(), whole.count());
+      assert_eq!(merged.sum().cmp_value(&whole.sum()), std::cmp::Ordering::Equal);
+      assert_eq!(merged.variance(-6, RoundingMode::HalfEven), whole.variance(-6, RoundingMode::HalfEven));
+      assert_eq!(merged.sample_variance(-6, RoundingMode::HalfEven), whole.sample_variance(-6, RoundingMode::HalfEven));
+    }
+  }
+}
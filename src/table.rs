@@ -0,0 +1,240 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::Display;
+
+use super::SciValue;
+
+/**
+ * The flavour of table a `TableWriter` emits.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableStyle {
+  /// Boxed with `+`, `-` and `|`, for terminals.
+  Ascii,
+  /// A GitHub-flavoured Markdown table.
+  Markdown
+}
+
+/**
+ * How the values in a column are written.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnFormat {
+  /// Every value in its own e-notation, as `Display` writes it.
+  Notation,
+  /// Values rescaled to one exponent, written once in the
+  /// header, so only bases appear in the cells.  Falls back
+  /// to `Notation` if some value can't be rescaled exactly.
+  SharedExponent
+}
+
+struct Column {
+  header: String,
+  format: ColumnFormat
+}
+
+/**
+ * Collects rows of values and writes them out as an
+ * aligned table, for quick reports from command line
+ * tools.  Values are right aligned and headers left
+ * aligned.
+ */
+pub struct TableWriter<B, E:PrimInt + Signed> {
+  style: TableStyle,
+  columns: Vec<Column>,
+  rows: Vec<Vec<SciValue<B,E>>>
+}
+
+impl<B:PrimInt + FromPrimitive + Display, E:PrimInt + Signed + Display> TableWriter<B,E> {
+  pub fn new(style:TableStyle) -> TableWriter<B,E> {
+    TableWriter{style, columns: Vec::new(), rows: Vec::new()}
+  }
+
+  pub fn add_column(&mut self, header:&str, format:ColumnFormat) {
+    self.columns.push(Column{header: header.to_string(), format});
+  }
+
+  /**
+   * Adds a row of values, one per column.
+   *
+   * Panics if the row's length doesn't match the
+   * number of columns.
+   */
+  pub fn add_row(&mut self, row:&[SciValue<B,E>]) {
+    if row.len() != self.columns.len() {
+      panic!("row has {} values but the table has {} columns", row.len(), self.columns.len());
+    }
+    self.rows.push(row.to_vec());
+  }
+
+  /// Writes out the table, one line per row, each ending in a newline.
+  pub fn render(&self) -> String {
+    let mut headers = Vec::with_capacity(self.columns.len());
+    let mut cells: Vec<Vec<String>> = self.rows.iter().map(|_| Vec::with_capacity(self.columns.len())).collect();
+    for (idx, column) in self.columns.iter().enumerate() {
+      let values: Vec<SciValue<B,E>> = self.rows.iter().map(|row| row[idx]).collect();
+      let shared = match column.format {
+        ColumnFormat::SharedExponent => shared_exponent(&values),
+        ColumnFormat::Notation       => None
+      };
+      match shared {
+        Some((exp, bases)) => {
+          headers.push(if exp == E::zero() { column.header.clone() } else { format!("{} [e{}]", column.header, exp) });
+          for (row_cells, base) in cells.iter_mut().zip(bases.iter()) {
+            row_cells.push(base.to_string());
+          }
+        },
+        None => {
+          headers.push(column.header.clone());
+          for (row_cells, val) in cells.iter_mut().zip(values.iter()) {
+            row_cells.push(val.to_string());
+          }
+        }
+      }
+    }
+
+    let widths: Vec<usize> = headers.iter().enumerate().map(|(idx, header)| {
+      cells.iter().map(|row| row[idx].chars().count()).fold(header.chars().count(), |max, len| max.max(len))
+    }).collect();
+
+    let mut out = String::new();
+    match self.style {
+      TableStyle::Ascii => {
+        let rule = rule_line(&widths, '+', '-', false);
+        out.push_str(&rule);
+        out.push_str(&cell_line(&headers, &widths, false));
+        out.push_str(&rule);
+        for row in cells.iter() {
+          out.push_str(&cell_line(row, &widths, true));
+        }
+        out.push_str(&rule);
+      },
+      TableStyle::Markdown => {
+        out.push_str(&cell_line(&headers, &widths, false));
+        out.push_str(&rule_line(&widths, '|', '-', true));
+        for row in cells.iter() {
+          out.push_str(&cell_line(row, &widths, true));
+        }
+      }
+    }
+    out
+  }
+}
+
+/**
+ * Rescales the column's values to the smallest exponent
+ * any nonzero value reduces to, returning that exponent
+ * and the rescaled bases, or None if that overflows.
+ */
+fn shared_exponent<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(values:&[SciValue<B,E>]) -> Option<(E, Vec<B>)> {
+  let exp = values.iter()
+                  .filter(|val| val.base != B::zero())
+                  .map(|val| val.reduce().e_exp)
+                  .min()
+                  .unwrap_or(E::zero());
+  values.iter().map(|val| val.rebalance_to_exponent(exp).ok().map(|scaled| scaled.base))
+        .collect::<Option<Vec<B>>>()
+        .map(|bases| (exp, bases))
+}
+
+fn cell_line(cells:&[String], widths:&[usize], right_align:bool) -> String {
+  let mut line = String::from("|");
+  for (cell, width) in cells.iter().zip(widths.iter()) {
+    if right_align {
+      line.push_str(&format!(" {:>width$} |", cell, width = *width));
+    } else {
+      line.push_str(&format!(" {:<width$} |", cell, width = *width));
+    }
+  }
+  line.push('\n');
+  line
+}
+
+/**
+ * A separator line; Markdown separators mark each
+ * column as right aligned.
+ */
+fn rule_line(widths:&[usize], joint:char, fill:char, markdown:bool) -> String {
+  let mut line = String::new();
+  line.push(joint);
+  for width in widths.iter() {
+    let dashes: String = (0..*width + 1).map(|_| fill).collect();
+    line.push_str(&dashes);
+    line.push(if markdown { ':' } else { fill });
+    line.push(joint);
+  }
+  line.push('\n');
+  line
+}
+
+#[cfg(test)]
+mod test{
+  use super::{TableWriter, TableStyle, ColumnFormat};
+  use super::super::SciValue;
+
+  fn sample(style:TableStyle) -> TableWriter<i32,isize> {
+    let mut table = TableWriter::new(style);
+    table.add_column("current", ColumnFormat::SharedExponent);
+    table.add_column("gain", ColumnFormat::Notation);
+    table.add_row(&[SciValue::wrap_with_exponent(15, -3), SciValue::wrap_with_exponent(2, 3)]);
+    table.add_row(&[SciValue::wrap_with_exponent(2500, -6), SciValue::wrap_with_exponent(-45, 1)]);
+    table
+  }
+
+  #[test]
+  fn ascii_table(){
+    assert_eq!(sample(TableStyle::Ascii).render(),
+               "+---------------+-------+\n\
+                | current [e-4] | gain  |\n\
+                +---------------+-------+\n\
+                |           150 |   2e3 |\n\
+                |            25 | -45e1 |\n\
+                +---------------+-------+\n");
+  }
+
+  #[test]
+  fn markdown_table(){
+    assert_eq!(sample(TableStyle::Markdown).render(),
+               "| current [e-4] | gain  |\n\
+                |--------------:|------:|\n\
+                |           150 |   2e3 |\n\
+                |            25 | -45e1 |\n");
+  }
+
+  #[test]
+  fn shared_exponent_fallback(){
+    let mut table: TableWriter<i8,i8> = TableWriter::new(TableStyle::Markdown);
+    table.add_column("x", ColumnFormat::SharedExponent);
+    table.add_row(&[SciValue::wrap_with_exponent(1, 5)]);
+    table.add_row(&[SciValue::wrap_with_exponent(3, 0)]);
+    table.add_row(&[SciValue::wrap(0)]);
+    assert_eq!(table.render(), "| x   |\n|----:|\n| 1e5 |\n| 3e0 |\n| 0e0 |\n");
+  }
+
+  #[test]
+  #[should_panic]
+  fn mismatched_row(){
+    sample(TableStyle::Ascii).add_row(&[SciValue::wrap(1)]);
+  }
+}
@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error};
+use std::ops::{Add, Sub, Mul, Div};
+
+use super::SciValue;
+
+/**
+ * Metadata which can follow values through arithmetic.
+ * When two tagged values are combined, the result is
+ * tagged with `lhs.merge(rhs)`.
+ */
+pub trait MergeTags {
+  fn merge(&self, other:&Self) -> Self;
+}
+
+/**
+ * A value carrying metadata about where it came from,
+ * such as a source id or a timestamp, for pipelines
+ * which have to trace every computed figure back to its
+ * inputs.
+ */
+pub struct Tagged<B, E:PrimInt + Signed, M> {
+  pub value: SciValue<B,E>,
+  pub tag: M
+}
+
+impl<B:PrimInt, E:PrimInt + Signed, M> Tagged<B,E,M> {
+  pub fn new(value:SciValue<B,E>, tag:M) -> Tagged<B,E,M> {
+    Tagged{value, tag}
+  }
+
+  /**
+   * Applies a function to the value, keeping the tag.
+   */
+  pub fn map<F:FnOnce(SciValue<B,E>) -> SciValue<B,E>>(self, func:F) -> Tagged<B,E,M> {
+    Tagged{value: func(self.value), tag: self.tag}
+  }
+
+  pub fn into_parts(self) -> (SciValue<B,E>, M) {
+    (self.value, self.tag)
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug, M:Debug> Debug for Tagged<B,E,M> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    write!(fmtr, "Tagged{{value : {:?}, tag : {:?}}}", self.value, self.tag)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed, M:Clone> Clone for Tagged<B,E,M> {
+  fn clone(&self) -> Tagged<B,E,M> {
+    Tagged{value: self.value, tag: self.tag.clone()}
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed, M:PartialEq> PartialEq for Tagged<B,E,M> {
+  fn eq(&self, rhs:&Tagged<B,E,M>) -> bool {
+    self.value == rhs.value && self.tag == rhs.tag
+  }
+}
+
+macro_rules! tagged_binop_impl {
+  ($op:ident, $method:ident, [$($b_bound:tt)*], [$($e_bound:tt)*]) => {
+    impl<B:$($b_bound)*, E:$($e_bound)*, M:MergeTags> $op for Tagged<B,E,M> {
+      type Output = Tagged<B,E,M>;
+
+      fn $method(self, rhs:Tagged<B,E,M>) -> Tagged<B,E,M> {
+        Tagged{value: $op::$method(self.value, rhs.value), tag: self.tag.merge(&rhs.tag)}
+      }
+    }
+  }
+}
+
+tagged_binop_impl!(Add, add, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+tagged_binop_impl!(Sub, sub, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+tagged_binop_impl!(Mul, mul, [PrimInt], [PrimInt + Signed]);
+tagged_binop_impl!(Div, div, [PrimInt + FromPrimitive], [PrimInt + Signed]);
+
+#[cfg(test)]
+mod test{
+  use super::{Tagged, MergeTags};
+  use super::super::SciValue;
+
+  /// The source ids a figure was computed from.
+  #[derive(Debug, Clone, PartialEq, Eq)]
+  struct Sources(Vec<u32>);
+
+  impl MergeTags for Sources {
+    fn merge(&self, other:&Sources) -> Sources {
+      let mut ids = self.0.clone();
+      ids.extend(other.0.iter().filter(|id| !self.0.contains(id)));
+      Sources(ids)
+    }
+  }
+
+  #[test]
+  fn tags_follow_arithmetic(){
+    let price = Tagged::new(SciValue::wrap_with_exponent(1999i64, -2i32), Sources(vec![1]));
+    let qty   = Tagged::new(SciValue::wrap(3i64), Sources(vec![2]));
+    let fee   = Tagged::new(SciValue::wrap_with_exponent(5i64, -1i32), Sources(vec![1]));
+
+    let total = (price * qty + fee).map(|val| val.reduce());
+    assert_eq!(total.into_parts(), (SciValue::wrap_with_exponent(6047, -2), Sources(vec![1, 2])));
+  }
+}
@@ -0,0 +1,299 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+//! Compact streaming encoding for runs of telemetry
+//! readings which mostly share one exponent.
+//!
+//! The stream is a sequence of records, each a LEB128
+//! varint.  The lowest bit of a record gives its kind:
+//!
+//! | bit 0 | record         | remaining bits                         |
+//! |-------|----------------|----------------------------------------|
+//! | 0     | mantissa       | zigzag delta from the previous mantissa |
+//! | 1     | exponent reset | zigzag exponent for following records  |
+//!
+//! A reset also sets the previous mantissa back to zero.
+//! Every stream starts with a reset to the encoder's
+//! target exponent, and the encoder only emits another
+//! when a value can't be written exactly at the current
+//! exponent.  Deltas wrap at 64 bits, so mantissas of
+//! any `i64` value are allowed.
+
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::marker::PhantomData;
+
+use super::{SciValue, PrecisionLoss};
+use super::binary::DecodeError;
+
+const KIND_RESET: u128 = 0x01;
+const MAX_VARINT_LEN: usize = 10;
+
+/**
+ * Streams values into the telemetry format.
+ */
+#[derive(Debug, Clone)]
+pub struct TelemetryEncoder<B, E:PrimInt + Signed> {
+  target: E,
+  current: E,
+  prev: i64,
+  bytes: Vec<u8>,
+  marker: PhantomData<B>
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> TelemetryEncoder<B,E> {
+  /**
+   * Creates an encoder which writes values at the given
+   * exponent wherever they can be written there exactly.
+   * Fails with `Overflow` if the exponent doesn't fit in
+   * an `i64`, as the stream can't record it.
+   */
+  pub fn new(target:E) -> Result<TelemetryEncoder<B,E>, PrecisionLoss> {
+    let mut encoder = TelemetryEncoder{
+      target,
+      current: target,
+      prev: 0,
+      bytes: Vec::new(),
+      marker: PhantomData
+    };
+    encoder.reset(target)?;
+    Ok(encoder)
+  }
+
+  /**
+   * Appends a value to the stream.  The value stays at
+   * the current exponent if it fits there exactly, and
+   * otherwise moves the stream back to the target
+   * exponent, or failing that to the value's own reduced
+   * exponent.  Fails with `Overflow` only if even the
+   * reduced mantissa or exponent doesn't fit in an `i64`,
+   * leaving the stream as it was.
+   */
+  pub fn push(&mut self, val:&SciValue<B,E>) -> Result<(), PrecisionLoss> {
+    if let Some(mantissa) = mantissa_at(val, self.current) {
+      self.push_mantissa(mantissa);
+      return Ok(());
+    }
+    if let Some(mantissa) = mantissa_at(val, self.target) {
+      let target = self.target;
+      self.reset(target)?;
+      self.push_mantissa(mantissa);
+      return Ok(());
+    }
+    let reduced = val.reduce();
+    let mantissa = reduced.base.to_i64().ok_or(PrecisionLoss::Overflow)?;
+    self.reset(reduced.e_exp)?;
+    self.push_mantissa(mantissa);
+    Ok(())
+  }
+
+  /**
+   * The bytes written so far.
+   */
+  pub fn as_bytes(&self) -> &[u8] {
+    self.bytes.as_slice()
+  }
+
+  /**
+   * Finishes the stream, returning its bytes.
+   */
+  pub fn finish(self) -> Vec<u8> {
+    self.bytes
+  }
+
+  fn reset(&mut self, exp:E) -> Result<(), PrecisionLoss> {
+    let field = exp.to_i64().ok_or(PrecisionLoss::Overflow)?;
+    push_varint(&mut self.bytes, (zigzag(field) << 1) | KIND_RESET);
+    self.current = exp;
+    self.prev = 0;
+    Ok(())
+  }
+
+  fn push_mantissa(&mut self, mantissa:i64) {
+    let delta = mantissa.wrapping_sub(self.prev);
+    push_varint(&mut self.bytes, zigzag(delta) << 1);
+    self.prev = mantissa;
+  }
+}
+
+/**
+ * Reads values back out of a telemetry stream, each at
+ * the exponent it was streamed with.  Stops after the
+ * first error.
+ */
+#[derive(Debug, Clone)]
+pub struct TelemetryDecoder<'a, B, E> {
+  bytes: &'a [u8],
+  pos: usize,
+  exp: Option<i64>,
+  prev: i64,
+  failed: bool,
+  marker: PhantomData<(B, E)>
+}
+
+impl<'a, B, E> TelemetryDecoder<'a, B, E> {
+  pub fn new(bytes:&'a [u8]) -> TelemetryDecoder<'a, B, E> {
+    TelemetryDecoder{
+      bytes,
+      pos: 0,
+      exp: None,
+      prev: 0,
+      failed: false,
+      marker: PhantomData
+    }
+  }
+
+  fn next_record(&mut self) -> Result<u128, DecodeError> {
+    let mut record = 0u128;
+    for idx in 0..MAX_VARINT_LEN {
+      let byte = *self.bytes.get(self.pos).ok_or(DecodeError::Truncated)?;
+      self.pos += 1;
+      record |= ((byte & 0x7F) as u128) << (7 * idx);
+      if byte & 0x80 == 0 {
+        return Ok(record);
+      }
+    }
+    Err(DecodeError::OutOfRange)
+  }
+}
+
+impl<'a, B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> Iterator for TelemetryDecoder<'a, B, E> {
+  type Item = Result<SciValue<B,E>, DecodeError>;
+
+  fn next(&mut self) -> Option<Result<SciValue<B,E>, DecodeError>> {
+    while !self.failed && self.pos < self.bytes.len() {
+      let record = match self.next_record() {
+        Ok(record) => record,
+        Err(err)   => {
+          self.failed = true;
+          return Some(Err(err));
+        }
+      };
+      let field = unzigzag((record >> 1) as u64);
+      if record & KIND_RESET != 0 {
+        self.exp  = Some(field);
+        self.prev = 0;
+        continue;
+      }
+      self.prev = self.prev.wrapping_add(field);
+      let base = <B as FromPrimitive>::from_i64(self.prev);
+      //A mantissa before any reset has no exponent to go with
+      let exp = self.exp.and_then(<E as FromPrimitive>::from_i64);
+      return match (base, exp) {
+        (Some(base), Some(exp)) => Some(Ok(SciValue::wrap_with_exponent(base, exp))),
+        _                       => {
+          self.failed = true;
+          Some(Err(DecodeError::OutOfRange))
+        }
+      };
+    }
+    None
+  }
+}
+
+/**
+ * Convenience for decoding a whole stream at once.
+ */
+pub fn decode_stream<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive>(bytes:&[u8]) -> Result<Vec<SciValue<B,E>>, DecodeError> {
+  TelemetryDecoder::new(bytes).collect()
+}
+
+fn mantissa_at<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(val:&SciValue<B,E>, exp:E) -> Option<i64> {
+  val.rebalance_to_exponent(exp).ok().and_then(|val| val.base.to_i64())
+}
+
+fn zigzag(val:i64) -> u128 {
+  ((val << 1) ^ (val >> 63)) as u64 as u128
+}
+
+fn unzigzag(val:u64) -> i64 {
+  ((val >> 1) as i64) ^ -((val & 1) as i64)
+}
+
+fn push_varint(bytes:&mut Vec<u8>, mut val:u128) {
+  while val >= 0x80 {
+    bytes.push((val as u8) | 0x80);
+    val >>= 7;
+  }
+  bytes.push(val as u8);
+}
+
+#[cfg(test)]
+mod test{
+  use super::{TelemetryEncoder, TelemetryDecoder, decode_stream};
+  use super::super::{SciValue, PrecisionLoss};
+  use super::super::binary::DecodeError;
+
+  #[test]
+  fn stream_layout(){
+    let mut encoder = TelemetryEncoder::new(-2i32).unwrap();
+    encoder.push(&SciValue::wrap_with_exponent(1234i64, -2)).unwrap();
+    encoder.push(&SciValue::wrap_with_exponent(123i64, -1)).unwrap();
+    encoder.push(&SciValue::wrap_with_exponent(1i64, -5)).unwrap();
+    //reset to -2, 1234, -4, reset to -5, 1
+    assert_eq!(encoder.finish(), vec![0x07, 0xC8, 0x26, 0x0E, 0x13, 0x04]);
+  }
+
+  #[test]
+  fn round_trip(){
+    let values = [
+      SciValue::wrap_with_exponent(2150i64, -3i16),
+      SciValue::wrap_with_exponent(-215i64, -2),
+      SciValue::wrap_with_exponent(7i64, -9),
+      SciValue::wrap_with_exponent(3i64, -9),
+      SciValue::wrap_with_exponent(i64::MIN, 0),
+      SciValue::wrap_with_exponent(4i64, 1)
+    ];
+    let mut encoder = TelemetryEncoder::new(-3i16).unwrap();
+    for val in values.iter() {
+      encoder.push(val).unwrap();
+    }
+    let decoded: Vec<SciValue<i64,i16>> = decode_stream(encoder.as_bytes()).unwrap();
+    assert_eq!(decoded, vec![
+      SciValue::wrap_with_exponent(2150i64, -3i16),
+      SciValue::wrap_with_exponent(-2150i64, -3),
+      SciValue::wrap_with_exponent(7i64, -9),
+      SciValue::wrap_with_exponent(3i64, -9),
+      SciValue::wrap_with_exponent(i64::MIN, 0),
+      SciValue::wrap_with_exponent(40i64, 0)
+    ]);
+  }
+
+  #[test]
+  fn decode_errors(){
+    let mut decoder: TelemetryDecoder<i64,i8> = TelemetryDecoder::new(&[0x01, 0x88]);
+    assert_eq!(decoder.next(), Some(Err(DecodeError::Truncated)));
+    assert_eq!(decoder.next(), None);
+    assert_eq!(decode_stream::<i8,i8>(&[0x01, 0xC8, 0x26]), Err(DecodeError::OutOfRange));
+    assert_eq!(decode_stream::<i8,i8>(&[0x04]), Err(DecodeError::OutOfRange));
+  }
+
+  #[test]
+  fn wide_exponents(){
+    assert_eq!(TelemetryEncoder::<i64,i128>::new(1i128 << 80).err(), Some(PrecisionLoss::Overflow));
+
+    let mut encoder = TelemetryEncoder::<i64,i128>::new(0).unwrap();
+    encoder.push(&SciValue::wrap(5)).unwrap();
+    let before = encoder.as_bytes().to_vec();
+    assert_eq!(encoder.push(&SciValue::wrap_with_exponent(3, 1i128 << 80)), Err(PrecisionLoss::Overflow));
+    assert_eq!(encoder.finish(), before);
+  }
+}
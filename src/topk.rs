@@ -0,0 +1,118 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::collections::BinaryHeap;
+use std::cmp::{Ordering, Reverse};
+
+use super::{SciValue, ByMagnitude};
+
+/**
+ * Keeps the `k` values of largest magnitude seen in a
+ * stream, regardless of their sign, without holding on
+ * to the rest.
+ *
+ * Values are ranked by `cmp_magnitude`, so `-9e3` beats
+ * `5e3` and `20e2` ties with `2e3`.  Ties with the
+ * smallest value kept don't displace it.
+ */
+pub struct TopK<B, E:PrimInt + Signed> {
+  k: usize,
+  heap: BinaryHeap<Reverse<ByMagnitude<B,E>>>
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> TopK<B,E> {
+  /// Creates an empty tracker keeping at most `k` values.
+  pub fn new(k:usize) -> TopK<B,E> {
+    TopK{k, heap: BinaryHeap::with_capacity(k)}
+  }
+
+  pub fn insert(&mut self, val:&SciValue<B,E>) {
+    if self.heap.len() < self.k {
+      self.heap.push(Reverse(ByMagnitude(*val)));
+      return;
+    }
+    let displaces = match self.heap.peek() {
+      Some(smallest) => val.cmp_magnitude(&(smallest.0).0) == Ordering::Greater,
+      None           => false
+    };
+    if displaces {
+      self.heap.pop();
+      self.heap.push(Reverse(ByMagnitude(*val)));
+    }
+  }
+
+  /// The number of values currently kept.
+  pub fn len(&self) -> usize {
+    self.heap.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+
+  /**
+   * The smallest magnitude value kept, which a new value
+   * has to exceed to be kept once the tracker is full.
+   */
+  pub fn threshold(&self) -> Option<&SciValue<B,E>> {
+    self.heap.peek().map(|smallest| &(smallest.0).0)
+  }
+
+  /// The values kept, largest magnitude first.
+  pub fn values(&self) -> Vec<SciValue<B,E>> {
+    self.heap.clone().into_sorted_vec().into_iter().map(|entry| (entry.0).0).collect()
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::TopK;
+  use super::super::SciValue;
+
+  #[test]
+  fn keeps_largest_magnitudes(){
+    let mut top = TopK::new(3);
+    for &(base, exp) in [(5i32, 3isize), (-9, 3), (1, 0), (7, 2), (20, 2), (-4, 4), (3, 1)].iter() {
+      top.insert(&SciValue::wrap_with_exponent(base, exp));
+    }
+
+    assert_eq!(top.len(), 3);
+    assert_eq!(top.values(), vec![SciValue::wrap_with_exponent(-4, 4isize),
+                                  SciValue::wrap_with_exponent(-9, 3isize),
+                                  SciValue::wrap_with_exponent(5, 3isize)]);
+    assert_eq!(top.threshold(), Some(&SciValue::wrap_with_exponent(5, 3isize)));
+
+    //A tie with the threshold doesn't displace it
+    top.insert(&SciValue::wrap_with_exponent(50, 2isize));
+    assert_eq!(top.threshold(), Some(&SciValue::wrap_with_exponent(5, 3isize)));
+  }
+
+  #[test]
+  fn empty_tracker(){
+    let mut top: TopK<i32,isize> = TopK::new(0);
+    top.insert(&SciValue::wrap(5));
+    assert!(top.is_empty());
+    assert_eq!(top.threshold(), None);
+    assert_eq!(top.values(), vec![]);
+  }
+}
@@ -0,0 +1,287 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive, Zero};
+use std::fmt::{Debug, Formatter, Error};
+use std::ops::{Add, Sub, Mul, Neg};
+use std::cmp::Ordering;
+
+use super::{SciValue, RoundingMode, observer};
+use super::observer::OverflowKind;
+
+/**
+ * A value stored as the unevaluated sum of two values,
+ * `hi + lo`, in the manner of double-double arithmetic.
+ *
+ * Adding values whose exponents are far apart can't be
+ * done in a single base - matching the exponents would
+ * overflow it.  Instead of failing, the smaller part is
+ * kept in `lo`, so small residues survive next to large
+ * values, and are folded into `hi` as soon as they fit.
+ * `hi` always has the larger magnitude.
+ *
+ * Only two scales can be held at once.  When a sum spans
+ * three, the smallest part is rounded into the middle
+ * one, which becomes `lo`.
+ */
+pub struct TwoSci<B, E:PrimInt + Signed> {
+  hi: SciValue<B,E>,
+  lo: SciValue<B,E>
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> TwoSci<B,E> {
+  pub fn new(val:SciValue<B,E>) -> TwoSci<B,E> {
+    TwoSci{hi: val, lo: SciValue::zero()}
+  }
+
+  /// The part with the larger magnitude.
+  pub fn hi(&self) -> &SciValue<B,E> {
+    &self.hi
+  }
+
+  /// The residue which couldn't be folded into `hi`.
+  pub fn lo(&self) -> &SciValue<B,E> {
+    &self.lo
+  }
+
+  /**
+   * Collapses to a single value, rounding the residue to
+   * the exponent of `hi` using `mode`.  Returns None if
+   * even the rounded sum doesn't fit.
+   */
+  pub fn to_sci(&self, mode:RoundingMode) -> Option<SciValue<B,E>> {
+    if self.lo.is_zero() {
+      return Some(self.hi);
+    }
+    let rounded = self.lo.round_to_exponent(self.hi.e_exp, mode);
+    if rounded.is_zero() {
+      return Some(self.hi);
+    }
+    self.hi.checked_add(&rounded)
+  }
+
+  /**
+   * Adds the values, returning None only if the middle
+   * part overflows even once rounded.
+   */
+  pub fn checked_add(&self, rhs:&TwoSci<B,E>) -> Option<TwoSci<B,E>> {
+    let mut sum = self.clone();
+    sum.absorb(rhs.hi)?;
+    sum.absorb(rhs.lo)?;
+    Some(sum)
+  }
+
+  /**
+   * Multiplies out all four cross terms, each exactly,
+   * and sums them as `checked_add` does.  Returns None if
+   * a term overflows.
+   */
+  pub fn checked_mul(&self, rhs:&TwoSci<B,E>) -> Option<TwoSci<B,E>> {
+    let mut product = TwoSci::new(SciValue::zero());
+    for lhs_part in [self.hi, self.lo].iter() {
+      for rhs_part in [rhs.hi, rhs.lo].iter() {
+        product.absorb(lhs_part.checked_mul(rhs_part)?)?;
+      }
+    }
+    Some(product)
+  }
+
+  /**
+   * Adds a single value in, exactly if either part can
+   * take it.  Leaves the value unchanged on failure.
+   */
+  fn absorb(&mut self, val:SciValue<B,E>) -> Option<()> {
+    if val.is_zero() {
+      return Some(());
+    }
+    if self.hi.is_zero() {
+      self.hi = val;
+    } else if let Some(sum) = self.hi.checked_add(&val) {
+      self.hi = sum;
+    } else if self.lo.is_zero() {
+      self.lo = val;
+    } else if let Some(sum) = self.lo.checked_add(&val) {
+      self.lo = sum;
+    } else {
+      let mut parts = [self.hi, self.lo, val];
+      parts.sort_by(|lhs, rhs| rhs.cmp_magnitude(lhs));
+      self.lo = fold_rounded(&parts[1], &parts[2])?;
+      self.hi = parts[0];
+    }
+    self.renormalize();
+    Some(())
+  }
+
+  fn renormalize(&mut self) {
+    //Zeros in either part are kept at the canonical 0e0,
+    //whose exponent then can't get in the way of matching
+    if self.lo.is_zero() {
+      self.lo = SciValue::zero();
+    } else if self.hi.is_zero() {
+      self.hi = self.lo;
+      self.lo = SciValue::zero();
+    } else if let Some(sum) = self.hi.checked_add(&self.lo) {
+      self.hi = sum;
+      self.lo = SciValue::zero();
+    } else if self.lo.cmp_magnitude(&self.hi) == Ordering::Greater {
+      std::mem::swap(&mut self.hi, &mut self.lo);
+    }
+    if self.hi.is_zero() {
+      self.hi = SciValue::zero();
+    }
+  }
+}
+
+/**
+ * Adds `small` into `mid`, rounding `small` to the exponent
+ * of `mid` if the exact sum doesn't fit, and both of them
+ * one place further if even that overflows.  Any rounding
+ * which changes a value is reported to the observer.
+ */
+fn fold_rounded<B:PrimInt + FromPrimitive, E:PrimInt + Signed>(mid:&SciValue<B,E>, small:&SciValue<B,E>) -> Option<SciValue<B,E>> {
+  if let Some(sum) = mid.checked_add(small) {
+    return Some(sum);
+  }
+  let changed = |val:&SciValue<B,E>, rounded:&SciValue<B,E>| val.cmp_value(rounded) != Ordering::Equal;
+
+  let small_rounded = small.round_to_exponent(mid.e_exp, RoundingMode::HalfEven);
+  let (sum, lossy) = match mid.checked_add(&small_rounded) {
+    Some(sum) => (sum, changed(small, &small_rounded)),
+    None      => {
+      let coarser = mid.e_exp.checked_add(&E::one())?;
+      let (mid_coarse, small_coarse) = (mid.round_to_exponent(coarser, RoundingMode::HalfEven),
+                                        small.round_to_exponent(coarser, RoundingMode::HalfEven));
+      (mid_coarse.checked_add(&small_coarse)?, changed(mid, &mid_coarse) || changed(small, &small_coarse))
+    }
+  };
+  if lossy {
+    observer::notify(OverflowKind::PrecisionLost, "TwoSci::checked_add");
+  }
+  Some(sum)
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for TwoSci<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    write!(fmtr, "TwoSci{{hi : {:?}, lo : {:?}}}", self.hi, self.lo)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for TwoSci<B,E> {
+  fn clone(&self) -> TwoSci<B,E> {
+    TwoSci{hi: self.hi, lo: self.lo}
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> PartialEq for TwoSci<B,E> {
+  fn eq(&self, rhs:&TwoSci<B,E>) -> bool {
+    self.hi == rhs.hi && self.lo == rhs.lo
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Add for TwoSci<B,E> {
+  type Output = TwoSci<B,E>;
+
+  fn add(self, rhs:TwoSci<B,E>) -> TwoSci<B,E> {
+    self.checked_add(&rhs).expect("TwoSci addition overflowed")
+  }
+}
+
+impl<B:PrimInt + Signed + FromPrimitive, E:PrimInt + Signed> Sub for TwoSci<B,E> {
+  type Output = TwoSci<B,E>;
+
+  fn sub(self, rhs:TwoSci<B,E>) -> TwoSci<B,E> {
+    self + (-rhs)
+  }
+}
+
+impl<B:PrimInt + Signed, E:PrimInt + Signed> Neg for TwoSci<B,E> {
+  type Output = TwoSci<B,E>;
+
+  fn neg(self) -> TwoSci<B,E> {
+    TwoSci{hi: -self.hi, lo: -self.lo}
+  }
+}
+
+/**
+ * Multiplies as `checked_mul` does, panicking if a cross
+ * term overflows.
+ */
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed> Mul for TwoSci<B,E> {
+  type Output = TwoSci<B,E>;
+
+  fn mul(self, rhs:TwoSci<B,E>) -> TwoSci<B,E> {
+    self.checked_mul(&rhs).expect("TwoSci multiplication overflowed")
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::TwoSci;
+  use super::super::{SciValue, RoundingMode};
+
+  #[test]
+  fn keeps_residues(){
+    let large = TwoSci::new(SciValue::wrap_with_exponent(1i32, 20isize));
+    let small = TwoSci::new(SciValue::wrap(1i32));
+    let sum = large.clone() + small.clone();
+    assert_eq!((*sum.hi(), *sum.lo()), (SciValue::wrap_with_exponent(1, 20), SciValue::wrap(1)));
+
+    //The residue cancels back out exactly
+    let back = sum.clone() - large.clone();
+    assert_eq!((*back.hi(), *back.lo()), (SciValue::wrap(1), SciValue::wrap(0)));
+    assert_eq!(sum.to_sci(RoundingMode::HalfEven), Some(SciValue::wrap_with_exponent(1, 20)));
+
+    //Residues fold in once they fit
+    let near = TwoSci::new(SciValue::wrap_with_exponent(3i32, 5isize)) + small.clone();
+    assert_eq!((*near.hi(), *near.lo()), (SciValue::wrap(300001), SciValue::wrap(0)));
+  }
+
+  #[test]
+  fn rounds_a_third_scale(){
+    let large = TwoSci::new(SciValue::wrap_with_exponent(1i32, 20isize));
+    let one = TwoSci::new(SciValue::wrap(1i32));
+    let tiny = TwoSci::new(SciValue::wrap_with_exponent(1i32, -20isize));
+
+    let sum = large.clone() + one.clone() + tiny.clone();
+    assert_eq!((*sum.hi(), *sum.lo()), (SciValue::wrap_with_exponent(1, 20), SciValue::wrap(1)));
+
+    //The smallest part is rounded whichever order they come in
+    let sum = tiny.clone() + one.clone() + large.clone();
+    assert_eq!((*sum.hi(), *sum.lo()), (SciValue::wrap_with_exponent(1, 20), SciValue::wrap(1)));
+    let sum = large.clone() + tiny.clone() + one.clone();
+    assert_eq!((*sum.hi(), *sum.lo()), (SciValue::wrap_with_exponent(1, 20), SciValue::wrap(1)));
+
+    let carried = TwoSci::new(SciValue::wrap(i32::MAX)) + large.clone() + TwoSci::new(SciValue::wrap_with_exponent(6i32, -1isize));
+    assert_eq!((*carried.hi(), *carried.lo()), (SciValue::wrap_with_exponent(1, 20), SciValue::wrap_with_exponent(214748365, 1)));
+  }
+
+  #[test]
+  fn multiplies_cross_terms(){
+    let val = TwoSci::new(SciValue::wrap_with_exponent(1i32, 20isize)) + TwoSci::new(SciValue::wrap(2i32));
+    let product = val * TwoSci::new(SciValue::wrap(3));
+    assert_eq!((*product.hi(), *product.lo()), (SciValue::wrap_with_exponent(3, 20), SciValue::wrap(6)));
+    assert_eq!(product.to_sci(RoundingMode::Ceiling), Some(SciValue::wrap_with_exponent(4, 20)));
+
+    let wide = TwoSci::new(SciValue::wrap_with_exponent(100000i32, 0isize));
+    assert_eq!(wide.checked_mul(&wide), None);
+  }
+}
@@ -0,0 +1,246 @@
+/*
+ * Copyright (c) 2015 Brandon Sanderson
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+ * THE SOFTWARE.
+ *
+ */
+use num_traits::{PrimInt, Signed, FromPrimitive};
+use std::fmt::{Debug, Formatter, Error};
+use std::ops::Deref;
+
+use super::{SciValue, FloatConversion};
+
+/**
+ * A vector of values, with bulk conversions to and from
+ * `f64` slices for handing data to numeric libraries.
+ */
+pub struct SciVec<B, E:PrimInt + Signed> {
+  values: Vec<SciValue<B,E>>
+}
+
+/**
+ * One bit per element of a bulk conversion, set for each
+ * element that couldn't be converted exactly.  Reporting
+ * this way keeps the converted values in one flat vector,
+ * rather than wrapping each in a `Result`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementFlags {
+  words: Vec<u64>,
+  len: usize
+}
+
+impl ElementFlags {
+  fn new(len:usize) -> ElementFlags {
+    ElementFlags{words: vec![0; len.div_ceil(64)], len}
+  }
+
+  fn set(&mut self, idx:usize) {
+    self.words[idx / 64] |= 1 << (idx % 64);
+  }
+
+  /// The number of elements covered.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Whether no elements are covered.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /**
+   * Whether the element at `idx` was flagged.  Panics if
+   * `idx` is out of range.
+   */
+  pub fn get(&self, idx:usize) -> bool {
+    assert!(idx < self.len, "Element index out of range");
+    self.words[idx / 64] & (1 << (idx % 64)) != 0
+  }
+
+  /// Whether any element was flagged.
+  pub fn any(&self) -> bool {
+    self.words.iter().any(|word| *word != 0)
+  }
+
+  /// The number of flagged elements.
+  pub fn count(&self) -> usize {
+    self.words.iter().map(|word| word.count_ones() as usize).sum()
+  }
+
+  /// The indices of the flagged elements, in order.
+  pub fn indices(&self) -> impl Iterator<Item=usize> + '_ {
+    self.words.iter().enumerate().flat_map(|(word_idx, word)| {
+      let mut rest = *word;
+      std::iter::from_fn(move || {
+        if rest == 0 {
+          return None;
+        }
+        let bit = rest.trailing_zeros() as usize;
+        rest &= rest - 1;
+        Some(word_idx * 64 + bit)
+      })
+    })
+  }
+
+  /// The flags as packed words, element 0 in the lowest bit.
+  pub fn as_words(&self) -> &[u64] {
+    &self.words
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> SciVec<B,E> {
+  /// An empty vector.
+  pub fn new() -> SciVec<B,E> {
+    SciVec{values: Vec::new()}
+  }
+
+  /// Adds a value to the end of the vector.
+  pub fn push(&mut self, val:SciValue<B,E>) {
+    self.values.push(val);
+  }
+
+  /// The values, as a plain `Vec`.
+  pub fn into_vec(self) -> Vec<SciValue<B,E>> {
+    self.values
+  }
+}
+
+impl<B:PrimInt + FromPrimitive, E:PrimInt + Signed + FromPrimitive> SciVec<B,E> {
+  /**
+   * Converts every value to its nearest `f64`.  The flags
+   * mark each value which was rounded, overflowed to an
+   * infinity or underflowed to zero.
+   */
+  pub fn to_f64_vec(&self) -> (Vec<f64>, ElementFlags) {
+    let mut flags = ElementFlags::new(self.values.len());
+    let floats = self.values.iter().enumerate().map(|(idx, val)| {
+      let (float, status) = val.to_f64_with_status();
+      if status != FloatConversion::Exact {
+        flags.set(idx);
+      }
+      float
+    }).collect();
+    (floats, flags)
+  }
+
+  /**
+   * Converts every float to the exact value it holds, as
+   * `SciValue::from_f64_exact` does.  Floats which can't be
+   * converted - non-finite ones, or ones whose exact value
+   * doesn't fit the types - become zero and are flagged.
+   */
+  pub fn from_f64_slice(floats:&[f64]) -> (SciVec<B,E>, ElementFlags) {
+    let mut flags = ElementFlags::new(floats.len());
+    let values = floats.iter().enumerate().map(|(idx, float)| {
+      SciValue::from_f64_exact(*float).unwrap_or_else(|_| {
+        flags.set(idx);
+        SciValue::wrap_with_exponent(B::zero(), E::zero())
+      })
+    }).collect();
+    (SciVec{values}, flags)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Default for SciVec<B,E> {
+  fn default() -> SciVec<B,E> {
+    SciVec::new()
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> From<Vec<SciValue<B,E>>> for SciVec<B,E> {
+  fn from(values:Vec<SciValue<B,E>>) -> SciVec<B,E> {
+    SciVec{values}
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> FromIterator<SciValue<B,E>> for SciVec<B,E> {
+  fn from_iter<I:IntoIterator<Item=SciValue<B,E>>>(iter:I) -> SciVec<B,E> {
+    SciVec{values: iter.into_iter().collect()}
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Deref for SciVec<B,E> {
+  type Target = [SciValue<B,E>];
+
+  fn deref(&self) -> &[SciValue<B,E>] {
+    &self.values
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> Clone for SciVec<B,E> {
+  fn clone(&self) -> SciVec<B,E> {
+    SciVec{values: self.values.clone()}
+  }
+}
+
+impl<B:PrimInt + Debug, E:PrimInt + Signed + Debug> Debug for SciVec<B,E> {
+  fn fmt(&self, fmtr:&mut Formatter) -> Result<(),Error> {
+    write!(fmtr, "SciVec{:?}", self.values)
+  }
+}
+
+impl<B:PrimInt, E:PrimInt + Signed> PartialEq for SciVec<B,E> {
+  fn eq(&self, rhs:&SciVec<B,E>) -> bool {
+    self.values == rhs.values
+  }
+}
+
+#[cfg(test)]
+mod test{
+  use super::SciVec;
+  use super::super::SciValue;
+
+  #[test]
+  fn bulk_to_floats(){
+    let values: SciVec<i64,i32> = vec![SciValue::wrap_with_exponent(15, -1),
+                                       SciValue::wrap_with_exponent(1, -1),
+                                       SciValue::wrap_with_exponent(-3, 2),
+                                       SciValue::wrap_with_exponent(1, 400),
+                                       SciValue::wrap_with_exponent(1, -400)].into();
+    let (floats, flags) = values.to_f64_vec();
+    assert_eq!(floats, vec![1.5, 0.1, -300.0, f64::INFINITY, 0.0]);
+    assert_eq!(flags.len(), 5);
+    assert!(!flags.get(0) && flags.get(1) && !flags.get(2));
+    assert_eq!(flags.indices().collect::<Vec<_>>(), vec![1, 3, 4]);
+    assert_eq!(flags.count(), 3);
+
+    let (floats, flags) = SciVec::<i64,i32>::new().to_f64_vec();
+    assert!(floats.is_empty() && flags.is_empty() && !flags.any());
+  }
+
+  #[test]
+  fn bulk_from_floats(){
+    let mut floats: Vec<f64> = (0..130).map(|idx| idx as f64 * 0.5).collect();
+    floats[3] = f64::NAN;
+    floats[64] = 0.1;
+    floats[129] = f64::NEG_INFINITY;
+    let (values, flags) = SciVec::<i64,i32>::from_f64_slice(&floats);
+    assert_eq!(values.len(), 130);
+    assert_eq!(values[2], SciValue::wrap(1));
+    assert_eq!(values[3], SciValue::wrap(0));
+    assert_eq!(values[5], SciValue::wrap_with_exponent(25, -1));
+    assert_eq!(flags.indices().collect::<Vec<_>>(), vec![3, 64, 129]);
+    assert_eq!(flags.as_words().len(), 3);
+
+    let (values, flags) = SciVec::<u8,i8>::from_f64_slice(&[-1.0, 250.0, 1e3]);
+    assert_eq!(values.into_vec(), vec![SciValue::wrap(0), SciValue::wrap_with_exponent(25, 1), SciValue::wrap_with_exponent(1, 3)]);
+    assert_eq!(flags.indices().collect::<Vec<_>>(), vec![0]);
+  }
+}